@@ -1,9 +1,43 @@
+#[cfg(windows)]
 extern crate winres;
 
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// 内置字体的位置（相对于 crate 根目录），见 `assets/fonts/README.md`
+const EMBEDDED_FONT_PATH: &str = "assets/fonts/embedded_cjk.ttf";
+
 fn main() {
-    if cfg!(target_os = "windows") {
+    embed_font();
+
+    #[cfg(windows)]
+    {
         let mut res = winres::WindowsResource::new();
         res.set_icon("assets/icon.ico");
         res.compile().unwrap();
     }
 }
+
+/// 生成 `OUT_DIR/embedded_font.rs`，定义 `EMBEDDED_FONT` 常量
+/// 启用 `embedded-font` 特性时把字体文件编译进程序；文件不存在时给出说明并终止构建，
+/// 而不是留给 `include_bytes!` 报一个难以理解的路径错误
+fn embed_font() {
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("embedded_font.rs");
+    let value = if env::var_os("CARGO_FEATURE_EMBEDDED_FONT").is_some() {
+        let font = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join(EMBEDDED_FONT_PATH);
+        println!("cargo:rerun-if-changed={}", font.display());
+        if !font.is_file() {
+            eprintln!(
+                "error: 启用了 `embedded-font` 特性，但找不到字体文件 {}\n\
+                 请放入一个许可证允许再分发的中文字体（如 Noto Sans SC），详见 assets/fonts/README.md",
+                font.display()
+            );
+            std::process::exit(1);
+        }
+        format!("Some(include_bytes!({:?}))", font)
+    } else {
+        "None".to_string()
+    };
+    fs::write(out, format!("const EMBEDDED_FONT: Option<&[u8]> = {};\n", value)).unwrap();
+}
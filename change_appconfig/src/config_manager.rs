@@ -1,3 +1,4 @@
+use config_core::appid;
 use serde::{Deserialize, Serialize};
 
 /// 应用程序配置结构体
@@ -14,6 +15,18 @@ pub struct AppConfig {
     pub appname: String,      
 }
 
+/// 界面中显示的 appid 的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AppIdSource {
+    /// JSON 和 JS 中都没有找到
+    #[default]
+    None,
+    /// 来自 project.config.json 的 "appid"
+    Json,
+    /// JSON 中为空，回退使用 JS 文件中的 "appId"
+    Js,
+}
+
 impl AppConfig {
     /// 创建一个新的默认 AppConfig 实例
     pub fn new() -> Self {
@@ -25,4 +38,94 @@ impl AppConfig {
     pub fn is_empty(&self) -> bool {
         self.appid.is_empty() && self.app_id.is_empty() && self.douyin_ids.is_empty() && self.appname.is_empty()
     }
+
+    /// 与加载时的配置逐字段比较，判断是否有未应用的修改
+    /// app_id 在应用修改时由 appid 同步得出，不单独比较
+    pub fn differs_from(&self, original: &AppConfig) -> bool {
+        self.appid != original.appid
+            || self.douyin_ids != original.douyin_ids
+            || self.appname != original.appname
+    }
+
+    /// 确定界面显示的 appid
+    /// JSON 中的 appid 为空而 JS 中存在 appId 时，使用 JS 的值，以便用户有可编辑的初始值
+    ///
+    /// # 返回值
+    /// * `AppIdSource` - appid 的来源
+    pub fn resolve_appid(&mut self) -> AppIdSource {
+        if !self.appid.is_empty() {
+            AppIdSource::Json
+        } else if !self.app_id.is_empty() {
+            self.appid = self.app_id.clone();
+            AppIdSource::Js
+        } else {
+            AppIdSource::None
+        }
+    }
+
+    /// 校验要写入的 appid 格式（`tt` 前缀 + 字母数字，长度见 `config_core::appid::APPID_LEN_RANGE`）
+    /// 与应用修改时一致，先去掉两端空白再校验；为空时不写入 appid，视为合法
+    ///
+    /// # 返回值
+    /// * `Err(String)` - 不符合格式的原因
+    pub fn validate_appid(&self) -> Result<(), String> {
+        appid::validate_appid(self.appid.trim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_differs_from_after_revert() {
+        let original = AppConfig {
+            appid: "tt1".to_string(),
+            appname: "Game".to_string(),
+            ..Default::default()
+        };
+        let mut edited = original.clone();
+        edited.appid = "tt2".to_string();
+        assert!(edited.differs_from(&original));
+        // 改回原值后不再视为修改
+        edited.appid = "tt1".to_string();
+        assert!(!edited.differs_from(&original));
+    }
+
+    #[test]
+    fn test_validate_appid() {
+        let mut config = AppConfig::new();
+        assert!(config.validate_appid().is_ok());
+        config.appid = " tt1a2b3c4d5e6f7a8b01 ".to_string();
+        assert!(config.validate_appid().is_ok());
+        config.appid = "1a2b3c4d5e6f7a8b0101".to_string();
+        assert!(config.validate_appid().is_err());
+        config.appid = "tt_json".to_string();
+        assert!(config.validate_appid().is_err());
+    }
+
+    #[test]
+    fn test_resolve_appid_prefers_json() {
+        let mut config = AppConfig {
+            appid: "tt_json".to_string(),
+            app_id: "tt_js".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(config.resolve_appid(), AppIdSource::Json);
+        assert_eq!(config.appid, "tt_json");
+    }
+
+    #[test]
+    fn test_resolve_appid_falls_back_to_js() {
+        let mut config = AppConfig {
+            app_id: "tt_js".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(config.resolve_appid(), AppIdSource::Js);
+        assert_eq!(config.appid, "tt_js");
+        assert_eq!(config.app_id, "tt_js");
+
+        let mut empty = AppConfig::new();
+        assert_eq!(empty.resolve_appid(), AppIdSource::None);
+    }
 }
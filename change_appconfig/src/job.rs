@@ -0,0 +1,216 @@
+use anyhow::{bail, Context, Result};
+use config_core::apply::apply_to_dir;
+use config_core::atomic::atomic_write;
+use config_core::backup::Backups;
+use config_core::config_file::read_config;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// 任务文件（JSON / YAML / TOML，按扩展名识别）
+///
+/// ```json
+/// { "entries": [ { "dir": "games/a", "appid": "tt1", "name": "游戏A", "douyinIds": ["id1"] } ] }
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct JobFile {
+    /// JS 中 AppID 的字段名，未填写时为 `appId`
+    #[serde(rename = "appIdField", default)]
+    pub app_id_field: String,
+    pub entries: Vec<JobEntry>,
+}
+
+/// 任务文件中的一项修改，未填写的字段保持不变
+#[derive(Debug, Deserialize)]
+pub struct JobEntry {
+    /// 项目目录（project.config.json 所在目录），相对路径以任务文件所在目录为基准
+    pub dir: PathBuf,
+    pub appid: Option<String>,
+    #[serde(alias = "projectname")]
+    pub name: Option<String>,
+    #[serde(rename = "douyinIds")]
+    pub douyin_ids: Option<Vec<String>>,
+}
+
+/// 单项修改的执行结果
+#[derive(Debug, Serialize)]
+pub struct EntryResult {
+    pub dir: PathBuf,
+    /// 内容发生变化的文件
+    pub changed_files: Vec<PathBuf>,
+    /// 失败原因，为空表示成功
+    pub errors: Vec<String>,
+}
+
+/// 读取任务文件
+pub fn read_job(path: &Path) -> Result<JobFile> {
+    read_config(path).with_context(|| format!("无法读取任务文件 {}", path.display()))
+}
+
+/// 依次执行任务文件中的所有修改，单项失败不影响后续项
+///
+/// 被修改的文件会先备份为 `.bak`；已有较新的备份时无法询问用户，保留原备份
+pub fn run_job(job: &JobFile, base_dir: &Path) -> Vec<EntryResult> {
+    let mut backups = Backups::default();
+    job.entries
+        .iter()
+        .map(|entry| {
+            let dir = base_dir.join(&entry.dir);
+            let mut result = EntryResult {
+                dir: dir.clone(),
+                changed_files: Vec::new(),
+                errors: Vec::new(),
+            };
+            if !dir.is_dir() {
+                result.errors.push("目录不存在".to_string());
+                return result;
+            }
+            match apply_to_dir(
+                &dir,
+                &job.app_id_field,
+                entry.appid.as_deref(),
+                entry.name.as_deref(),
+                entry.douyin_ids.as_deref(),
+                Some(&mut backups),
+            ) {
+                Ok(report) => {
+                    result.changed_files = report.changed_files;
+                    result.errors = report
+                        .failed_files
+                        .iter()
+                        .map(|(file, e)| format!("{}: {}", file.display(), e))
+                        .collect();
+                    // 要求修改的 JS 字段在任何文件中都不存在时，该字段没有被写入，视为失败
+                    result.errors.extend(report.missing_fields.iter().map(|e| e.to_string()));
+                }
+                Err(e) => result.errors.push(e.to_string()),
+            }
+            result
+        })
+        .collect()
+}
+
+/// 解析命令行参数，存在 `--job <任务文件>` 时以无界面模式执行
+///
+/// 每项结果输出到标准输出；指定 `--report <文件>` 时同时写出 JSON 报告。
+///
+/// # 返回值
+/// * `Option<i32>` - 未指定 `--job` 时返回 None（正常启动界面），否则为进程退出码
+pub fn run_from_args(args: impl IntoIterator<Item = String>) -> Option<i32> {
+    let mut job_path = None;
+    let mut report_path = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--job" => job_path = args.next().map(PathBuf::from),
+            "--report" => report_path = args.next().map(PathBuf::from),
+            _ => {}
+        }
+    }
+    let job_path = job_path?;
+    attach_parent_console();
+    match run_headless(&job_path, report_path.as_deref()) {
+        Ok(true) => Some(0),
+        Ok(false) => Some(1),
+        Err(e) => {
+            eprintln!("{:#}", e);
+            Some(2)
+        }
+    }
+}
+
+/// Release 版本使用 windows 子系统，没有自己的控制台，输出默认不可见
+/// 无界面模式下附加到启动程序的终端，使每项结果和错误信息显示在终端中；
+/// 输出已被重定向（如 CI 中写入日志文件）或不是从终端启动时保持原样
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // SAFETY: AttachConsole 不涉及任何指针，失败时只返回 0，不影响后续执行
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
+
+/// 执行任务文件并输出报告
+///
+/// # 返回值
+/// * `Result<bool>` - 是否所有项都执行成功
+fn run_headless(job_path: &Path, report_path: Option<&Path>) -> Result<bool> {
+    let job = read_job(job_path)?;
+    if job.entries.is_empty() {
+        bail!("任务文件 {} 中没有任何修改项", job_path.display());
+    }
+    let base_dir = job_path.parent().unwrap_or(Path::new("."));
+    let results = run_job(&job, base_dir);
+
+    for result in &results {
+        if result.errors.is_empty() {
+            println!("[成功] {}: 修改了 {} 个文件", result.dir.display(), result.changed_files.len());
+        } else {
+            println!("[失败] {}: {}", result.dir.display(), result.errors.join("; "));
+        }
+    }
+    let failed = results.iter().filter(|r| !r.errors.is_empty()).count();
+    println!("共 {} 项，成功 {}，失败 {}", results.len(), results.len() - failed, failed);
+
+    if let Some(path) = report_path {
+        let json = serde_json::to_string_pretty(&results)?;
+        atomic_write(path, json).with_context(|| format!("无法写入报告 {}", path.display()))?;
+    }
+    Ok(failed == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_run_job() {
+        let dir = std::env::temp_dir().join("bytegame_job_test");
+        if dir.exists() {
+            fs::remove_dir_all(&dir).unwrap();
+        }
+        fs::create_dir_all(dir.join("game_a")).unwrap();
+        fs::write(dir.join("game_a").join("project.config.json"), r#"{"appid": "tt_old", "projectname": "A"}"#).unwrap();
+        fs::write(dir.join("game_a").join("index.js"), r#"d.appId="tt_old",d.douyinIds=["x"];"#).unwrap();
+        fs::create_dir_all(dir.join("game_b")).unwrap();
+        fs::write(dir.join("game_b").join("index.js"), r#"d.appId="tt_old";"#).unwrap();
+        let job_path = dir.join("job.toml");
+        fs::write(
+            &job_path,
+            "[[entries]]\ndir = \"game_a\"\nappid = \"tt_new\"\ndouyinIds = [\"y\", \"z\"]\n\n[[entries]]\ndir = \"missing\"\nname = \"B\"\n\n[[entries]]\ndir = \"game_b\"\nappid = \"tt_new\"\ndouyinIds = [\"y\"]\n",
+        )
+        .unwrap();
+
+        let job = read_job(&job_path).unwrap();
+        let results = run_job(&job, &dir);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].changed_files.len(), 2);
+        assert!(results[0].errors.is_empty());
+        assert_eq!(results[1].errors, vec!["目录不存在"]);
+        // 没有 douyinIds 数组的项目：AppID 照常写入，缺少的字段记为失败
+        assert_eq!(results[2].changed_files.len(), 1);
+        assert_eq!(results[2].errors.len(), 1);
+        assert!(results[2].errors[0].contains("douyinIds"));
+        assert_eq!(
+            fs::read_to_string(dir.join("game_a").join("index.js")).unwrap(),
+            r#"d.appId="tt_new",d.douyinIds=["y","z"];"#
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("game_a").join("index.js.bak")).unwrap(),
+            r#"d.appId="tt_old",d.douyinIds=["x"];"#
+        );
+
+        // 未指定 --job 时正常启动界面
+        assert_eq!(run_from_args(vec!["--report".to_string(), "r.json".to_string()]), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
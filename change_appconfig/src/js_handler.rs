@@ -1,22 +1,23 @@
 use crate::config_manager::AppConfig;
-use anyhow::{Context, Result};
-use regex::Regex;
-use std::fs;
+use anyhow::Result;
+use config_core::js_patch::{find_app_id, find_id_arrays, parse_id_list, unescape_js_string, DOUYIN_IDS_FIELD};
+use config_core::encoding::read_text_detect;
 
 /// 读取 JS 配置文件
-/// 从 JS 文件内容中提取 appId 和 douyinIds
+/// 从 JS 文件内容中提取 AppID 字段和 douyinIds
 ///
 /// # 参数
 /// * `path` - JS 文件的路径
+/// * `app_id_field` - AppID 的字段名（如 `appId`、`applicationId`）
 ///
 /// # 返回值
 /// * `Result<AppConfig>` - 包含提取出的配置信息
-pub fn read_js_config(path: &std::path::Path) -> Result<AppConfig> {
-    let content = fs::read_to_string(path)
-        .with_context(|| format!("无法读取文件: {}", path.display()))?;
+pub fn read_js_config(path: &std::path::Path, app_id_field: &str) -> Result<AppConfig> {
+    // 旧版打包产物可能是 GBK 编码
+    let (content, _) = read_text_detect(path)?;
 
     // 提取配置字段
-    let app_id = extract_app_id(&content).unwrap_or_default();
+    let app_id = extract_app_id(&content, app_id_field).unwrap_or_default();
     let douyin_ids = extract_douyin_ids(&content).unwrap_or_default();
 
     Ok(AppConfig {
@@ -27,69 +28,25 @@ pub fn read_js_config(path: &std::path::Path) -> Result<AppConfig> {
     })
 }
 
-/// 将配置写入 JS 文件
-/// 使用正则表达式替换文件中的 appId 和 douyinIds 字段
-///
-/// # 参数
-/// * `path` - JS 文件的路径
-/// * `config` - 包含新值的配置对象
-///
-/// # 返回值
-/// * `Result<bool>` - 如果文件内容被修改返回 true，否则返回 false
-pub fn write_js_config(path: &std::path::Path, config: &AppConfig) -> Result<bool> {
-    let mut content = fs::read_to_string(path)
-        .with_context(|| format!("无法读取文件: {}", path.display()))?;
-
-    let original_content = content.clone();
-
-    // 替换 appId
-    // 匹配模式: appId="xxxx" 或 appId='xxxx'
-    if let Ok(re) = Regex::new(r#"appId\s*=\s*["']([^"']*)["']"#) {
-        let new_val = format!("appId=\"{}\"", config.app_id);
-        if re.is_match(&content) {
-            println!("Replacing appId in {}", path.display());
-            content = re.replace_all(&content, new_val).to_string();
-        } else {
-            // println!("appId pattern not found in {}", path.display());
-        }
-    }
-
-    // 替换 douyinIds
-    // 匹配模式: douyinIds=[xxxx]
-    if let Ok(re) = Regex::new(r#"douyinIds\s*=\s*\[([^\]]*)\]"#) {
-        let new_val = format!("douyinIds=[{}]", config.douyin_ids);
-        if re.is_match(&content) {
-             println!("Replacing douyinIds in {}", path.display());
-             content = re
-                .replace_all(&content, new_val)
-                .to_string();
-        } else {
-            // println!("douyinIds pattern not found in {}", path.display());
-        }
-    }
-
-    // 只有当内容实际发生变化时才写入文件
-    if content != original_content {
-        fs::write(path, &content)
-            .with_context(|| format!("无法写入文件: {}", path.display()))?;
-        Ok(true)
-    } else {
-        Ok(false)
-    }
-}
-
-/// 从内容中提取 appId
-/// 查找 appId="value" 或 appId='value' 的模式
-fn extract_app_id(content: &str) -> Option<String> {
-    let re = Regex::new(r#"appId\s*=\s*["']([^"']*)["']"#).ok()?;
-    re.captures(content)?.get(1).map(|m| m.as_str().to_string())
+/// 从内容中提取 AppID
+/// 查找 .field="value" 或 .field='value' 的模式（与抖音配置编辑器共用 `js_patch`）
+fn extract_app_id(content: &str, field: &str) -> Option<String> {
+    find_app_id(content, field).map(|range| unescape_js_string(&content[range]))
 }
 
-/// 从内容中提取 douyinIds
-/// 查找 douyinIds=[value] 的模式
+/// 从内容中提取 douyinIds 数组，整理为单行的 `"id1","id2"` 形式用于编辑
+/// 查找 .douyinIds=[value] 的模式；跨越多行的数组和末尾多余的逗号都会被整理掉
 fn extract_douyin_ids(content: &str) -> Option<String> {
-    let re = Regex::new(r#"douyinIds\s*=\s*\[([^\]]*)\]"#).ok()?;
-    re.captures(content)?.get(1).map(|m| m.as_str().to_string())
+    find_id_arrays(content)
+        .into_iter()
+        .find(|(name, _)| name == DOUYIN_IDS_FIELD)
+        .map(|(_, range)| {
+            parse_id_list(&content[range])
+                .iter()
+                .map(|id| format!("\"{}\"", id))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
 }
 
 /// 递归查找指定目录下的所有 .js 文件
@@ -100,43 +57,111 @@ fn extract_douyin_ids(content: &str) -> Option<String> {
 /// # 返回值
 /// * `Vec<std::path::PathBuf>` - 所有找到的 .js 文件路径列表
 pub fn find_js_files(dir: &std::path::Path) -> Vec<std::path::PathBuf> {
-    use walkdir::WalkDir;
-
-    WalkDir::new(dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .map(|ext| ext == "js")
-                .unwrap_or(false)
-        })
-        .map(|e| e.path().to_path_buf())
-        .collect()
+    config_core::apply::find_js_files(dir)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use config_core::apply::apply_to_js_file;
+    use config_core::js_patch::{replace_app_id, replace_id_array, DEFAULT_APP_ID_FIELD};
+    use config_core::text::split_douyin_ids;
+    use std::fs;
 
     #[test]
     fn test_extract_app_id() {
-        let content = r#"appId="test123""#;
-        assert_eq!(extract_app_id(content), Some("test123".to_string()));
+        let content = r#"d.appId="test123""#;
+        assert_eq!(extract_app_id(content, DEFAULT_APP_ID_FIELD), Some("test123".to_string()));
+        // 自定义字段名
+        let content = r#"d.applicationId='tt9',d.appId="other""#;
+        assert_eq!(extract_app_id(content, "applicationId"), Some("tt9".to_string()));
     }
 
     #[test]
     fn test_extract_douyin_ids() {
-        let content = r#"douyinIds=["id1","id2"]"#;
+        let content = r#"d.douyinIds=["id1","id2"]"#;
         assert_eq!(extract_douyin_ids(content), Some("\"id1\",\"id2\"".to_string()));
+
+        // 多行数组和末尾逗号
+        let content = "d.douyinIds=[\n  'id1',\n  'id2',\n]";
+        assert_eq!(extract_douyin_ids(content), Some("\"id1\",\"id2\"".to_string()));
+    }
+
+    #[test]
+    fn test_same_result_as_douyin_editor() {
+        // 与 douyin_config 中 test_same_result_as_bytegame_editor 使用相同的输入和期望结果
+        let dir = std::env::temp_dir().join("bytegame_js_shared_fixture_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.js");
+        fs::write(&path, r#"var myappId="keep";d.appId='tt_old',d.douyinIds=["a", "b"],d.rewardIds=["r1"];"#).unwrap();
+
+        let config = read_js_config(&path, DEFAULT_APP_ID_FIELD).unwrap();
+        assert_eq!(config.app_id, "tt_old");
+        assert_eq!(split_douyin_ids(&config.douyin_ids), vec!["a", "b"]);
+
+        let ids = vec!["c".to_string(), "d".to_string()];
+        assert!(apply_to_js_file(&path, DEFAULT_APP_ID_FIELD, Some("tt_new"), Some(&ids), None).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            r#"var myappId="keep";d.appId='tt_new',d.douyinIds=["c", "d"],d.rewardIds=["r1"];"#
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unicode_round_trip() {
+        let dir = std::env::temp_dir().join("bytegame_js_unicode_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.js");
+        fs::write(&path, r#"var s="中文";d.appId="tt旧",d.douyinIds=["抖音1"];// 注释🎮"#).unwrap();
+
+        let config = AppConfig {
+            appid: String::new(),
+            app_id: "tt新🎮e\u{301}".to_string(),
+            douyin_ids: "\"抖音🎵\",\"id2\"".to_string(),
+            appname: String::new(),
+        };
+        let ids = split_douyin_ids(&config.douyin_ids);
+        assert!(apply_to_js_file(&path, DEFAULT_APP_ID_FIELD, Some(&config.app_id), Some(&ids), None).unwrap());
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "var s=\"中文\";d.appId=\"tt新🎮e\u{301}\",d.douyinIds=[\"抖音🎵\",\"id2\"];// 注释🎮"
+        );
+        let reloaded = read_js_config(&path, DEFAULT_APP_ID_FIELD).unwrap();
+        assert_eq!(reloaded.app_id, config.app_id);
+        assert_eq!(reloaded.douyin_ids, config.douyin_ids);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_app_id_only_keeps_douyin_ids() {
+        let dir = std::env::temp_dir().join("bytegame_js_app_id_only_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.js");
+        fs::write(&path, r#"d.appId="tt_old",d.douyinIds=["id1","id2"];"#).unwrap();
+
+        // 只编辑了 appId，douyinIds 不写入
+        assert!(apply_to_js_file(&path, DEFAULT_APP_ID_FIELD, Some("tt_new"), None, None).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            r#"d.appId="tt_new",d.douyinIds=["id1","id2"];"#
+        );
+
+        // 两个字段都不写入时文件不变
+        assert!(!apply_to_js_file(&path, DEFAULT_APP_ID_FIELD, None, None, None).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 
     #[test]
     fn test_extract_real_content() {
         // 测试真实场景下的代码片段
         let content = r#"d.rewardVideoAd=void 0,d.nowid=0,d.appId="appId",d.douyinIds=["id1","id2"],e._RF.pop()"#;
-        assert_eq!(extract_app_id(content), Some("appId".to_string()));
+        assert_eq!(extract_app_id(content, DEFAULT_APP_ID_FIELD), Some("appId".to_string()));
         assert_eq!(extract_douyin_ids(content), Some("\"id1\",\"id2\"".to_string()));
         
         let mut new_content = content.to_string();
@@ -147,15 +172,22 @@ mod tests {
             appname: "".to_string(),
         };
         
-        // 手动应用写入逻辑进行测试
-         if let Ok(re) = Regex::new(r#"appId\s*=\s*["']([^"']*)["']"#) {
-            new_content = re.replace_all(&new_content, format!("appId=\"{}\"", config.app_id)).to_string();
-        }
-        if let Ok(re) = Regex::new(r#"douyinIds\s*=\s*\[([^\]]*)\]"#) {
-            new_content = re.replace_all(&new_content, format!("douyinIds=[{}]", config.douyin_ids)).to_string();
-        }
+        // 使用保存时相同的替换函数
+        new_content = replace_app_id(&new_content, DEFAULT_APP_ID_FIELD, &config.app_id);
+        new_content = replace_id_array(&new_content, DOUYIN_IDS_FIELD, &split_douyin_ids(&config.douyin_ids));
         
         assert!(new_content.contains(r#"d.appId="new_app_id""#));
         assert!(new_content.contains(r#"d.douyinIds=["new_id1","new_id2"]"#));
     }
+
+    #[test]
+    fn test_write_leaves_decoy_fields() {
+        // 名称中包含 appId 的其它字段不能被替换
+        let content = r#"likeNotAppId="x",d.someOtherAppId="y",d.myappId="z",d.appId="tt_old""#;
+        assert_eq!(extract_app_id(content, DEFAULT_APP_ID_FIELD), Some("tt_old".to_string()));
+        assert_eq!(
+            replace_app_id(content, DEFAULT_APP_ID_FIELD, "tt_new"),
+            r#"likeNotAppId="x",d.someOtherAppId="y",d.myappId="z",d.appId="tt_new""#
+        );
+    }
 }
@@ -1,24 +1,21 @@
 use crate::config_manager::AppConfig;
-use anyhow::{Context, Result};
+use anyhow::Result;
+use config_core::config_file::read_config;
+use config_core::text::strip_wrapping_quotes;
 use serde_json::Value;
-use std::fs;
 use std::path::Path;
 
-/// 读取 JSON 配置文件 (通常是 project.config.json)
+/// 读取项目配置文件 (project.config.json 或其 YAML / TOML 变体)
+/// 格式由扩展名决定，与 `config_core::apply::apply_to_dir` 写入的文件一致
 ///
 /// # 参数
-/// * `path` - JSON 文件的路径
+/// * `path` - 配置文件的路径
 ///
 /// # 返回值
 /// * `Result<AppConfig>` - 成功则返回包含 appid 和 projectname 的 AppConfig，失败返回错误
-pub fn read_json_config(path: &Path) -> Result<AppConfig> {
-    // 读取文件内容
-    let content = fs::read_to_string(path)
-        .with_context(|| format!("无法读取文件: {}", path.display()))?;
-
-    // 解析 JSON
-    let json: Value = serde_json::from_str(&content)
-        .with_context(|| format!("无法解析 JSON 文件: {}", path.display()))?;
+pub fn read_project_config(path: &Path) -> Result<AppConfig> {
+    // 读取并解析配置
+    let json: Value = read_config(path)?;
 
     // 提取 appid，如果不存在则默认为空字符串
     let appid = json
@@ -28,11 +25,11 @@ pub fn read_json_config(path: &Path) -> Result<AppConfig> {
         .to_string();
 
     // 提取 projectname，如果不存在则默认为空字符串
-    let appname = json
-        .get("projectname")
-        .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .to_string();
+    // 去掉之前错误编辑留下的多余引号（如 "\"Game\""）
+    let appname = strip_wrapping_quotes(
+        json.get("projectname").and_then(|v| v.as_str()).unwrap_or(""),
+    )
+    .to_string();
 
     Ok(AppConfig {
         appid,
@@ -42,77 +39,67 @@ pub fn read_json_config(path: &Path) -> Result<AppConfig> {
     })
 }
 
-/// 写入 JSON 配置文件
-///
-/// # 参数
-/// * `path` - JSON 文件的路径
-/// * `config` - 包含新值的配置对象
-///
-/// # 返回值
-/// * `Result<()>` - 成功返回 Ok(())，失败返回错误
-pub fn write_json_config(path: &Path, config: &AppConfig) -> Result<()> {
-    // 读取现有文件内容
-    let content = fs::read_to_string(path)
-        .with_context(|| format!("无法读取文件: {}", path.display()))?;
-
-    // 解析 JSON
-    let mut json: Value = serde_json::from_str(&content)
-        .with_context(|| format!("无法解析 JSON 文件: {}", path.display()))?;
-
-    // 更新 appid 字段
-    if let Some(appid) = json.get_mut("appid") {
-        *appid = Value::String(config.appid.clone());
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use config_core::apply::apply_to_config_file;
+    use std::fs;
 
-    // 更新 projectname 字段
-    if let Some(projectname) = json.get_mut("projectname") {
-        *projectname = Value::String(config.appname.clone());
+    #[test]
+    fn test_read_project_config() {
+        // 只有 YAML 变体的项目也能读取到项目名
+        let dir = std::env::temp_dir().join("bytegame_yaml_config_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("project.config.yaml");
+        fs::write(&path, "appid: tt1\nprojectname: Game\n").unwrap();
+
+        let config = read_project_config(&path).unwrap();
+        assert_eq!(config.appid, "tt1");
+        assert_eq!(config.appname, "Game");
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 
-    // 序列化回字符串，使用 pretty print 保持格式
-    let new_content = serde_json::to_string_pretty(&json)
-        .with_context(|| "无法序列化 JSON")?;
-
-    // 写入文件
-    fs::write(path, new_content)
-        .with_context(|| format!("无法写入文件: {}", path.display()))?;
-
-    Ok(())
-}
-
-/// 在指定目录中查找 project.config.json 文件
-///
-/// # 参数
-/// * `dir` - 要搜索的目录路径
-///
-/// # 返回值
-/// * `Vec<std::path::PathBuf>` - 找到的文件路径列表（虽然通常只有一个）
-pub fn find_json_files(dir: &Path) -> Vec<std::path::PathBuf> {
-    let mut json_files = Vec::new();
-
-    if let Ok(entries) = fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_file() {
-                if let Some(name) = path.file_name() {
-                    // 只匹配文件名为 project.config.json 的文件
-                    if name == "project.config.json" {
-                        json_files.push(path);
-                    }
-                }
-            }
-        }
+    #[test]
+    fn test_quoted_project_name() {
+        let dir = std::env::temp_dir().join("bytegame_json_quoted_name_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("project.config.json");
+        fs::write(&path, r#"{"appid": "tt1", "projectname": "\"Game\""}"#).unwrap();
+
+        let config = read_project_config(&path).unwrap();
+        assert_eq!(config.appname, "Game");
+
+        // 写回时不会再带上引号，即使输入框中被手动加了引号
+        let mut edited = config.clone();
+        edited.appname = "\"Game 2\"".to_string();
+        apply_to_config_file(&path, Some(&edited.appid), Some(&edited.appname), None).unwrap();
+        let json: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["projectname"], "Game 2");
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 
-    json_files
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
     #[test]
-    fn test_read_json_config() {
-        // 测试用例需要实际的测试文件
+    fn test_unicode_round_trip() {
+        let dir = std::env::temp_dir().join("bytegame_json_unicode_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("project.config.json");
+        fs::write(&path, r#"{"appid": "tt旧", "projectname": "旧游戏", "compileType": "game"}"#).unwrap();
+
+        let mut config = read_project_config(&path).unwrap();
+        assert_eq!(config.appname, "旧游戏");
+
+        config.appid = "tt新🎮e\u{301}".to_string();
+        config.appname = "消消乐🍬 Café\u{301}".to_string();
+        apply_to_config_file(&path, Some(&config.appid), Some(&config.appname), None).unwrap();
+
+        let reloaded = read_project_config(&path).unwrap();
+        assert_eq!(reloaded.appid, config.appid);
+        assert_eq!(reloaded.appname, config.appname);
+        // 非 ASCII 字符原样写入，而不是转义为 \uXXXX
+        assert!(fs::read_to_string(&path).unwrap().contains("消消乐🍬"));
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 }
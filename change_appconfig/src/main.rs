@@ -3,26 +3,35 @@
 use eframe::egui;
 
 mod config_manager;
+mod job;
 mod json_handler;
 mod js_handler;
+mod settings;
 mod ui;
 
+use settings::Settings;
 use ui::BytegameConfigEditor;
 
 /// 程序入口函数
 /// 负责初始化应用程序窗口，加载图标，并启动 GUI
 fn main() -> eframe::Result<()> {
-    // 可选加载图标
-    // 尝试从 assets/icon.png 读取图标数据
-    // 如果读取成功，则转换为 eframe 可用的图标格式
-    let icon = std::fs::read("assets/icon.png")
-        .ok()
-        .and_then(|data| eframe::icon_data::from_png_bytes(&data).ok());
+    // 无界面批量模式：bytegame-config-editor --job <任务文件> [--report <报告文件>]
+    if let Some(code) = job::run_from_args(std::env::args().skip(1)) {
+        std::process::exit(code);
+    }
+
+    // 读取上次保存的设置；窗口大小要在创建窗口前确定，其余设置交给 BytegameConfigEditor::new 应用
+    let settings = Settings::load();
+
+    // 加载图标
+    // 图标在编译时嵌入程序，无论从哪个目录启动都能显示；
+    // 嵌入的图标无法解码时，才尝试读取工作目录下的 assets/icon.png
+    let icon = load_icon();
 
     // 配置原生窗口选项
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([900.0, 700.0]) // 设置初始窗口大小
+            .with_inner_size(settings.window_size) // 恢复上次的窗口大小
             .with_min_inner_size([800.0, 600.0]) // 设置最小窗口大小
             .with_icon(icon.unwrap_or_default()), // 设置窗口图标（如果加载成功）
         ..Default::default()
@@ -35,7 +44,21 @@ fn main() -> eframe::Result<()> {
         Box::new(|cc| {
             // 初始化应用状态
             // cc (CreationContext) 包含 egui 上下文等信息
-            Box::new(BytegameConfigEditor::new(cc))
+            Box::new(BytegameConfigEditor::new(cc, settings))
         }),
     )
 }
+
+/// 编译时嵌入的窗口图标（与 build.rs 中的 exe 图标相同）
+const EMBEDDED_ICON: &[u8] = include_bytes!("../assets/icon.ico");
+
+/// 加载窗口图标，优先使用嵌入的图标
+fn load_icon() -> Option<egui::IconData> {
+    config_core::icon::decode_icon(EMBEDDED_ICON)
+        .map(|(rgba, width, height)| egui::IconData { rgba, width, height })
+        .or_else(|| {
+            std::fs::read("assets/icon.png")
+                .ok()
+                .and_then(|data| eframe::icon_data::from_png_bytes(&data).ok())
+        })
+}
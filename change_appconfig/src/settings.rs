@@ -0,0 +1,70 @@
+use config_core::i18n::Lang;
+use config_core::js_patch::DEFAULT_APP_ID_FIELD;
+use config_core::settings;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 设置文件所在的子目录名（`<系统配置目录>/bytegame-config-editor/settings.toml`）
+const APP_DIR_NAME: &str = "bytegame-config-editor";
+
+/// 默认窗口大小（逻辑像素，不含界面缩放）
+const DEFAULT_WINDOW_SIZE: [f32; 2] = [900.0, 700.0];
+
+/// 需要跨启动保留的设置
+/// 启动时读取一次、退出时写回；缺少的字段取默认值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// 窗口大小（逻辑像素，不含界面缩放）
+    pub window_size: [f32; 2],
+    /// 界面语言
+    pub lang: Lang,
+    /// 界面缩放比例
+    pub ui_scale: f32,
+    /// 上次选择的项目目录，启动时只恢复路径，不自动加载
+    pub last_project_dir: Option<PathBuf>,
+    /// 选择目录后是否立即加载配置
+    pub load_on_pick: bool,
+    /// JS 中 AppID 的字段名
+    pub app_id_field: String,
+    /// 修改文件前是否备份为 `.bak`
+    pub backup_before_save: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            window_size: DEFAULT_WINDOW_SIZE,
+            lang: Lang::default(),
+            ui_scale: 1.0,
+            last_project_dir: None,
+            load_on_pick: true,
+            app_id_field: DEFAULT_APP_ID_FIELD.to_string(),
+            backup_before_save: true,
+        }
+    }
+}
+
+impl Settings {
+    /// 从系统配置目录读取设置
+    /// 文件不存在或无法解析时使用默认值，不影响程序启动
+    pub fn load() -> Self {
+        let Some(path) = settings::settings_path(APP_DIR_NAME) else {
+            return Self::default();
+        };
+        settings::load(&path).unwrap_or_else(|e| {
+            eprintln!("Warning: {}，使用默认设置", e);
+            Self::default()
+        })
+    }
+
+    /// 写入系统配置目录，失败时只打印警告（程序正在退出，无处提示）
+    pub fn save(&self) {
+        let Some(path) = settings::settings_path(APP_DIR_NAME) else {
+            return;
+        };
+        if let Err(e) = settings::save(&path, self) {
+            eprintln!("Warning: {}", e);
+        }
+    }
+}
@@ -1,35 +1,42 @@
-use crate::config_manager::AppConfig;
-use crate::json_handler::{find_json_files, read_json_config, write_json_config};
-use crate::js_handler::{find_js_files, read_js_config, write_js_config};
+use crate::config_manager::{AppConfig, AppIdSource};
+use crate::json_handler::read_project_config;
+use crate::js_handler::{find_js_files, read_js_config};
+use crate::settings::Settings;
+use config_core::apply::{apply_to_dir, find_config_files};
+use config_core::backup::{restore_backup, Backups};
+use config_core::i18n::{self, tr, trf, Lang};
+use config_core::js_patch::DEFAULT_APP_ID_FIELD;
+use config_core::text::split_douyin_ids;
+use config_core::image_probe::{image_size, is_image_file, PREVIEW_WIDTHS};
+use config_core::walk::walk_logged;
 use eframe::egui;
 use std::path::PathBuf;
 
+/// 预览图的最大显示高度
+/// 超高的长图会按比例缩小到此高度内，避免撑破横向预览区
+const PREVIEW_MAX_HEIGHT: f32 = 600.0;
+
+/// 界面缩放比例的可调范围
+const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.75..=2.0;
+
+// 找不到系统中文字体时使用的内置字体 `EMBEDDED_FONT`，由 build.rs 生成
+// 仅在启用 `embedded-font` 特性时编译进程序，避免默认构建的体积增大
+include!(concat!(env!("OUT_DIR"), "/embedded_font.rs"));
+
 /// 设置自定义字体以支持中文显示
-/// 尝试加载 Windows 系统自带的中文字体（微软雅黑、黑体、宋体）
+/// 按操作系统依次尝试系统自带的中文字体（见 `config_core::fonts::FONT_CANDIDATES`），
+/// 都找不到时使用内置字体（需启用 `embedded-font` 特性）
 fn setup_custom_fonts(ctx: &egui::Context) {
     let mut fonts = egui::FontDefinitions::default();
 
-    // 尝试加载系统字体
-    let font_paths = [
-        "c:\\Windows\\Fonts\\msyh.ttc", // 微软雅黑
-        "c:\\Windows\\Fonts\\simhei.ttf", // 黑体
-        "c:\\Windows\\Fonts\\simsun.ttc", // 宋体
-    ];
-
-    let mut font_data = None;
-    for path in font_paths {
-        if let Ok(data) = std::fs::read(path) {
-            font_data = Some(data);
-            break;
-        }
-    }
+    // 尝试加载系统字体，失败时回退到内置字体
+    let font_data = config_core::fonts::load_system_cjk_font()
+        .map(|(_, data)| egui::FontData::from_owned(data))
+        .or_else(|| EMBEDDED_FONT.map(egui::FontData::from_static));
 
-    if let Some(data) = font_data {
+    if let Some(font_data) = font_data {
         // 注册字体数据
-        fonts.font_data.insert(
-            "my_font".to_owned(),
-            egui::FontData::from_owned(data),
-        );
+        fonts.font_data.insert("my_font".to_owned(), font_data);
 
         // 将自定义字体设置为比例字体（Proportional）的首选，用于常规文本显示
         fonts
@@ -46,9 +53,27 @@ fn setup_custom_fonts(ctx: &egui::Context) {
             .push("my_font".to_owned());
 
         ctx.set_fonts(fonts);
+    } else {
+        eprintln!("Warning: Failed to load a CJK font from {:?}", config_core::fonts::FONT_CANDIDATES);
     }
 }
 
+/// 绘制单行输入框，内容与加载时的值不同时以浅黄色背景高亮
+/// 让用户一眼看出哪些字段会在应用修改时写入
+fn highlighted_text_edit(ui: &mut egui::Ui, text: &mut String, changed: bool) -> egui::Response {
+    ui.scope(|ui| {
+        if changed {
+            ui.visuals_mut().extreme_bg_color = if ui.visuals().dark_mode {
+                egui::Color32::from_rgb(90, 80, 20)
+            } else {
+                egui::Color32::from_rgb(255, 245, 180)
+            };
+        }
+        ui.text_edit_singleline(text)
+    })
+    .inner
+}
+
 /// 应用程序主状态结构体
 pub struct BytegameConfigEditor {
     /// 当前选择的项目目录
@@ -57,6 +82,8 @@ pub struct BytegameConfigEditor {
     config: AppConfig,
     /// 用户正在编辑的新配置
     new_config: AppConfig,
+    /// 当前显示的 appid 的来源（JSON 或 JS 回退）
+    appid_source: AppIdSource,
     /// 状态栏显示的消息
     status_message: String,
     /// 是否正在进行修改操作（用于显示加载动画）
@@ -65,8 +92,31 @@ pub struct BytegameConfigEditor {
     show_success: bool,
     /// 记录本次操作修改了哪些文件
     modified_files: Vec<String>,
-    /// 预览图片列表，存储图片的 URI 和二进制数据
-    preview_images: Vec<(String, Vec<u8>)>,
+    /// 选择目录后是否立即加载配置
+    /// 关闭后需手动点击"加载"，避免误选超大目录时长时间卡顿
+    load_on_pick: bool,
+    /// 预览图片列表，存储图片的 URI、二进制数据和原始尺寸 [宽, 高]
+    preview_images: Vec<(String, Vec<u8>, [u32; 2])>,
+    /// 界面缩放比例（在系统 DPI 缩放基础上再乘以此值，持久化）
+    ui_scale: f32,
+    /// 是否正在拖动缩放滑块（拖动期间不从 egui 同步缩放值）
+    ui_scale_dragging: bool,
+    /// 窗口大小（逻辑像素，不含界面缩放；每帧同步，退出时保存）
+    window_size: [f32; 2],
+    /// JS 中 AppID 的字段名（默认 `appId`，持久化）
+    app_id_field: String,
+    /// 界面语言（持久化）
+    lang: Lang,
+    /// 修改文件前是否先备份为 `.bak`（默认开启，持久化）
+    backup_before_save: bool,
+    /// 本次会话的备份记录，每个文件只在第一次被修改前备份
+    backups: Backups,
+    /// 已有较新备份、等待用户确认是否覆盖的文件
+    confirm_backup_overwrite: Vec<PathBuf>,
+    /// 是否显示"恢复备份"确认对话框
+    confirm_restore: bool,
+    /// AppID 格式不正确时等待用户确认的原因，非空时弹出确认对话框
+    confirm_invalid_appid: Option<String>,
 }
 
 impl BytegameConfigEditor {
@@ -74,20 +124,47 @@ impl BytegameConfigEditor {
     ///
     /// # 参数
     /// * `cc` - eframe 创建上下文
-    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+    /// * `settings` - 启动时从设置文件读取的设置
+    pub fn new(cc: &eframe::CreationContext<'_>, settings: Settings) -> Self {
         // 初始化字体和图片加载器
         setup_custom_fonts(&cc.egui_ctx);
         egui_extras::install_image_loaders(&cc.egui_ctx);
-        
+
+        // 恢复上次的界面缩放；使用缩放系数而非 pixels_per_point，保留系统的 DPI 缩放
+        let ui_scale = settings.ui_scale.clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end());
+        cc.egui_ctx.set_zoom_factor(ui_scale);
+        i18n::set_lang(settings.lang);
+        // 只恢复路径，不自动加载；目录已不存在时当作未选择
+        let project_dir = settings.last_project_dir
+            .filter(|dir| dir.is_dir())
+            .unwrap_or_default();
+        let status_message = if project_dir.as_os_str().is_empty() {
+            tr("请选择字节跳动小游戏项目目录").to_string()
+        } else {
+            tr("已恢复上次的目录，点击\"加载\"读取配置").to_string()
+        };
+
         Self {
-            project_dir: PathBuf::new(),
+            project_dir,
             config: AppConfig::new(),
             new_config: AppConfig::new(),
-            status_message: String::from("请选择字节跳动小游戏项目目录"),
+            appid_source: AppIdSource::None,
+            status_message,
             is_modifying: false,
             show_success: false,
             modified_files: Vec::new(),
+            load_on_pick: settings.load_on_pick,
             preview_images: Vec::new(),
+            ui_scale,
+            ui_scale_dragging: false,
+            window_size: settings.window_size,
+            app_id_field: settings.app_id_field,
+            lang: settings.lang,
+            backup_before_save: settings.backup_before_save,
+            backups: Backups::default(),
+            confirm_backup_overwrite: Vec::new(),
+            confirm_restore: false,
+            confirm_invalid_appid: None,
         }
     }
 
@@ -100,40 +177,43 @@ impl BytegameConfigEditor {
 
         self.config = AppConfig::new();
 
-        // 尝试查找预览图片 (PNG)，且宽度必须为 750px
+        // 尝试查找预览图片，且宽度必须为 750px
         self.preview_images.clear();
+        // 单个条目无法访问时只跳过该条目，其余文件继续遍历
+        let mut skipped = 0;
         let walker = walkdir::WalkDir::new(&self.project_dir).into_iter();
-        for entry in walker.filter_map(|e| e.ok()) {
+        for entry in walk_logged(walker, &mut skipped) {
             let path = entry.path();
-            if path.extension().map_or(false, |ext| ext == "png") {
-                 // 找到一个 PNG 文件，读取并检查宽度
-                 if let Ok(data) = std::fs::read(path) {
-                     // 尝试加载图片获取尺寸
-                     if let Ok(img) = image::load_from_memory(&data) {
-                         if img.width() == 750 {
-                             // 将路径转换为 file URI 格式，并确保使用正斜杠
-                             let uri = format!("file:///{}", path.display().to_string().replace("\\", "/"));
-                             self.preview_images.push((uri, data));
-                         }
-                     }
-                 }
+            if !is_image_file(path) {
+                continue;
+            }
+            // 先只读文件头判断宽度，宽度匹配的图片才读取完整数据用于显示
+            let Ok((w, h)) = image_size(path) else { continue };
+            if !PREVIEW_WIDTHS.contains(&w) {
+                continue;
+            }
+            if let Ok(data) = std::fs::read(path) {
+                // 将路径转换为 file URI 格式，并确保使用正斜杠
+                let uri = format!("file:///{}", path.display().to_string().replace("\\", "/"));
+                self.preview_images.push((uri, data, [w, h]));
             }
         }
 
-        // 读取 JSON 配置 (project.config.json)
-        let json_files = find_json_files(&self.project_dir);
-        for file in json_files {
-            if let Ok(cfg) = read_json_config(&file) {
+        // 读取项目配置 (project.config.json 或其 YAML / TOML 变体)
+        // 与"应用修改"写入的是同一组文件（`find_config_files`），有多个变体时使用第一个能读取的
+        for file in find_config_files(&self.project_dir) {
+            if let Ok(cfg) = read_project_config(&file) {
                 self.config.appid = cfg.appid;
                 self.config.appname = cfg.appname;
-                self.status_message = format!("成功加载配置: {}", file.display());
+                self.status_message = trf("成功加载配置: {}", &[&file.display()]);
+                break;
             }
         }
 
         // 读取 JS 配置 (查找包含 appId 和 douyinIds 的 JS 文件)
         let js_files = find_js_files(&self.project_dir);
         for file in js_files {
-            if let Ok(cfg) = read_js_config(&file) {
+            if let Ok(cfg) = read_js_config(&file, &self.app_id_field) {
                 let mut found = false;
                 if !cfg.app_id.is_empty() {
                     self.config.app_id = cfg.app_id.clone();
@@ -149,36 +229,74 @@ impl BytegameConfigEditor {
             }
         }
 
+        // JSON 中没有 appid 时回退使用 JS 中的 appId
+        self.appid_source = self.config.resolve_appid();
+        if self.appid_source == AppIdSource::Js {
+            self.status_message = tr("project.config.json 中 appid 为空，已使用 JS 文件中的 appId").to_string();
+        }
+        if skipped > 0 {
+            self.status_message.push_str(&trf("（{} 个条目无法访问，已跳过）", &[&skipped]));
+        }
+
         // 初始化新配置为当前值，以便用户编辑
         self.new_config = self.config.clone();
     }
 
+    /// 应用修改可能写入的文件：项目配置文件和目录下的所有 JS 文件
+    fn project_files(&self) -> Vec<PathBuf> {
+        let mut files = find_config_files(&self.project_dir);
+        files.extend(find_js_files(&self.project_dir));
+        files
+    }
+
+    /// 点击"应用修改"：AppID 格式不正确时先请用户确认，再检查备份
+    fn request_apply(&mut self) {
+        if let Err(reason) = self.new_config.validate_appid() {
+            self.confirm_invalid_appid = Some(reason);
+            return;
+        }
+        self.confirm_backups_then_apply();
+    }
+
+    /// 存在较新的 `.bak` 时先请用户确认是否覆盖，否则直接应用
+    fn confirm_backups_then_apply(&mut self) {
+        if self.backup_before_save && !self.project_dir.as_os_str().is_empty() {
+            let files = self.project_files();
+            self.confirm_backup_overwrite = self.backups.pending_newer(files.iter().map(PathBuf::as_path));
+            if !self.confirm_backup_overwrite.is_empty() {
+                return;
+            }
+        }
+        self.apply_modifications();
+    }
+
+    /// 用 `.bak` 备份覆盖项目目录中的配置文件和 JS 文件，然后重新加载
+    fn restore_backups(&mut self) {
+        let mut restored = 0;
+        let mut failed = Vec::new();
+        for file in self.project_files() {
+            match restore_backup(&file) {
+                Ok(true) => restored += 1,
+                Ok(false) => {}
+                Err(e) => failed.push(e.to_string()),
+            }
+        }
+        self.load_config();
+        self.show_success = false;
+        self.status_message = trf("已从备份恢复 {} 个文件", &[&restored]);
+        if let Some(e) = failed.first() {
+            self.status_message.push_str(&trf("，{} 个文件恢复失败（如 {}）", &[&failed.len(), e]));
+        }
+    }
+
     /// 应用用户修改的配置
     /// 将新配置写入到 JSON 和 JS 文件中
     fn apply_modifications(&mut self) {
         if self.project_dir.as_os_str().is_empty() {
-            self.status_message = String::from("请先选择项目目录");
+            self.status_message = tr("请先选择项目目录").to_string();
             return;
         }
 
-        // 格式化 douyinIds: 按逗号分割，确保每个ID都有双引号
-        if !self.new_config.douyin_ids.is_empty() {
-            let formatted_ids = self.new_config.douyin_ids
-                .split(',')
-                .map(|s| s.trim())
-                .filter(|s| !s.is_empty())
-                .map(|s| {
-                    if s.starts_with('"') && s.ends_with('"') {
-                        s.to_string()
-                    } else {
-                        format!("\"{}\"", s)
-                    }
-                })
-                .collect::<Vec<String>>()
-                .join(",");
-            self.new_config.douyin_ids = formatted_ids;
-        }
-
         // 同步 appid 到 app_id (确保 JSON 和 JS 使用相同的值)
         // 用户只需输入一次 AppId，程序会自动同步到两个字段
         self.new_config.app_id = self.new_config.appid.clone();
@@ -186,44 +304,52 @@ impl BytegameConfigEditor {
         self.is_modifying = true;
         self.modified_files.clear();
 
-        // 修改 JSON 文件
-        let json_files = find_json_files(&self.project_dir);
-        for file in &json_files {
-            match write_json_config(file, &self.new_config) {
-                Ok(_) => {
-                    self.modified_files.push(format!("JSON: {}", file.display()));
-                }
-                Err(e) => {
-                    self.status_message = format!("修改 JSON 失败: {}", e);
-                    self.is_modifying = false;
-                    return;
-                }
-            }
-        }
-
-        // 修改 JS 文件
-        // 遍历所有 JS 文件并尝试替换，只有真正修改了内容的文件才会被记录
-        let js_files = find_js_files(&self.project_dir);
-        for file in js_files {
-            match write_js_config(&file, &self.new_config) {
-                Ok(modified) => {
-                    if modified {
-                        self.modified_files.push(format!("JS: {}", file.display()));
-                    }
-                }
-                Err(e) => {
-                    self.status_message = format!("修改 JS 失败: {}", e);
-                    // 继续尝试修改其他文件，不立即停止
-                }
+        // 修改配置文件和所有 JS 文件，只有真正修改了内容的文件才会被记录
+        // 未被编辑的字段不写入：douyinIds 避免用加载时的旧值覆盖其它 JS 文件中的数组，
+        // 项目名避免在没有读取到名称的配置文件中写入空的 projectname；
+        // appid 取自 JS 时配置文件中还没有该值，即使未编辑也写入，使两边保持一致
+        let appid = self.new_config.appid.trim();
+        let appid_edited = appid != self.config.appid || self.appid_source == AppIdSource::Js;
+        let appid = (!appid.is_empty() && appid_edited).then_some(appid);
+        let name = (self.new_config.appname != self.config.appname).then_some(self.new_config.appname.as_str());
+        // 按解析后的 ID 列表比较，只改了引号或空格时不算编辑
+        let douyin_ids = split_douyin_ids(&self.new_config.douyin_ids);
+        let douyin_ids = (!douyin_ids.is_empty() && douyin_ids != split_douyin_ids(&self.config.douyin_ids))
+            .then_some(douyin_ids);
+        let report = match apply_to_dir(
+            &self.project_dir,
+            &self.app_id_field,
+            appid,
+            name,
+            douyin_ids.as_deref(),
+            self.backup_before_save.then_some(&mut self.backups),
+        ) {
+            Ok(report) => report,
+            Err(e) => {
+                self.status_message = trf("修改配置失败: {}", &[&e]);
+                self.is_modifying = false;
+                return;
             }
+        };
+        for file in &report.changed_files {
+            let kind = if file.extension().is_some_and(|ext| ext == "js") { "JS" } else { "JSON" };
+            self.modified_files.push(format!("{}: {}", kind, file.display()));
         }
 
         self.is_modifying = false;
         self.show_success = true;
-        self.status_message = format!(
-            "成功修改 {} 个文件",
-            self.modified_files.len()
-        );
+        self.status_message = trf("成功修改 {} 个文件", &[&self.modified_files.len()]);
+        // 单个 JS 文件失败时其它文件仍会被修改
+        if let Some((file, e)) = report.failed_files.first() {
+            self.status_message.push_str(&trf(
+                "，{} 个 JS 文件修改失败（如 {}: {}）",
+                &[&report.failed_files.len(), &file.display(), e],
+            ));
+        }
+        // 要修改的字段在所有 JS 文件中都不存在，该字段没有被写入
+        for e in &report.missing_fields {
+            self.status_message.push_str(&trf("；{}", &[e]));
+        }
         // 更新当前配置为新配置
         self.config = self.new_config.clone();
     }
@@ -232,7 +358,7 @@ impl BytegameConfigEditor {
     /// 将编辑框中的值恢复为最初加载的配置值
     fn reset(&mut self) {
         self.new_config = self.config.clone();
-        self.status_message = String::from("已重置为原始值");
+        self.status_message = tr("已重置为原始值").to_string();
     }
 }
 
@@ -240,33 +366,90 @@ impl eframe::App for BytegameConfigEditor {
     /// GUI 更新循环
     /// 每一帧绘制 UI 界面
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // egui 自带 Ctrl+= / Ctrl+- / Ctrl+0 缩放快捷键，这里同步当前缩放以便显示和持久化
+        if !self.ui_scale_dragging {
+            self.ui_scale = ctx.zoom_factor();
+        }
+        // 记录窗口大小供下次启动使用；最大化时保留之前的大小，避免还原后窗口占满屏幕
+        let viewport = ctx.input(|i| (i.viewport().inner_rect, i.viewport().maximized));
+        if let (Some(rect), Some(false) | None) = viewport {
+            self.window_size = (rect.size() * ctx.zoom_factor()).into();
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             egui::ScrollArea::vertical().show(ui, |ui| {
                 // 顶部标题
                 ui.vertical_centered(|ui| {
-                    ui.heading("字节跳动小游戏配置编辑器");
+                    ui.heading(tr("字节跳动小游戏配置编辑器"));
                     ui.add_space(10.0);
                 });
 
+                // 界面缩放
+                ui.horizontal(|ui| {
+                    ui.label(tr("界面缩放:"));
+                    let slider = ui.add(egui::Slider::new(&mut self.ui_scale, UI_SCALE_RANGE).step_by(0.05))
+                        .on_hover_text(tr("快捷键：Ctrl+= 放大，Ctrl+- 缩小，Ctrl+0 重置"));
+                    // 拖动过程中不立即生效，避免界面缩放导致滑块在指针下来回跳动
+                    self.ui_scale_dragging = slider.dragged();
+                    if slider.drag_stopped() || (slider.changed() && !slider.dragged()) {
+                        ctx.set_zoom_factor(self.ui_scale);
+                    }
+                    if ui.small_button(tr("重置")).clicked() {
+                        self.ui_scale = 1.0;
+                        ctx.set_zoom_factor(1.0);
+                    }
+
+                    ui.add_space(20.0);
+                    ui.label(tr("语言:"));
+                    let lang_before = self.lang;
+                    egui::ComboBox::from_id_source("lang")
+                        .selected_text(self.lang.label())
+                        .show_ui(ui, |ui| {
+                            for lang in Lang::ALL {
+                                ui.selectable_value(&mut self.lang, lang, lang.label());
+                            }
+                        });
+                    if self.lang != lang_before {
+                        i18n::set_lang(self.lang);
+                    }
+                });
+
+                // JS 中 AppID 的字段名
+                ui.horizontal(|ui| {
+                    ui.label(tr("JS AppID 字段名:"));
+                    ui.add(egui::TextEdit::singleline(&mut self.app_id_field).hint_text(DEFAULT_APP_ID_FIELD).desired_width(120.0))
+                        .on_hover_text(tr("部分打包产物使用 aid、applicationId 等字段名；修改后点击\"加载\"重新读取"));
+                });
+
                 ui.separator();
                 ui.add_space(10.0);
 
                 // 目录选择区
                 ui.horizontal(|ui| {
-                    ui.label("项目目录:");
-                    if ui.button("选择目录").clicked() {
+                    ui.label(tr("项目目录:"));
+                    if ui.button(tr("选择目录")).clicked() {
                         if let Some(path) = rfd::FileDialog::new()
-                            .set_title("选择字节跳动小游戏项目目录")
+                            .set_title(tr("选择字节跳动小游戏项目目录"))
                             .pick_folder()
                         {
                             self.project_dir = path;
-                            self.load_config();
+                            if self.load_on_pick {
+                                self.load_config();
+                            } else {
+                                self.config = AppConfig::new();
+                                self.status_message = tr("已选择目录，点击\"加载\"读取配置").to_string();
+                            }
                         }
                     }
 
                     if !self.project_dir.as_os_str().is_empty() {
                         ui.label(format!("{}", self.project_dir.display()));
+                        if ui.button(tr("加载")).clicked() {
+                            self.load_config();
+                        }
                     }
+
+                    ui.checkbox(&mut self.load_on_pick, tr("选择后立即加载"));
                 });
 
                 ui.add_space(10.0);
@@ -278,7 +461,7 @@ impl eframe::App for BytegameConfigEditor {
                 // 如果未加载配置，显示提示信息
                 if self.config.is_empty() {
                     ui.vertical_centered(|ui| {
-                        ui.label("请选择包含 project.config.json 和 JS 文件的项目目录");
+                        ui.label(tr("请选择包含 project.config.json 和 JS 文件的项目目录"));
                     });
                     return;
                 }
@@ -289,22 +472,35 @@ impl eframe::App for BytegameConfigEditor {
                     .spacing([10.0, 10.0])
                     .striped(true)
                     .show(ui, |ui| {
-                        // appid 输入框
-                        ui.label("AppId:");
+                        // appid 输入框，标注值的来源
+                        match self.appid_source {
+                            AppIdSource::Js => ui.label(tr("AppId (来自 JS):"))
+                                .on_hover_text(tr("project.config.json 中 appid 为空，显示的是 JS 文件中的 appId；保存时会同步写入两处")),
+                            _ => ui.label(tr("AppId:")),
+                        };
                         ui.label(&self.config.appid);
-                        ui.text_edit_singleline(&mut self.new_config.appid);
+                        let changed = self.new_config.appid != self.config.appid;
+                        ui.horizontal(|ui| {
+                            highlighted_text_edit(ui, &mut self.new_config.appid, changed);
+                            if let Err(reason) = self.new_config.validate_appid() {
+                                ui.label(egui::RichText::new("⚠").color(egui::Color32::RED))
+                                    .on_hover_text(reason);
+                            }
+                        });
                         ui.end_row();
 
                         // douyinIds 输入框
                         ui.label("douyinIds (JS):");
                         ui.label(&self.config.douyin_ids);
-                        ui.text_edit_singleline(&mut self.new_config.douyin_ids);
+                        let changed = self.new_config.douyin_ids != self.config.douyin_ids;
+                        highlighted_text_edit(ui, &mut self.new_config.douyin_ids, changed);
                         ui.end_row();
 
                          // appname 输入框
                         ui.label("AppName:");
                         ui.label(&self.config.appname);
-                        ui.text_edit_singleline(&mut self.new_config.appname);
+                        let changed = self.new_config.appname != self.config.appname;
+                        highlighted_text_edit(ui, &mut self.new_config.appname, changed);
                         ui.end_row();
                     });
 
@@ -312,13 +508,23 @@ impl eframe::App for BytegameConfigEditor {
 
                 // 操作按钮区
                 ui.horizontal(|ui| {
-                    if ui.button("应用修改").clicked() {
-                        self.apply_modifications();
+                    if ui.button(tr("应用修改")).clicked() {
+                        self.request_apply();
                     }
 
-                    if ui.button("重置").clicked() {
+                    if ui.button(tr("重置")).clicked() {
                         self.reset();
                     }
+
+                    ui.checkbox(&mut self.backup_before_save, tr("修改前备份为 .bak"))
+                        .on_hover_text(tr("每个文件在本次运行中第一次被修改前，把原内容复制为同目录下的 <文件名>.bak"));
+                    if ui.button(tr("恢复备份")).clicked() {
+                        self.confirm_restore = true;
+                    }
+
+                    if self.new_config.differs_from(&self.config) {
+                        ui.label(egui::RichText::new(tr("● 待保存")).color(egui::Color32::RED));
+                    }
                 });
 
                 ui.add_space(10.0);
@@ -327,16 +533,16 @@ impl eframe::App for BytegameConfigEditor {
                 if self.is_modifying {
                     ui.horizontal(|ui| {
                         ui.spinner();
-                        ui.label("正在修改配置...");
+                        ui.label(tr("正在修改配置..."));
                     });
                 }
 
                 // 成功提示和修改文件列表
                 if self.show_success {
                     ui.add_space(10.0);
-                    ui.colored_label(egui::Color32::DARK_GREEN, "✓ 修改成功!");
+                    ui.colored_label(egui::Color32::DARK_GREEN, tr("✓ 修改成功!"));
                     ui.separator();
-                    ui.label("已修改的文件:");
+                    ui.label(tr("已修改的文件:"));
                     for file in &self.modified_files {
                         ui.label(format!("  - {}", file));
                     }
@@ -348,20 +554,22 @@ impl eframe::App for BytegameConfigEditor {
                 // 显示所有宽度为 750px 的 PNG 图片
                 if !self.preview_images.is_empty() {
                     ui.separator();
-                    ui.heading(format!("图片预览 (共 {} 张, 宽度 750px)", self.preview_images.len()));
+                    ui.heading(trf("图片预览 (共 {} 张, 宽度 750px)", &[&self.preview_images.len()]));
                     ui.add_space(10.0);
                     
                     // 使用水平滚动区域展示图片
                     egui::ScrollArea::horizontal().show(ui, |ui| {
                         ui.horizontal(|ui| {
-                            for (uri, data) in &self.preview_images {
+                            for (uri, data, [w, h]) in &self.preview_images {
                                 ui.vertical(|ui| {
                                     // 限制单张图片的显示区域宽度
                                     ui.set_max_width(400.0);
-                                    ui.label(format!("路径: {}", uri));
+                                    ui.label(trf("路径: {}", &[uri]));
+                                    ui.label(trf("尺寸: {}×{}", &[w, h]));
                                     ui.add(
                                         egui::Image::from_bytes(uri.clone(), data.clone())
                                             .max_width(750.0) // 限制显示宽度
+                                            .max_height(PREVIEW_MAX_HEIGHT) // 限制显示高度，长图按比例缩小
                                             .fit_to_original_size(0.5) // 缩放显示
                                     );
                                 });
@@ -375,11 +583,97 @@ impl eframe::App for BytegameConfigEditor {
                 ui.separator();
                 // 底部状态栏
                 ui.horizontal(|ui| {
-                    ui.label("版本: 0.1.2");
+                    ui.label(tr("版本: 0.1.2"));
                     ui.label(" | ");
-                    ui.label("支持修改 AppId 和 douyinIds 配置，显示 750px 宽图片");
+                    ui.label(tr("支持修改 AppId 和 douyinIds 配置，显示 750px 宽图片"));
                 });
             });
         });
+
+        // 已有较新备份时的确认对话框，避免覆盖上一次留下的备份
+        if !self.confirm_backup_overwrite.is_empty() {
+            egui::Window::new(tr("已有较新的备份"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(trf(
+                        "{} 个文件的 .bak 备份比文件本身更新，覆盖后将无法再恢复这些备份：",
+                        &[&self.confirm_backup_overwrite.len()],
+                    ));
+                    for file in &self.confirm_backup_overwrite {
+                        ui.label(format!("  - {}", file.display()));
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("覆盖备份并应用")).clicked() {
+                            let files = std::mem::take(&mut self.confirm_backup_overwrite);
+                            self.backups.allow_overwrite(&files);
+                            self.apply_modifications();
+                        }
+                        if ui.button(tr("保留旧备份并应用")).clicked() {
+                            self.confirm_backup_overwrite.clear();
+                            self.apply_modifications();
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            self.confirm_backup_overwrite.clear();
+                        }
+                    });
+                });
+        }
+
+        // AppID 格式不正确时的确认对话框
+        if let Some(reason) = self.confirm_invalid_appid.clone() {
+            egui::Window::new(tr("AppID 格式不正确"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(format!("{}：{}", self.new_config.appid.trim(), reason));
+                    ui.label(tr("格式不正确的 AppID 可能导致游戏无法上传或运行，是否仍然应用？"));
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("仍然应用")).clicked() {
+                            self.confirm_invalid_appid = None;
+                            self.confirm_backups_then_apply();
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            self.confirm_invalid_appid = None;
+                        }
+                    });
+                });
+        }
+
+        // "恢复备份"确认对话框
+        if self.confirm_restore {
+            egui::Window::new(tr("恢复备份"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(tr("将用 .bak 备份覆盖项目目录中的配置文件和 JS 文件，当前内容会丢失，是否继续？"));
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("确认恢复")).clicked() {
+                            self.confirm_restore = false;
+                            self.restore_backups();
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            self.confirm_restore = false;
+                        }
+                    });
+                });
+        }
+    }
+
+    /// 退出时把设置写入设置文件
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        Settings {
+            window_size: self.window_size,
+            lang: self.lang,
+            ui_scale: self.ui_scale,
+            last_project_dir: Some(self.project_dir.clone()).filter(|dir| !dir.as_os_str().is_empty()),
+            load_on_pick: self.load_on_pick,
+            app_id_field: self.app_id_field.clone(),
+            backup_before_save: self.backup_before_save,
+        }
+        .save();
     }
 }
@@ -0,0 +1,135 @@
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+
+/// 字节小游戏 AppID 的固定前缀
+pub const APPID_PREFIX: &str = "tt";
+/// AppID 的合法长度范围（含前缀）
+pub const APPID_LEN_RANGE: RangeInclusive<usize> = 18..=20;
+
+/// 校验 AppID 格式：`tt` 前缀 + 字母数字，长度在 [`APPID_LEN_RANGE`] 内
+///
+/// 空字符串表示未设置，不做校验
+///
+/// # Returns
+/// * `Ok(())` - 格式正确或为空
+/// * `Err(String)` - 不符合格式的原因，可直接显示给用户
+pub fn validate_appid(appid: &str) -> Result<(), String> {
+    if appid.is_empty() {
+        return Ok(());
+    }
+    if !appid.starts_with(APPID_PREFIX) {
+        return Err(format!("AppID 应以 \"{}\" 开头", APPID_PREFIX));
+    }
+    if !appid.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("AppID 只能包含英文字母和数字（注意不要带空格或引号）".to_string());
+    }
+    if !APPID_LEN_RANGE.contains(&appid.len()) {
+        return Err(format!(
+            "AppID 长度应为 {}~{} 个字符，当前为 {} 个",
+            APPID_LEN_RANGE.start(),
+            APPID_LEN_RANGE.end(),
+            appid.len()
+        ));
+    }
+    Ok(())
+}
+
+/// 在 AppID 末尾的数字上加上偏移量，生成递增的 AppID
+///
+/// 保留末尾数字的位数（前导零），例如 `tt0009` + 1 = `tt0010`；
+/// 进位超出原位数时自然变长（`tt99` + 1 = `tt100`）。
+///
+/// # Returns
+/// * `Some(String)` - 递增后的 AppID
+/// * `None` - AppID 不以数字结尾，或数字过大无法计算，此时无法自动生成
+pub fn increment_appid(base: &str, offset: u64) -> Option<String> {
+    let digits_start = base
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_ascii_digit())
+        .last()
+        .map(|(i, _)| i)?;
+    let (prefix, digits) = base.split_at(digits_start);
+    let number: u64 = digits.parse().ok()?;
+    let next = number.checked_add(offset)?;
+    Some(format!("{}{:0width$}", prefix, next, width = digits.len()))
+}
+
+/// 找出出现多次的非空 AppID
+///
+/// # Returns
+/// * 每个重复的 AppID 及其出现次数，按 AppID 排序
+pub fn find_duplicates<'a>(appids: impl IntoIterator<Item = &'a str>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for id in appids {
+        let id = id.trim();
+        if !id.is_empty() {
+            *counts.entry(id).or_default() += 1;
+        }
+    }
+    let mut dups: Vec<(String, usize)> = counts
+        .into_iter()
+        .filter(|(_, n)| *n > 1)
+        .map(|(id, n)| (id.to_string(), n))
+        .collect();
+    dups.sort();
+    dups
+}
+
+/// 按 AppID 分组，只返回被多个条目使用的非空 AppID
+///
+/// # Arguments
+/// * `items` - (AppID, 条目标识) 列表，标识可以是项目下标或路径
+///
+/// # Returns
+/// * 重复的 AppID 到使用它的条目标识（按输入顺序）的映射
+pub fn group_duplicates<'a, T>(items: impl IntoIterator<Item = (&'a str, T)>) -> BTreeMap<String, Vec<T>> {
+    let mut groups: BTreeMap<String, Vec<T>> = BTreeMap::new();
+    for (id, item) in items {
+        let id = id.trim();
+        if !id.is_empty() {
+            groups.entry(id.to_string()).or_default().push(item);
+        }
+    }
+    groups.retain(|_, items| items.len() > 1);
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_increment_appid() {
+        assert_eq!(increment_appid("tt0009", 1).as_deref(), Some("tt0010"));
+        assert_eq!(increment_appid("tt99", 1).as_deref(), Some("tt100"));
+        assert_eq!(increment_appid("123", 0).as_deref(), Some("123"));
+        assert_eq!(increment_appid("tt12ab", 1), None);
+        assert_eq!(increment_appid("", 1), None);
+    }
+
+    #[test]
+    fn test_validate_appid() {
+        assert!(validate_appid("tt1a2b3c4d5e6f7a8b01").is_ok());
+        assert!(validate_appid("tt1a2b3c4d5e6f7a8b").is_ok());
+        assert!(validate_appid("").is_ok());
+
+        assert!(validate_appid("1a2b3c4d5e6f7a8b0101").unwrap_err().contains("开头"));
+        assert!(validate_appid("TT1a2b3c4d5e6f7a8b01").is_err());
+        assert!(validate_appid("tt1a2b3c4d5e6f7a8b0 ").unwrap_err().contains("字母和数字"));
+        assert!(validate_appid("tt1a2b-3c4d5e6f7a8b0").is_err());
+        assert!(validate_appid("tt中文3c4d5e6f7a8b01").is_err());
+        assert!(validate_appid("tt123").unwrap_err().contains("长度"));
+        assert!(validate_appid("tt1a2b3c4d5e6f7a8b0102").is_err());
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let dups = find_duplicates(["a", "b", "a", "", "", "c", "b", "a"]);
+        assert_eq!(dups, vec![("a".to_string(), 3), ("b".to_string(), 2)]);
+
+        let groups = group_duplicates([("a", 0), ("b", 1), (" a ", 2), ("", 3), ("", 4), ("c", 5)]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups["a"], vec![0, 2]);
+    }
+}
@@ -0,0 +1,398 @@
+use crate::backup::Backups;
+use crate::config_file::{read_config, write_config};
+use crate::encoding::{read_text_detect, write_text};
+use crate::error::{ConfigError, Result};
+use crate::encoding::TextEncoding;
+use crate::js_patch::{
+    app_id_field, format_assignment, has_app_id, has_id_array, replace_app_id, replace_id_array, DOUYIN_IDS_FIELD,
+};
+use crate::text::{match_line_endings, strip_wrapping_quotes};
+use crate::walk::walk_logged;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 识别为项目配置的文件名（JSON 及其 YAML / TOML 变体）
+pub const CONFIG_FILENAMES: &[&str] = &[
+    "project.config.json",
+    "project.config.yaml",
+    "project.config.yml",
+    "project.config.toml",
+];
+
+/// `apply_to_dir` 的结果
+#[derive(Debug, Default)]
+pub struct ChangeReport {
+    /// 内容发生变化并已写回的文件
+    pub changed_files: Vec<PathBuf>,
+    /// 写入失败的 JS 文件及原因（单个 JS 文件失败不影响其它文件）
+    pub failed_files: Vec<(PathBuf, ConfigError)>,
+    /// 要求修改、但目录中没有任何 JS 文件包含的字段（`ConfigError::JsPatternNotFound`，路径为项目目录）
+    pub missing_fields: Vec<ConfigError>,
+}
+
+/// 将配置应用到单个项目目录
+///
+/// 修改目录下的项目配置文件（appid、projectname）以及目录中所有 JS 文件里的
+/// `appId="..."` 和 `douyinIds=[...]`。参数为 `None` 的字段保持不变；
+/// 只有内容实际变化的文件才会被写入。每个 JS 文件只修改其中存在的字段，
+/// 没有任何 JS 文件包含的字段记录在报告的 `missing_fields` 中。
+///
+/// # Arguments
+/// * `dir` - 项目目录（project.config.json 所在目录）
+/// * `app_id_field` - JS 中 AppID 的字段名（为空时使用 `appId`）
+/// * `appid` - 同时写入配置文件的 appid 和 JS 的 AppID 字段
+/// * `name` - 项目名称，两端多余的引号会被去掉
+/// * `douyin_ids` - 不带引号的 ID 列表，写入时沿用原数组的引号风格
+/// * `backups` - 为 `Some` 时，覆盖文件前先备份为 `.bak`（每个文件每次会话一次）
+///
+/// # Returns
+/// * `Result<ChangeReport>` - 配置文件写入失败时返回错误；JS 文件的失败和缺少的字段记录在报告中
+pub fn apply_to_dir(
+    dir: &Path,
+    app_id_field: &str,
+    appid: Option<&str>,
+    name: Option<&str>,
+    douyin_ids: Option<&[String]>,
+    mut backups: Option<&mut Backups>,
+) -> Result<ChangeReport> {
+    let mut report = ChangeReport::default();
+
+    for file in find_config_files(dir) {
+        if apply_to_config_file(&file, appid, name, backups.as_deref_mut())? {
+            report.changed_files.push(file);
+        }
+    }
+
+    if appid.is_some() || douyin_ids.is_some() {
+        let (mut app_id_found, mut ids_found) = (false, false);
+        for file in find_js_files(dir) {
+            let (original, encoding) = match read_text_detect(&file) {
+                Ok(text) => text,
+                Err(e) => {
+                    report.failed_files.push((file, e));
+                    continue;
+                }
+            };
+            // 目录中的 JS 文件大多与配置无关，只修改本文件中存在的字段
+            let has_app_id = appid.is_some() && has_app_id(&original, app_id_field);
+            let has_ids = douyin_ids.is_some() && has_id_array(&original, DOUYIN_IDS_FIELD);
+            if !has_app_id && !has_ids {
+                continue;
+            }
+            app_id_found |= has_app_id;
+            ids_found |= has_ids;
+            let changes = JsChanges {
+                app_id: appid.filter(|_| has_app_id).map(str::to_string),
+                id_arrays: douyin_ids
+                    .filter(|_| has_ids)
+                    .map(|ids| (DOUYIN_IDS_FIELD.to_string(), ids.to_vec()))
+                    .into_iter()
+                    .collect(),
+                format_assignments: false,
+            };
+            match write_js_changes(&file, original, encoding, app_id_field, &changes, backups.as_deref_mut()) {
+                Ok(true) => report.changed_files.push(file),
+                Ok(false) => {}
+                Err(e) => report.failed_files.push((file, e)),
+            }
+        }
+        let not_found = |field: &str| ConfigError::JsPatternNotFound { path: dir.to_path_buf(), field: field.to_string() };
+        if appid.is_some() && !app_id_found {
+            report.missing_fields.push(not_found(crate::js_patch::app_id_field(app_id_field)));
+        }
+        if douyin_ids.is_some() && !ids_found {
+            report.missing_fields.push(not_found(DOUYIN_IDS_FIELD));
+        }
+    }
+
+    Ok(report)
+}
+
+/// 修改单个项目配置文件的 appid 和 projectname，按原文件的格式写回
+/// 字段不存在时会被添加到根对象中；其它字段原样保留
+/// 内容变化且 `backups` 为 `Some` 时，写入前先备份原文件
+///
+/// # Returns
+/// * `Result<bool>` - 文件内容是否发生变化
+/// * `Err(ConfigError::NotAnObject)` - 根节点不是对象
+pub fn apply_to_config_file(
+    path: &Path,
+    appid: Option<&str>,
+    name: Option<&str>,
+    backups: Option<&mut Backups>,
+) -> Result<bool> {
+    let mut config: Value = read_config(path)?;
+    let original = config.clone();
+    let Value::Object(map) = &mut config else {
+        return Err(ConfigError::NotAnObject { path: path.to_path_buf() });
+    };
+    if let Some(appid) = appid {
+        map.insert("appid".to_string(), Value::String(appid.to_string()));
+    }
+    if let Some(name) = name {
+        map.insert("projectname".to_string(), Value::String(strip_wrapping_quotes(name).to_string()));
+    }
+    if config == original {
+        return Ok(false);
+    }
+    if let Some(backups) = backups {
+        backups.backup(path)?;
+    }
+    write_config(path, &config)?;
+    Ok(true)
+}
+
+/// 对单个 JS 文件的修改，由 `apply_js_changes` 写入
+#[derive(Debug, Clone, Default)]
+pub struct JsChanges {
+    /// 新的 AppID，替换所有出现的位置；`None` 时保持不变
+    pub app_id: Option<String>,
+    /// 要替换的 ID 数组：(字段名, ID 列表)，每个字段只替换第一次出现的位置
+    pub id_arrays: Vec<(String, Vec<String>)>,
+    /// 是否把被修改字段的赋值规范为 `.name = ...` 的空格风格（不会格式化整个文件）
+    pub format_assignments: bool,
+}
+
+/// 替换单个 JS 文件中的 AppID 字段（所有出现的位置）和 douyinIds 数组（第一次出现的位置）
+/// 匹配规则见 `js_patch`；内容变化且 `backups` 为 `Some` 时，写入前先备份原文件
+/// GBK 编码的文件按 GBK 写回（见 `encoding`）
+///
+/// # Returns
+/// * `Result<bool>` - 文件内容是否发生变化
+pub fn apply_to_js_file(
+    path: &Path,
+    app_id_field: &str,
+    app_id: Option<&str>,
+    douyin_ids: Option<&[String]>,
+    backups: Option<&mut Backups>,
+) -> Result<bool> {
+    let changes = JsChanges {
+        app_id: app_id.map(str::to_string),
+        id_arrays: douyin_ids
+            .map(|ids| (DOUYIN_IDS_FIELD.to_string(), ids.to_vec()))
+            .into_iter()
+            .collect(),
+        format_assignments: false,
+    };
+    apply_js_changes(path, app_id_field, &changes, backups)
+}
+
+/// 按 `changes` 修改单个 JS 文件，其余内容（包括格式和注释）保持不变
+/// 数组沿用原来的引号风格和分隔符；内容变化且 `backups` 为 `Some` 时，写入前先备份原文件
+///
+/// # Returns
+/// * `Result<bool>` - 文件内容是否发生变化
+/// * `Err(ConfigError::JsPatternNotFound)` - 文件中没有要修改的 AppID 或 ID 数组字段，此时文件不会被修改
+pub fn apply_js_changes(
+    path: &Path,
+    field: &str,
+    changes: &JsChanges,
+    backups: Option<&mut Backups>,
+) -> Result<bool> {
+    let (original, encoding) = read_text_detect(path)?;
+    let missing = if changes.app_id.is_some() && !has_app_id(&original, field) {
+        Some(app_id_field(field))
+    } else {
+        changes.id_arrays.iter()
+            .map(|(name, _)| name.as_str())
+            .find(|name| !has_id_array(&original, name))
+    };
+    if let Some(missing) = missing {
+        return Err(ConfigError::JsPatternNotFound { path: path.to_path_buf(), field: missing.to_string() });
+    }
+    write_js_changes(path, original, encoding, field, changes, backups)
+}
+
+/// 在已读取的文件内容上应用 `changes` 并写回，调用方已确认要修改的字段存在
+fn write_js_changes(
+    path: &Path,
+    original: String,
+    encoding: TextEncoding,
+    field: &str,
+    changes: &JsChanges,
+    backups: Option<&mut Backups>,
+) -> Result<bool> {
+    let mut content = original.clone();
+
+    if let Some(app_id) = &changes.app_id {
+        content = replace_app_id(&content, field, app_id);
+    }
+    for (name, ids) in &changes.id_arrays {
+        content = replace_id_array(&content, name, ids);
+    }
+
+    if changes.format_assignments {
+        if changes.app_id.is_some() {
+            content = format_assignment(&content, app_id_field(field));
+        }
+        for (name, _) in &changes.id_arrays {
+            content = format_assignment(&content, name);
+        }
+    }
+
+    // 沿用原文件的换行符和末尾换行
+    let content = match_line_endings(&original, content);
+    if content == original {
+        return Ok(false);
+    }
+    if let Some(backups) = backups {
+        backups.backup(path)?;
+    }
+    write_text(path, &content, encoding)?;
+    Ok(true)
+}
+
+/// 查找目录下（不递归）的项目配置文件
+pub fn find_config_files(dir: &Path) -> Vec<PathBuf> {
+    CONFIG_FILENAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// 递归查找目录下的所有 .js 文件，无法访问的条目会被跳过
+pub fn find_js_files(dir: &Path) -> Vec<PathBuf> {
+    let mut skipped = 0;
+    walk_logged(WalkDir::new(dir).into_iter(), &mut skipped)
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "js"))
+        .map(|e| e.path().to_path_buf())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::js_patch::{app_id_regex, find_app_id, DEFAULT_APP_ID_FIELD};
+    use std::fs;
+
+    #[test]
+    fn test_apply_to_dir() {
+        let dir = std::env::temp_dir().join("config_core_apply_test");
+        if dir.exists() {
+            fs::remove_dir_all(&dir).unwrap();
+        }
+        fs::create_dir_all(dir.join("assets")).unwrap();
+        let config_path = dir.join("project.config.json");
+        fs::write(&config_path, r#"{"appid": "tt_old", "projectname": "Old", "compileType": "game"}"#).unwrap();
+        let js_path = dir.join("assets").join("index.js");
+        fs::write(&js_path, r#"d.appId='tt_old',d.douyinIds=['a','b'];"#).unwrap();
+        let other_js = dir.join("assets").join("other.js");
+        fs::write(&other_js, "console.log(1);").unwrap();
+
+        let ids = vec!["c".to_string()];
+        let mut backups = Backups::default();
+        let report = apply_to_dir(&dir, DEFAULT_APP_ID_FIELD, Some("tt_new"), Some("\"New\""), Some(&ids), Some(&mut backups)).unwrap();
+        assert_eq!(report.changed_files, vec![config_path.clone(), js_path.clone()]);
+        assert!(report.failed_files.is_empty());
+
+        let config: Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(config["appid"], "tt_new");
+        assert_eq!(config["projectname"], "New");
+        assert_eq!(config["compileType"], "game");
+        // 引号风格保持不变
+        assert_eq!(fs::read_to_string(&js_path).unwrap(), r#"d.appId='tt_new',d.douyinIds=['c'];"#);
+        // 只有被修改的文件才会备份原内容
+        assert_eq!(
+            fs::read_to_string(dir.join("assets").join("index.js.bak")).unwrap(),
+            r#"d.appId='tt_old',d.douyinIds=['a','b'];"#
+        );
+        assert!(dir.join("project.config.json.bak").is_file());
+        assert!(!dir.join("assets").join("other.js.bak").exists());
+
+        // 再次应用相同的值时没有文件变化；None 的字段不会被修改
+        let report = apply_to_dir(&dir, DEFAULT_APP_ID_FIELD, Some("tt_new"), None, None, None).unwrap();
+        assert!(report.changed_files.is_empty());
+        assert!(report.missing_fields.is_empty());
+
+        // 没有任何 JS 文件包含的字段记录在报告中，其它字段照常写入
+        fs::write(&js_path, r#"d.appId='tt_new';"#).unwrap();
+        let report = apply_to_dir(&dir, DEFAULT_APP_ID_FIELD, Some("tt_2"), None, Some(&ids), None).unwrap();
+        assert_eq!(report.changed_files, vec![config_path.clone(), js_path.clone()]);
+        assert!(matches!(
+            report.missing_fields.as_slice(),
+            [ConfigError::JsPatternNotFound { field, .. }] if field == DOUYIN_IDS_FIELD
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_apply_js_changes_pattern_not_found() {
+        let dir = std::env::temp_dir().join("config_core_apply_not_found_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.js");
+        let content = r#"d.appId="tt_old";"#;
+        fs::write(&path, content).unwrap();
+
+        // 要求的 ID 数组不存在时报错，且不会写入文件中存在的 AppID
+        let changes = JsChanges {
+            app_id: Some("tt_new".to_string()),
+            id_arrays: vec![(DOUYIN_IDS_FIELD.to_string(), vec!["a".to_string()])],
+            format_assignments: false,
+        };
+        let err = apply_js_changes(&path, DEFAULT_APP_ID_FIELD, &changes, None).unwrap_err();
+        assert!(matches!(err, ConfigError::JsPatternNotFound { ref field, .. } if field == DOUYIN_IDS_FIELD));
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+
+        // AppID 字段名不匹配时同样报错，与读写失败区分开
+        let err = apply_to_js_file(&path, "aid", Some("tt_new"), None, None).unwrap_err();
+        assert!(matches!(err, ConfigError::JsPatternNotFound { ref field, .. } if field == "aid"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_apply_to_config_file_fields() {
+        let dir = std::env::temp_dir().join("config_core_apply_config_file_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("project.config.json");
+
+        // 字段已存在时更新
+        fs::write(&path, r#"{"appid": "tt_old", "projectname": "Old"}"#).unwrap();
+        assert!(apply_to_config_file(&path, Some("tt_new"), Some("New"), None).unwrap());
+        let config: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(config["appid"], "tt_new");
+        assert_eq!(config["projectname"], "New");
+
+        // 字段不存在时添加到根对象
+        fs::write(&path, r#"{"compileType": "game"}"#).unwrap();
+        assert!(apply_to_config_file(&path, Some("tt_new"), Some("New"), None).unwrap());
+        let config: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(config["appid"], "tt_new");
+        assert_eq!(config["projectname"], "New");
+        assert_eq!(config["compileType"], "game");
+
+        // 根节点不是对象时报错，文件不被修改
+        fs::write(&path, r#"["tt_old"]"#).unwrap();
+        assert!(matches!(
+            apply_to_config_file(&path, Some("tt_new"), None, None),
+            Err(ConfigError::NotAnObject { .. })
+        ));
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"["tt_old"]"#);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_custom_app_id_field() {
+        let dir = std::env::temp_dir().join("config_core_app_id_field_test");
+        fs::create_dir_all(&dir).unwrap();
+        let js_path = dir.join("index.js");
+        fs::write(&js_path, r#"d.applicationId="tt_old",d.appId="other";"#).unwrap();
+
+        assert_eq!(app_id_field("  "), DEFAULT_APP_ID_FIELD);
+        let content = r#"d.applicationId = 'tt1'"#;
+        assert!(app_id_regex("applicationId").is_match(content));
+        let range = find_app_id(content, "applicationId").unwrap();
+        assert_eq!(&content[range], "tt1");
+
+        // 只替换配置的字段，默认的 appId 保持不变
+        assert!(apply_to_js_file(&js_path, "applicationId", Some("tt_new"), None, None).unwrap());
+        assert_eq!(fs::read_to_string(&js_path).unwrap(), r#"d.applicationId="tt_new",d.appId="other";"#);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
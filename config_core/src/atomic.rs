@@ -0,0 +1,62 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// 原子地写入文件
+///
+/// 先写入同目录下的临时文件并落盘，再通过 `fs::rename` 覆盖原文件。
+/// 同一分区内的重命名是原子的，写入中途崩溃或出错时原文件保持不变。
+/// 原文件存在时会沿用它的权限。
+pub fn atomic_write(path: &Path, content: impl AsRef<[u8]>) -> io::Result<()> {
+    let tmp = temp_path(path);
+    let result = write_and_rename(path, &tmp, content.as_ref());
+    if result.is_err() {
+        // 清理残留的临时文件，失败也不影响原文件
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+fn write_and_rename(path: &Path, tmp: &Path, content: &[u8]) -> io::Result<()> {
+    let mut file = File::create(tmp)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    drop(file);
+
+    if let Ok(meta) = fs::metadata(path) {
+        fs::set_permissions(tmp, meta.permissions())?;
+    }
+    fs::rename(tmp, path)
+}
+
+/// 临时文件路径：与目标文件同目录，以 `.` 开头并带进程号，避免与其他文件冲突
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atomic_write() {
+        let dir = std::env::temp_dir().join("config_core_atomic_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.js");
+
+        atomic_write(&path, "第一版").unwrap();
+        atomic_write(&path, "第二版").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "第二版");
+        // 不应残留临时文件
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        // 目标目录不存在时报错
+        assert!(atomic_write(&dir.join("missing/a.js"), "x").is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
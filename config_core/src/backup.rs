@@ -0,0 +1,158 @@
+//! 覆盖配置文件前的 `.bak` 备份
+//!
+//! 每个文件在一次会话中只备份一次（第一次被覆盖之前），之后的保存不再更新备份，
+//! 这样 `.bak` 始终是打开程序前的原始内容，可以通过 [`restore_backup`] 恢复。
+
+use crate::atomic::atomic_write;
+use crate::error::{ConfigError, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 备份文件路径：在原文件名后追加 `.bak`（如 `project.config.json.bak`）
+///
+/// 不替换扩展名，避免 `game.js` 与 `game.json` 的备份互相覆盖
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// 是否存在比原文件更新、且内容不同的备份
+///
+/// 这种备份通常来自上次保存失败或手动恢复后的编辑，直接覆盖会丢失它，需要先确认
+pub fn has_newer_backup(path: &Path) -> bool {
+    let bak = backup_path(path);
+    let modified = |p: &Path| fs::metadata(p).and_then(|m| m.modified()).ok();
+    match (modified(&bak), modified(path)) {
+        (Some(bak_time), Some(file_time)) if bak_time > file_time => {
+            fs::read(&bak).ok() != fs::read(path).ok()
+        }
+        _ => false,
+    }
+}
+
+/// 用备份覆盖原文件
+///
+/// # Returns
+/// * `Result<bool>` - 是否存在备份并已恢复
+pub fn restore_backup(path: &Path) -> Result<bool> {
+    let bak = backup_path(path);
+    if !bak.is_file() {
+        return Ok(false);
+    }
+    let content = fs::read(&bak).map_err(|source| ConfigError::Io { path: bak, source })?;
+    atomic_write(path, content).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(true)
+}
+
+/// 一次会话内的备份记录
+#[derive(Debug, Default)]
+pub struct Backups {
+    /// 本次会话中已经处理过（已备份或保留了已有备份）的文件
+    done: HashSet<PathBuf>,
+    /// 用户确认可以覆盖的较新备份
+    overwrite_allowed: HashSet<PathBuf>,
+}
+
+impl Backups {
+    /// 在覆盖文件之前调用：本次会话第一次写入该文件时把原内容复制到 `.bak`
+    ///
+    /// 已存在较新的备份（见 [`has_newer_backup`]）且未经 [`Backups::allow_overwrite`] 确认时保留它，不再备份
+    ///
+    /// # Returns
+    /// * `Result<bool>` - 是否写入了新的备份
+    pub fn backup(&mut self, path: &Path) -> Result<bool> {
+        if self.done.contains(path) || !path.is_file() {
+            return Ok(false);
+        }
+        let allowed = self.overwrite_allowed.remove(path);
+        self.done.insert(path.to_path_buf());
+        if has_newer_backup(path) && !allowed {
+            return Ok(false);
+        }
+        let bak = backup_path(path);
+        fs::copy(path, &bak).map_err(|source| ConfigError::Io { path: bak, source })?;
+        Ok(true)
+    }
+
+    /// 在本次会话中尚未备份、且存在较新备份的文件，保存前应先请用户确认
+    pub fn pending_newer<'a>(&self, paths: impl IntoIterator<Item = &'a Path>) -> Vec<PathBuf> {
+        paths
+            .into_iter()
+            .filter(|path| !self.done.contains(*path) && has_newer_backup(path))
+            .map(Path::to_path_buf)
+            .collect()
+    }
+
+    /// 用户确认后允许覆盖这些文件的较新备份
+    pub fn allow_overwrite(&mut self, paths: &[PathBuf]) {
+        self.overwrite_allowed.extend(paths.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_backup_once_and_restore() {
+        let dir = std::env::temp_dir().join("config_core_backup_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("game.js");
+        let bak = dir.join("game.js.bak");
+        assert_eq!(backup_path(&path), bak);
+
+        fs::write(&path, "原始").unwrap();
+        let mut backups = Backups::default();
+        assert!(backups.backup(&path).unwrap());
+        fs::write(&path, "第一次保存").unwrap();
+        // 同一会话内不再覆盖备份
+        assert!(!backups.backup(&path).unwrap());
+        fs::write(&path, "第二次保存").unwrap();
+        assert_eq!(fs::read_to_string(&bak).unwrap(), "原始");
+        // 不存在的文件没有可备份的内容
+        assert!(!backups.backup(&dir.join("missing.js")).unwrap());
+
+        assert!(restore_backup(&path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "原始");
+        assert!(!restore_backup(&dir.join("missing.js")).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_keep_newer_backup() {
+        let dir = std::env::temp_dir().join("config_core_backup_newer_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("project.config.json");
+        let bak = backup_path(&path);
+        fs::write(&path, "当前").unwrap();
+        fs::write(&bak, "较新的备份").unwrap();
+        let older = SystemTime::now() - Duration::from_secs(60);
+        fs::File::options().write(true).open(&path).unwrap().set_modified(older).unwrap();
+        assert!(has_newer_backup(&path));
+
+        // 未确认时保留较新的备份
+        let mut backups = Backups::default();
+        assert_eq!(backups.pending_newer([path.as_path()]), vec![path.clone()]);
+        assert!(!backups.backup(&path).unwrap());
+        assert_eq!(fs::read_to_string(&bak).unwrap(), "较新的备份");
+        assert!(backups.pending_newer([path.as_path()]).is_empty());
+
+        // 确认后覆盖
+        let mut backups = Backups::default();
+        backups.allow_overwrite(std::slice::from_ref(&path));
+        assert!(backups.backup(&path).unwrap());
+        assert_eq!(fs::read_to_string(&bak).unwrap(), "当前");
+        assert!(!has_newer_backup(&path));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
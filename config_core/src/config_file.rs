@@ -0,0 +1,131 @@
+use crate::atomic::atomic_write;
+use crate::error::{ConfigError, Result};
+use crate::json::{read_json, write_json};
+use crate::text::{keep_bom, read_text, strip_bom};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+
+/// 项目配置文件的格式，根据扩展名判断
+/// 大部分项目使用 project.config.json，少数使用 .yaml / .yml / .toml 变体
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// 根据文件扩展名判断格式（不区分大小写），不支持的扩展名返回 None
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// 按扩展名对应的格式读取并解析配置文件，开头的 BOM 会被忽略
+pub fn read_config<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let format = ConfigFormat::from_path(path)
+        .ok_or_else(|| ConfigError::UnsupportedFormat { path: path.to_path_buf() })?;
+    match format {
+        ConfigFormat::Json => read_json(path),
+        ConfigFormat::Yaml => {
+            let content = read_text(path)?;
+            serde_yaml::from_str(strip_bom(&content)).map_err(|source| ConfigError::Yaml {
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+        ConfigFormat::Toml => {
+            let content = read_text(path)?;
+            toml::from_str(strip_bom(&content)).map_err(|e| ConfigError::Toml {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })
+        }
+    }
+}
+
+/// 按扩展名对应的格式原子地写回配置文件，格式（以及开头的 BOM）与原文件保持一致
+pub fn write_config<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    let format = ConfigFormat::from_path(path)
+        .ok_or_else(|| ConfigError::UnsupportedFormat { path: path.to_path_buf() })?;
+    let content = match format {
+        ConfigFormat::Json => return write_json(path, value),
+        ConfigFormat::Yaml => serde_yaml::to_string(value).map_err(|source| ConfigError::Yaml {
+            path: path.to_path_buf(),
+            source,
+        })?,
+        ConfigFormat::Toml => toml::to_string_pretty(value).map_err(|e| ConfigError::Toml {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?,
+    };
+    atomic_write(path, keep_bom(path, content)).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(ConfigFormat::from_path(Path::new("a/project.config.json")), Some(ConfigFormat::Json));
+        assert_eq!(ConfigFormat::from_path(Path::new("project.config.YML")), Some(ConfigFormat::Yaml));
+        assert_eq!(ConfigFormat::from_path(Path::new("project.config.toml")), Some(ConfigFormat::Toml));
+        assert_eq!(ConfigFormat::from_path(Path::new("project.config")), None);
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let dir = std::env::temp_dir().join("config_core_yaml_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("project.config.yaml");
+        std::fs::write(&path, "appid: tt1\nprojectname: 游戏\nsetting:\n  es6: true\n").unwrap();
+
+        let mut value: Value = read_config(&path).unwrap();
+        assert_eq!(value["projectname"], "游戏");
+        value["appid"] = Value::from("tt2");
+        write_config(&path, &value).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("appid: tt2"));
+        let reloaded: Value = read_config(&path).unwrap();
+        assert_eq!(reloaded["setting"]["es6"], true);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let dir = std::env::temp_dir().join("config_core_toml_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("project.config.toml");
+        std::fs::write(&path, "appid = \"tt1\"\nprojectname = \"游戏\"\n\n[setting]\nes6 = true\n").unwrap();
+
+        let mut value: Value = read_config(&path).unwrap();
+        assert_eq!(value["projectname"], "游戏");
+        value["appid"] = Value::from("tt2");
+        write_config(&path, &value).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("appid = \"tt2\""));
+        assert!(content.contains("[setting]"));
+        let reloaded: Value = read_config(&path).unwrap();
+        assert_eq!(reloaded["setting"]["es6"], true);
+
+        std::fs::write(&path, "appid = ").unwrap();
+        assert!(matches!(read_config::<Value>(&path), Err(ConfigError::Toml { .. })));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
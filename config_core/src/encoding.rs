@@ -0,0 +1,121 @@
+//! JS 文件的编码识别
+//!
+//! 中文 Windows 上生成的旧版打包产物可能以 GBK 保存。读取时先按 UTF-8 解码，
+//! 失败后再尝试 GBK；写回时使用读取时识别到的编码，避免把整个文件转换成 UTF-8。
+
+use crate::atomic::atomic_write;
+use crate::error::{ConfigError, Result};
+use std::path::Path;
+
+/// 文本文件的编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextEncoding {
+    #[default]
+    Utf8,
+    Gbk,
+}
+
+impl TextEncoding {
+    /// 界面上显示的编码名称
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Gbk => "GBK",
+        }
+    }
+
+    /// 按此编码把字节解码为字符串，存在无效字节时返回 None
+    pub fn decode(self, bytes: &[u8]) -> Option<String> {
+        match self {
+            Self::Utf8 => String::from_utf8(bytes.to_vec()).ok(),
+            Self::Gbk => encoding_rs::GBK
+                .decode_without_bom_handling_and_without_replacement(bytes)
+                .map(|s| s.into_owned()),
+        }
+    }
+
+    /// 按此编码把字符串编码为字节，存在无法表示的字符时返回 None
+    pub fn encode(self, content: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Utf8 => Some(content.as_bytes().to_vec()),
+            Self::Gbk => {
+                let (bytes, _, had_errors) = encoding_rs::GBK.encode(content);
+                (!had_errors).then(|| bytes.into_owned())
+            }
+        }
+    }
+}
+
+/// 读取文本文件并识别编码：优先 UTF-8，失败时回退到 GBK
+///
+/// # Returns
+/// * `Err(ConfigError::Encoding)` - 两种编码都无法解码
+pub fn read_text_detect(path: &Path) -> Result<(String, TextEncoding)> {
+    let bytes = std::fs::read(path).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    [TextEncoding::Utf8, TextEncoding::Gbk]
+        .into_iter()
+        .find_map(|encoding| encoding.decode(&bytes).map(|content| (content, encoding)))
+        .ok_or_else(|| ConfigError::Encoding { path: path.to_path_buf() })
+}
+
+/// 按指定编码原子地写入文本文件
+///
+/// # Returns
+/// * `Err(ConfigError::Unencodable)` - 内容中有该编码无法表示的字符（如 GBK 文件中写入了 emoji），文件保持不变
+pub fn write_text(path: &Path, content: &str, encoding: TextEncoding) -> Result<()> {
+    let bytes = encoding.encode(content).ok_or_else(|| ConfigError::Unencodable {
+        path: path.to_path_buf(),
+        encoding: encoding.label(),
+    })?;
+    atomic_write(path, bytes).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_gbk_round_trip() {
+        let dir = std::env::temp_dir().join("config_core_encoding_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("gbk.js");
+        // "// 中文\nd.appId=\"tt1\"" 的 GBK 编码
+        let mut gbk = b"// ".to_vec();
+        gbk.extend([0xD6, 0xD0, 0xCE, 0xC4]);
+        gbk.extend(b"\nd.appId=\"tt1\"");
+        fs::write(&path, &gbk).unwrap();
+
+        let (content, encoding) = read_text_detect(&path).unwrap();
+        assert_eq!(encoding, TextEncoding::Gbk);
+        assert_eq!(content, "// 中文\nd.appId=\"tt1\"");
+
+        // 写回时仍为 GBK
+        write_text(&path, &content.replace("tt1", "tt2"), encoding).unwrap();
+        let mut expected = gbk.clone();
+        let len = expected.len();
+        expected[len - 2] = b'2';
+        assert_eq!(fs::read(&path).unwrap(), expected);
+
+        // GBK 无法表示的字符不会被写成乱码
+        assert!(matches!(
+            write_text(&path, "😀", TextEncoding::Gbk),
+            Err(ConfigError::Unencodable { .. })
+        ));
+        assert_eq!(fs::read(&path).unwrap(), expected);
+
+        fs::write(&path, "中文").unwrap();
+        assert_eq!(read_text_detect(&path).unwrap().1, TextEncoding::Utf8);
+        // 两种编码都无效
+        fs::write(&path, [0xFF, 0xFF]).unwrap();
+        assert!(matches!(read_text_detect(&path), Err(ConfigError::Encoding { .. })));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
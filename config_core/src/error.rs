@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// 共享库的错误类型
+///
+/// 库函数返回具体的错误类型，调用方可以按失败原因分别处理
+/// （例如"没有找到要替换的字段"与"文件无法读写"区别对待）；
+/// 两个程序在 UI 边界处仍然统一转换为 `anyhow::Error`。
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// 文件读写失败
+    #[error("无法读写文件 {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// JSON 解析或序列化失败
+    #[error("无法解析 JSON 文件 {path}: {source}")]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    /// YAML 解析或序列化失败
+    #[error("无法解析 YAML 文件 {path}: {source}")]
+    Yaml {
+        path: PathBuf,
+        #[source]
+        source: serde_yaml::Error,
+    },
+    /// TOML 解析或序列化失败（toml 的解析和序列化错误类型不同，统一保存错误信息）
+    #[error("无法解析 TOML 文件 {path}: {message}")]
+    Toml { path: PathBuf, message: String },
+    /// 不支持的配置文件格式（扩展名不是 json / yaml / yml / toml）
+    #[error("不支持的配置文件格式: {path}")]
+    UnsupportedFormat { path: PathBuf },
+    /// 配置文件的根节点不是对象（如数组或字符串），无法写入字段
+    #[error("{path} 的根节点不是对象，无法写入 appid / projectname")]
+    NotAnObject { path: PathBuf },
+    /// JS 文件中没有找到要替换的字段（如 `.appId=` 或 `.douyinIds=[...]`）
+    #[error("{path} 中没有找到 {field} 字段")]
+    JsPatternNotFound { path: PathBuf, field: String },
+    /// 文件不是有效的 UTF-8 文本
+    #[error("{path} 不是有效的 UTF-8 文本")]
+    Encoding { path: PathBuf },
+    /// 文本中有字符无法用文件原来的编码（如 GBK）写回
+    #[error("{path} 包含无法用 {encoding} 编码的字符")]
+    Unencodable { path: PathBuf, encoding: &'static str },
+    /// 图片无法解码
+    #[error("无法读取图片 {path}: {source}")]
+    Image {
+        path: PathBuf,
+        #[source]
+        source: image::ImageError,
+    },
+}
+
+/// 共享库函数的返回类型
+pub type Result<T> = std::result::Result<T, ConfigError>;
@@ -0,0 +1,71 @@
+//! 按操作系统查找可显示中文的系统字体
+//!
+//! 两个程序的界面都需要中文字体，egui 自带的字体不含中文字符。
+
+use std::path::Path;
+
+/// 当前操作系统上的中文字体候选路径，按优先级排序
+#[cfg(target_os = "windows")]
+pub const FONT_CANDIDATES: &[&str] = &[
+    "C:\\Windows\\Fonts\\msyh.ttc",   // 微软雅黑
+    "C:\\Windows\\Fonts\\simhei.ttf", // 黑体
+    "C:\\Windows\\Fonts\\simsun.ttc", // 宋体
+];
+
+/// 当前操作系统上的中文字体候选路径，按优先级排序
+#[cfg(target_os = "macos")]
+pub const FONT_CANDIDATES: &[&str] = &[
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/STHeiti Medium.ttc",
+    "/System/Library/Fonts/STHeiti Light.ttc",
+    "/Library/Fonts/Arial Unicode.ttf",
+];
+
+/// 当前操作系统上的中文字体候选路径，按优先级排序
+/// Linux 及其他类 Unix 系统：Noto CJK 与文泉驿的常见安装位置
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub const FONT_CANDIDATES: &[&str] = &[
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-zenhei.ttc",
+    "/usr/share/fonts/wenquanyi/wqy-microhei/wqy-microhei.ttc",
+];
+
+/// 依次尝试候选路径，返回第一个能读取的字体文件
+///
+/// # Returns
+/// * `Option<(&str, Vec<u8>)>` - 字体路径及其内容，全部无法读取时为 None
+pub fn load_first_font<'a>(candidates: &[&'a str]) -> Option<(&'a str, Vec<u8>)> {
+    candidates
+        .iter()
+        .find_map(|path| std::fs::read(Path::new(path)).ok().map(|data| (*path, data)))
+}
+
+/// 读取当前系统上第一个可用的中文字体
+pub fn load_system_cjk_font() -> Option<(&'static str, Vec<u8>)> {
+    load_first_font(FONT_CANDIDATES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_font_candidates() {
+        assert!(!FONT_CANDIDATES.is_empty());
+
+        let dir = std::env::temp_dir().join("config_core_fonts_test");
+        fs::create_dir_all(&dir).unwrap();
+        let font = dir.join("font.ttc");
+        fs::write(&font, b"font").unwrap();
+        let font_path = font.to_str().unwrap();
+        let (path, data) = load_first_font(&["/nonexistent/font.ttc", font_path]).unwrap();
+        assert_eq!(path, font_path);
+        assert_eq!(data, b"font");
+        assert!(load_first_font(&["/nonexistent/font.ttc"]).is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
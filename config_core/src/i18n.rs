@@ -0,0 +1,500 @@
+//! 界面文字的中英文切换
+//!
+//! 以中文原文作为键：中文界面直接返回原文，英文界面查表，表中没有的文字仍显示中文。
+//! 这样界面代码只需把字符串字面量包在 [`tr`] 中，新增文字时忘记翻译也不会显示空白。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Lang {
+    #[default]
+    Zh,
+    En,
+}
+
+impl Lang {
+    /// 所有可选语言，用于语言切换菜单
+    pub const ALL: [Lang; 2] = [Lang::Zh, Lang::En];
+
+    /// 语言在切换菜单中的名称（始终使用该语言本身书写）
+    pub fn label(self) -> &'static str {
+        match self {
+            Lang::Zh => "中文",
+            Lang::En => "English",
+        }
+    }
+}
+
+/// 当前界面语言，0 为中文，1 为英文
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// 英文翻译表：(中文原文, 英文)
+static EN: &[(&str, &str)] = &[
+    ("取消", "Cancel"),
+    ("应用", "Apply"),
+    ("AppID:", "AppID:"),
+    ("确认还原", "Revert"),
+    ("确认修改", "Confirm"),
+    ("按住 Shift 点击可选择一段范围", "Shift+click to select a range"),
+    ("关闭", "Close"),
+    ("● 待保存", "● Unsaved"),
+    ("🧹 清理图片缓存", "🧹 Clear image cache"),
+    ("🧪 正则调试面板", "🧪 Regex debug panel"),
+    ("🧩 IDE", "🧩 IDE"),
+    ("🛠️ 字节小游戏配置助手", "🛠️ ByteDance Mini Game Config Helper"),
+    ("🔄 重试加载", "🔄 Retry"),
+    ("📦 打包全部选中", "📦 Zip selected projects"),
+    ("选择压缩包输出目录", "Choose the output folder for ZIP files"),
+    ("保存压缩包", "Save ZIP file"),
+    ("排除:", "Exclude:"),
+    ("压缩方式:", "Compression:"),
+    ("仅存储", "Store only"),
+    ("快速压缩", "Fast"),
+    ("标准压缩", "Normal"),
+    ("最大压缩", "Maximum"),
+    ("文件已存在", "File already exists"),
+    ("覆盖", "Overwrite"),
+    ("📦 打包", "📦 Zip"),
+    ("📥 导入 CSV 映射", "📥 Import CSV mapping"),
+    ("📄 分页显示", "📄 Paginate"),
+    ("📄 分页", "📄 Pages"),
+    ("📂 选择根目录", "📂 Choose root folder"),
+    ("📁 文件夹按项目名重命名", "📁 Rename folders to project names"),
+    ("📁 打开", "📁 Open"),
+    ("💾 保存所有更改", "💾 Save all changes"),
+    ("💾 保存后退出", "💾 Save and quit"),
+    ("💾 保存为模板", "💾 Save as profile"),
+    ("🌲 目录树", "🌲 Folder tree"),
+    ("🔍 扫描", "🔍 Scan"),
+    ("🔄 刷新列表", "🔄 Refresh"),
+    ("⚙ 设置", "⚙ Settings"),
+    ("🔎 搜索 JS 内容", "🔎 Search JS content"),
+    ("📋 配置模板", "📋 Config profiles"),
+    ("默认", "Default"),
+    ("预览图文件名:", "Preview file names:"),
+    ("项目名:", "Project name:"),
+    ("重置", "Reset"),
+    ("选择后立即扫描", "Scan right after choosing"),
+    ("跳过隐藏目录", "Skip hidden folders"),
+    ("跟随符号链接", "Follow symbolic links"),
+    ("跳过源目录中已有的 .zip 文件", "Skip existing .zip files in the source folder"),
+    ("路径、AppID 或项目名", "Path, AppID or project name"),
+    ("仅显示有JS", "With JS only"),
+    ("仅显示缺JS", "Missing JS only"),
+    ("路径:", "Path:"),
+    ("调试", "Debug"),
+    ("规范 JS 赋值的空格", "Normalize spacing of JS assignments"),
+    ("保存前备份为 .bak", "Back up to .bak before saving"),
+    ("修改前备份为 .bak", "Back up to .bak before modifying"),
+    ("恢复备份", "Restore backups"),
+    ("↶ 撤销", "↶ Undo"),
+    ("替换", "Replace"),
+    ("追加", "Append"),
+    ("去重追加", "Append unique"),
+    ("移除 DouyinIDs:", "Remove DouyinIDs:"),
+    ("移除", "Remove"),
+    ("逗号分隔，从选中项目中删除这些 ID", "Comma-separated IDs to delete from selected projects"),
+    ("↷ 重做", "↷ Redo"),
+    ("确认恢复", "Restore"),
+    ("已有较新的备份", "Newer backups exist"),
+    ("覆盖备份并保存", "Overwrite backups and save"),
+    ("保留旧备份并保存", "Keep old backups and save"),
+    ("覆盖备份并应用", "Overwrite backups and apply"),
+    ("保留旧备份并应用", "Keep old backups and apply"),
+    ("将用 .bak 备份覆盖所有项目的配置文件和 JS 文件并重新扫描，当前内容和未保存的修改都会丢失，是否继续？", "Overwrite the config and JS files of all projects with their .bak backups and rescan? Current contents and unsaved changes will be lost."),
+    ("将用 .bak 备份覆盖项目目录中的配置文件和 JS 文件，当前内容会丢失，是否继续？", "Overwrite the config and JS files in the project folder with their .bak backups? Current contents will be lost."),
+    ("自动递增", "Auto-increment"),
+    ("缩放:", "Zoom:"),
+    ("筛选:", "Filter:"),
+    ("磁盘上的变更", "Changes on disk"),
+    ("确认重命名文件夹", "Confirm folder rename"),
+    ("确认重命名", "Rename"),
+    ("确认退出", "Confirm quit"),
+    ("正在打包", "Packaging in progress"),
+    ("正在写入压缩包，打包完成后将自动退出。", "A ZIP archive is being written. The app will quit when packaging finishes."),
+    ("取消退出", "Cancel quit"),
+    ("确认批量修改", "Confirm batch edit"),
+    ("知道了", "OK"),
+    ("直接退出", "Quit without saving"),
+    ("目录树", "Folder tree"),
+    ("界面", "Interface"),
+    ("渠道目录层级:", "Channel folder depth:"),
+    ("浏览", "Browse"),
+    ("模板名称:", "Profile name:"),
+    ("校验预览图宽高比:", "Check preview aspect ratio:"),
+    ("暂无项目，请选择正确的根目录。", "No projects yet. Please choose the correct root folder."),
+    ("改为自动递增", "Switch to auto-increment"),
+    ("搜索", "Search"),
+    ("按渠道选中:", "Select by channel:"),
+    ("按宽度 (750px) 识别预览图", "Detect previews by width (750px)"),
+    ("展开卡片时再查找预览图", "Find previews when a card is expanded"),
+    ("批量操作需确认", "Confirm batch operations"),
+    ("批量修改影响预览", "Batch edit impact preview"),
+    ("批量修改 (仅针对选中项目)", "Batch edit (selected projects only)"),
+    ("扫描", "Scan"),
+    ("打开所在文件夹", "Open containing folder"),
+    ("打包结果", "Zip results"),
+    ("打包", "Packaging"),
+    ("开发者工具", "Developer tool"),
+    ("应用前预览影响", "Preview impact before applying"),
+    ("备注:", "Note:"),
+    ("在抖音开发者工具中打开此项目", "Open this project in the Douyin developer tool"),
+    ("图片探测线程数:", "Image probe threads:"),
+    ("只选中缺少 JS 配置的项目", "Select only projects missing a JS config"),
+    ("只选中此渠道下的项目", "Select only projects in this channel"),
+    ("参数:", "Arguments:"),
+    ("删除模板", "Delete profile"),
+    ("切换到卡片视图并定位到此项目", "Switch to card view and scroll to this project"),
+    ("全部渠道", "All channels"),
+    ("全选", "Select all"),
+    ("全不选", "Select none"),
+    ("反选", "Invert selection"),
+    ("保持不变", "Keep as is"),
+    ("保存", "Saving"),
+    ("从批量输入填充", "Fill from batch inputs"),
+    ("仍然全部显示", "Show all anyway"),
+    ("仅含 JS 配置的项目", "Only projects with a JS config"),
+    ("丢弃此项目未保存的修改", "Discard unsaved changes of this project"),
+    ("与其他项目同名，已改名", "Renamed to avoid a name clash"),
+    ("下一页 ▶", "Next ▶"),
+    ("◀ 上一页", "◀ Previous"),
+    ("➕ 添加", "➕ Add"),
+    ("❌ 加载失败", "❌ Failed to load"),
+    ("⚠ 预览图比例", "⚠ Preview aspect ratio"),
+    ("预览图（展开时查找）", "Previews (found on expand)"),
+    ("未找到预览图", "No preview images found"),
+    ("⚠ 需重新扫描", "⚠ Rescan needed"),
+    ("当前项目是按旧字段名扫描的，重新扫描前无法保存", "The current projects were scanned with the old field name; saving is disabled until you rescan"),
+    ("AppID 字段名已修改，请重新扫描后再保存", "The AppID field name changed; rescan before saving"),
+    ("正在查找预览图...", "Looking for previews..."),
+    ("点击查看原图", "Click to view full size"),
+    ("适应窗口", "Fit to window"),
+    ("⚠ 文件已不存在", "⚠ Files missing"),
+    ("⚠ 共享 JS", "⚠ Shared JS"),
+    ("⚠ AppID 重复", "⚠ Duplicate AppID"),
+    ("AppID 格式不正确", "Invalid AppID format"),
+    ("配置格式不正确", "Invalid configuration values"),
+    ("格式不正确的 AppID 可能导致游戏无法上传或运行，是否仍然应用？", "An invalid AppID may prevent the game from being uploaded or run. Apply anyway?"),
+    ("仍然应用", "Apply anyway"),
+    ("仍然保存", "Save anyway"),
+    ("⚠ JS 未识别 AppID", "⚠ No AppID in JS"),
+    ("☰ 紧凑列表", "☰ Compact list"),
+    ("☰ 切换到紧凑列表", "☰ Switch to compact list"),
+    ("↩ 还原", "↩ Revert"),
+    ("↩ 全部还原", "↩ Revert all"),
+    ("ℹ 配置与文件夹不符", "ℹ Config does not match folder"),
+    ("JS AppID 字段名:", "JS AppID field:"),
+    ("CSV 导入结果", "CSV import result"),
+    ("AppID 重复", "Duplicate AppID"),
+    ("统一 AppID:", "Set AppID:"),
+    ("统一项目名:", "Set name:"),
+    ("统一 DouyinIDs:", "Set DouyinIDs:"),
+    ("语言:", "Language:"),
+    ("字节跳动小游戏配置编辑器", "ByteDance Mini Game Config Editor"),
+    ("界面缩放:", "Zoom:"),
+    ("项目目录:", "Project folder:"),
+    ("选择目录", "Choose folder"),
+    ("加载", "Load"),
+    ("选择后立即加载", "Load right after choosing"),
+    ("请选择包含 project.config.json 和 JS 文件的项目目录", "Please choose a project folder containing project.config.json and JS files"),
+    ("应用修改", "Apply changes"),
+    ("正在修改配置...", "Applying changes..."),
+    ("✓ 修改成功!", "✓ Changes applied!"),
+    ("已修改的文件:", "Modified files:"),
+    ("支持修改 AppId 和 douyinIds 配置，显示 750px 宽图片", "Edits AppId and douyinIds, shows 750px-wide images"),
+    ("DouyinID 应为纯数字", "DouyinIDs must be numeric"),
+    ("移除此 ID", "Remove this ID"),
+    ("➡ 定位到此项目", "➡ Go to this project"),
+    ("关闭后，选择根目录只记录路径，需手动点击「扫描」", "When off, picking a root folder only records the path; click “Scan” yourself"),
+    ("恢复为 CPU 核心数", "Reset to the number of CPU cores"),
+    ("机械硬盘上建议调低线程数，避免频繁寻道拖慢扫描", "Use fewer threads on hard disks to avoid slow seeking"),
+    ("不进入以 . 开头的文件夹（如 .cache、.vscode、.git），扫描更快，也不会显示其中的图片", "Skip folders starting with . (such as .cache, .vscode, .git); scans are faster and their images are not shown"),
+    ("扫描和打包时进入符号链接（目录联接）指向的目录；关闭时忽略链接本身。指向上级目录的循环链接总会被跳过", "Follow symbolic links (directory junctions) when scanning and packaging; when off, links are ignored. Links that loop back to a parent folder are always skipped"),
+    ("扫描时跳过没有找到 JS 配置的项目，只批量编辑 JS 时减少干扰", "Skip projects without a JS config, to reduce clutter when only editing JS"),
+    ("部分打包产物使用 aid、applicationId 等字段名；修改后需重新扫描", "Some builds use field names such as aid or applicationId; rescan after changing it"),
+    ("按目录约定识别渠道：1 表示 根目录/<渠道>/<游戏>/project.config.json；0 表示不识别", "Detect channels from the folder layout: 1 means root/<channel>/<game>/project.config.json; 0 turns detection off"),
+    ("扫描时只解析配置，不遍历素材目录；宽高比校验只对已查找过预览图的项目生效", "Only parse configs while scanning, without walking asset folders; aspect-ratio checks only cover projects whose previews have been looked up"),
+    ("逗号分隔的通配符模式（* 任意字符，? 单个字符，不区分大小写），与宽度规则满足其一即可", "Comma-separated wildcard patterns (* any characters, ? one character, case-insensitive); matching either this or the width rule is enough"),
+    ("逗号分隔的宽高比，如 3:4, 1:1（允许 2% 误差）；不符合任一比例的预览图会在项目上提示", "Comma-separated aspect ratios such as 3:4, 1:1 (2% tolerance); previews matching none of them are flagged on the project"),
+    ("快捷键：Ctrl+= 放大，Ctrl+- 缩小，Ctrl+0 重置", "Shortcuts: Ctrl+= zoom in, Ctrl+- zoom out, Ctrl+0 reset"),
+    ("释放已加载的预览图以减少内存占用，再次查看项目时会重新加载", "Free loaded previews to reduce memory use; they are reloaded when a project is viewed again"),
+    ("保存时把修改的字段写成 .appId = \"x\"、.douyinIds = [...] 的形式，不会格式化文件的其他部分", "Write edited fields as .appId = \"x\", .douyinIds = [...] when saving; the rest of the file is not reformatted"),
+    ("每个文件在本次运行中第一次被覆盖前，把原内容复制为同目录下的 <文件名>.bak", "Before a file is first overwritten in this session, copy its content to <file name>.bak next to it"),
+    ("抖音开发者工具或其 CLI 的可执行文件", "Executable of the Douyin developer tools or its CLI"),
+    ("以空格分隔，{project} 会被替换为项目目录", "Space-separated; {project} is replaced with the project folder"),
+    ("避免把之前打出的压缩包嵌套进新的压缩包；输出文件本身始终会被排除", "Avoid nesting earlier archives inside new ones; the output file itself is always excluded"),
+    ("逗号分隔的通配符模式（* 任意字符，? 单个字符，不区分大小写），与每个文件和文件夹的名称比较，匹配的文件夹整个跳过", "Comma-separated wildcard patterns (* any characters, ? one character, case-insensitive) matched against every file and folder name; matching folders are skipped entirely"),
+    ("粘贴 JS 片段，查看 AppID / DouyinIDs 的匹配和替换结果，不会修改任何文件", "Paste a JS snippet to see how AppID / DouyinIDs are matched and replaced; no files are changed"),
+    ("如旧的 SDK Key", "e.g. an old SDK key"),
+    ("先列出会被写入的配置文件、JS 文件及新值，确认后才修改", "List the config files, JS files and new values that will be written, and only change them after confirmation"),
+    ("按选中顺序为每个项目的 AppID 末尾数字依次加 1，例如 tt0001、tt0002 ...", "Increase the trailing number of the AppID by 1 for each project in selection order, e.g. tt0001, tt0002 ..."),
+    ("替换：用输入的 ID 覆盖原有列表；追加：加到每个项目原有列表的末尾；去重追加：跳过项目中已有的 ID", "Replace: overwrite the list with the entered IDs; Append: add them to the end of each project's list; Append unique: skip IDs the project already has"),
+    ("按文件夹名称匹配项目，表头为 folder、appid、projectname、douyinIds；\nfolder 中包含 / 时按路径结尾匹配", "Match projects by folder name; the header is folder, appid, projectname, douyinIds;\na folder containing / is matched against the end of the path"),
+    ("选择输出目录后逐个打包选中的项目，同名项目的压缩包会自动加上 AppID 区分", "Pick an output folder and package the selected projects one by one; archives of projects with the same name get their AppID appended"),
+    ("正在打包，请等待完成", "Packaging in progress, please wait"),
+    ("把选中项目所在的文件夹重命名为项目名称，名称冲突时自动加后缀", "Rename the selected projects' folders to their project names, adding a suffix on conflicts"),
+    ("留空的字段不会被修改；支持占位符 {folder}（文件夹名）和 {index}（选中序号）", "Empty fields are left unchanged; supports the placeholders {folder} (folder name) and {index} (selection order)"),
+    ("这些项目没有找到 index.js / application.js 等 JS 配置，DouyinIDs 和 JS 中的 AppID 无法批量写入", "No JS config such as index.js / application.js was found for these projects, so DouyinIDs and the JS AppID cannot be batch-written"),
+    ("每个项目只显示一行，项目很多时更流畅", "One line per project; smoother with many projects"),
+    ("可以在上方输入筛选条件，或切换到紧凑列表。", "Enter a filter above, or switch to the compact list."),
+    ("按目录约定识别的渠道", "Channel detected from the folder layout"),
+    ("配置文件或 JS 文件已被移动或删除，保存时会跳过此项目，请重新扫描", "The config or JS file was moved or deleted; this project is skipped when saving. Please rescan"),
+    ("JS 文件中没有找到 .appId=\"...\" 赋值，保存时不会写入 JS 的 AppID", "No .appId=\"...\" assignment found in the JS file; the JS AppID will not be written when saving"),
+    ("如 待审核 / 已提交", "e.g. pending review / submitted"),
+    ("该 JS 文件不是 UTF-8 编码，保存时按原编码写回；无法用该编码表示的字符会导致保存失败", "This JS file is not UTF-8 and is written back in its original encoding; characters that encoding cannot represent make saving fail"),
+    ("🔍 JS 匹配位置（扫描时）", "🔍 JS match positions (at scan time)"),
+    ("查看图片路径", "Show image paths"),
+    ("此操作会移动磁盘上的目录，无法通过\"还原\"撤销。", "This moves folders on disk and cannot be undone with \"Revert\"."),
+    ("以下 AppID 被多个项目使用，上传时可能互相覆盖：", "These AppIDs are used by several projects and may overwrite each other on upload:"),
+    ("与上一次扫描相比，以下项目的配置已在外部被修改：", "Since the last scan, the configs of these projects were changed outside the editor:"),
+    ("🧪 正则调试", "🧪 Regex debugger"),
+    ("JS 片段:", "JS snippet:"),
+    ("粘贴打包后 JS 中的一段内容，如 d.appId=\"tt123\",d.douyinIds=[\"a\"]", "Paste part of the built JS, e.g. d.appId=\"tt123\",d.douyinIds=[\"a\"]"),
+    ("替换 AppID:", "Replace AppID:"),
+    ("替换 DouyinIDs:", "Replace DouyinIDs:"),
+    ("替换结果:", "Result:"),
+    ("部分打包产物使用 aid、applicationId 等字段名；修改后点击\"加载\"重新读取", "Some builds use field names such as aid or applicationId; click \"Load\" again after changing it"),
+    ("AppId (来自 JS):", "AppId (from JS):"),
+    ("project.config.json 中 appid 为空，显示的是 JS 文件中的 appId；保存时会同步写入两处", "appid in project.config.json is empty, so the appId from the JS file is shown; saving writes it to both places"),
+    ("每个文件在本次运行中第一次被修改前，把原内容复制为同目录下的 <文件名>.bak", "Before a file is first modified in this session, copy its content to <file name>.bak next to it"),
+    ("版本: 0.1.2", "Version: 0.1.2"),
+    ("全部还原", "Revert all"),
+    ("CSV 导入", "CSV import"),
+    ("输出目录中已有以下 {} 个压缩包，是否覆盖？", "The output folder already contains these {} archives. Overwrite them?"),
+    ("保存更改", "Save changes"),
+    ("批量应用 AppID", "Batch apply AppID"),
+    ("批量应用项目名", "Batch apply project name"),
+    ("批量应用 DouyinIDs", "Batch apply DouyinIDs"),
+    ("批量移除 DouyinIDs", "Batch remove DouyinIDs"),
+    ("应用配置模板", "Apply config template"),
+    ("⚠ {} 个非数字 ID", "⚠ {} non-numeric IDs"),
+    ("DouyinID 应为纯数字，以下 ID 格式不正确：\n{}", "DouyinIDs must be numeric; these IDs are invalid:\n{}"),
+    ("📦 正在打包 {} ({}/{})", "📦 Packaging {} ({}/{})"),
+    ("撤销：{}（Ctrl+Z）", "Undo: {} (Ctrl+Z)"),
+    ("重做：{}（Ctrl+Y）", "Redo: {} (Ctrl+Y)"),
+    ("当前路径: {}", "Current path: {}"),
+    ("上次扫描耗时: {}s（目录遍历 {}s，图片探测 {}s）", "Last scan: {}s (directory walk {}s, image probing {}s)"),
+    ("已缓存图片: {}", "Cached images: {}"),
+    ("不压缩，速度最快，压缩包最大", "No compression: fastest, largest archive"),
+    ("压缩较快，压缩包略大", "Faster, slightly larger archive"),
+    ("速度与体积均衡", "Balanced speed and size"),
+    ("压缩包最小，速度最慢", "Smallest archive, slowest"),
+    ("{} ({} 处)", "{} ({} hits)"),
+    ("作用于当前筛选结果中的项目，被筛选隐藏的项目保持不变", "Applies to projects in the current filter; hidden projects are left unchanged"),
+    ("已选 {} / 共 {}", "Selected {} / {}"),
+    ("选中超过 {} 个项目时，批量应用前弹出确认", "Ask for confirmation before batch edits on more than {} projects"),
+    ("选中的项目都没有 JS 配置，DouyinIDs 只会写入 JS 文件", "None of the selected projects has a JS config; DouyinIDs are only written to JS files"),
+    ("正在匹配 CSV：{} / {} 行", "Matching CSV: {} / {} rows"),
+    ("AppID: {} | 名称: {} | DouyinIDs: {}", "AppID: {} | Name: {} | DouyinIDs: {}"),
+    ("只显示找到了 JS 配置的项目", "Show only projects with a JS config"),
+    ("只显示没有找到 JS 配置的项目，这些项目不受 DouyinIDs 等 JS 字段的批量修改影响", "Show only projects without a JS config; batch edits of JS fields such as DouyinIDs do not affect them"),
+    ("⚠ {} 个项目缺少 JS", "⚠ {} projects without JS"),
+    ("卡片每页显示 {} 个项目，选中和批量操作仍作用于所有页", "Show {} cards per page; selection and batch edits still cover all pages"),
+    ("⚠ 扫描到 {} 个项目，全部以卡片显示可能导致界面卡顿。", "⚠ Found {} projects; showing them all as cards may make the UI sluggish."),
+    ("第 {} / {} 页（共 {} 个项目）", "Page {} / {} ({} projects)"),
+    ("此项目的 JS 文件同时被以下项目使用，修改会互相影响：\n{}", "This project's JS file is also used by the following projects; edits affect each other:\n{}"),
+    ("以下项目使用了相同的 AppID：\n{}", "The following projects use the same AppID:\n{}"),
+    ("以下预览图不符合允许的宽高比（{}）：\n{}", "These previews do not match the allowed aspect ratios ({}):\n{}"),
+    ("逐条编辑 DouyinIDs ({})", "Edit DouyinIDs one by one ({})"),
+    ("逐条编辑 {} ({})", "Edit {} one by one ({})"),
+    ("预览图 (共{}张)", "Previews ({})"),
+    ("保存后将写入以下 {} 个文件：", "Saving will write the following {} files:"),
+    ("AppID (含JS) 修改为", "AppID (incl. JS) to"),
+    ("项目名称修改为", "project name to"),
+    ("DouyinIDs (仅JS) 修改为", "DouyinIDs (JS only) to"),
+    ("DouyinIDs (仅JS) 中移除", "DouyinIDs (JS only) by removing"),
+    ("将把 {} 个选中项目的 {}：", "Change {} selected projects' {}:"),
+    ("将丢弃 {} 个项目的所有未保存修改，是否继续？", "All unsaved changes in {} projects will be discarded. Continue?"),
+    ("有 {} 个项目存在未保存的更改。", "{} projects have unsaved changes."),
+    ("{} 个文件的 .bak 备份比文件本身更新，覆盖后将无法再恢复这些备份：", "{} files have a .bak backup newer than the file itself; overwriting makes those backups unrecoverable:"),
+    ("待保存的项目中有 {} 处格式不正确，可能导致游戏无法上传或运行：", "{} values to be saved are malformed and may stop the game from uploading or running:"),
+    ("{} 已存在，是否覆盖？", "{} already exists. Overwrite it?"),
+    ("将把 {} 个选中项目所在的文件夹重命名为项目名称。", "The folders of {} selected projects will be renamed to their project names."),
+    ("已匹配并应用 {} 行，未匹配 {} 行，有歧义 {} 行。", "{} rows matched and applied, {} unmatched, {} ambiguous."),
+    ("第 {} 行「{}」：未找到项目", "Row {} “{}”: no matching project"),
+    ("第 {} 行「{}」：多个项目同名，请选择：", "Row {} “{}”: several projects share this name, pick one:"),
+    ("失败: {}", "Failed: {}"),
+    ("正在保存更改...", "Saving changes..."),
+    ("❌ 加载失败: {}", "❌ Failed to load: {}"),
+    ("AppID 正则: {}", "AppID regex: {}"),
+    ("✖ 未匹配到 .{}=\"...\"", "✖ No match for .{}=\"...\""),
+    ("✖ 未匹配到 .xxxIds=[...]", "✖ No match for .xxxIds=[...]"),
+    ("项目名称「{}」与文件夹「{}」不符", "Project name “{}” does not match folder “{}”"),
+    ("AppID 和名称与 {} 完全相同", "AppID and name are identical to {}"),
+    ("可能是复制项目时带入了其他项目的配置", "The config may have been copied over from another project"),
+    ("选择字节跳动小游戏项目目录", "Select a ByteDance mini-game project directory"),
+    ("图片预览 (共 {} 张, 宽度 750px)", "Image previews ({}, 750px wide)"),
+    ("路径: {}", "Path: {}"),
+    ("尺寸: {}×{}", "Size: {}×{}"),
+    ("已恢复上次的根目录 {}，点击「🔍 扫描」开始。", "Restored the last root folder {}. Click “🔍 Scan” to start."),
+    ("准备就绪。请选择包含小游戏项目的文件夹。", "Ready. Choose a folder that contains mini game projects."),
+    ("正在扫描...", "Scanning..."),
+    ("扫描意外中断", "The scan stopped unexpectedly"),
+    ("正在扫描... 已找到 {} 个项目", "Scanning... {} projects found"),
+    ("{}，共找到 {} 个配置文件（耗时 {}s：目录遍历 {}s，图片探测 {}s；图片探测线程: {}）", "{}: found {} config files (took {}s: directory walk {}s, image probing {}s; probe threads: {})"),
+    ("扫描已取消", "Scan cancelled"),
+    ("扫描完成", "Scan finished"),
+    ("，{} 张图片未变化，复用上次结果", ", {} unchanged images reused from the last scan"),
+    ("。{} 个条目无法访问，已跳过", ". {} inaccessible entries skipped"),
+    ("。{} 个项目没有 JS 配置，已按设置跳过", ". {} projects without a JS config skipped as configured"),
+    ("。⚠ {} 个项目缺少 JS 配置，无法写入 DouyinIDs", ". ⚠ {} projects have no JS config, so DouyinIDs cannot be written"),
+    ("。⚠ {} 个项目的 JS 未识别到 AppID", ". ⚠ No AppID found in the JS of {} projects"),
+    ("。⚠ 发现 {} 个 JS 文件被多个项目共享，修改其一会影响其他项目", ". ⚠ {} JS files are shared by several projects; editing one affects the others"),
+    ("。⚠ 发现 {} 组重复 AppID", ". ⚠ {} groups of duplicate AppIDs found"),
+    ("。{} 个项目的配置在磁盘上已被修改", ". {} projects were changed on disk"),
+    ("。⚠ 读取备注失败: {}", ". ⚠ Failed to read notes: {}"),
+    ("保存结束：成功 {} 个，失败 {} 个", "Save finished: {} succeeded, {} failed"),
+    ("（如 {}）", " (e.g. {})"),
+    ("，{} 个项目的文件已不存在（可能被移动或删除），已跳过，请重新扫描", ", {} projects were skipped because their files no longer exist (moved or deleted?). Please rescan"),
+    ("已从备份恢复 {} 个文件，失败 {} 个，正在重新扫描...", "Restored {} files from backups, {} failed. Rescanning..."),
+    ("已还原 {} 个项目的未保存修改", "Reverted unsaved changes in {} projects"),
+    ("已撤销：{}", "Undone: {}"),
+    ("没有可撤销的操作", "Nothing to undo"),
+    ("已重做：{}", "Redone: {}"),
+    ("没有可重做的操作", "Nothing to redo"),
+    ("已重命名 {} 个项目文件夹", "Renamed {} project folders"),
+    ("，{} 个失败：{}", ", {} failed: {}"),
+    ("。已跟随 {} 个符号链接", ". Followed {} symbolic links"),
+    ("。{} 个符号链接未跟随（可在设置中开启「跟随符号链接」）", ". {} symbolic links not followed (enable “Follow symbolic links” in Settings)"),
+    ("，跳过 {} 个循环链接", ", skipped {} looping links"),
+    ("{}: {} 个项目已更新{}", "{}: {} projects updated{}"),
+    ("；{} 个项目的值本来就相同", "; {} projects already had this value"),
+    ("，请点击保存生效。", ". Click Save to apply."),
+    ("已选中渠道「{}」的 {} 个项目", "Selected channel “{}”: {} projects"),
+    ("保存备注失败: {}", "Failed to save notes: {}"),
+    ("正在导入 CSV 映射...", "Importing CSV mapping..."),
+    ("CSV 导入完成：匹配 {} 行（{} 个项目有修改），未匹配 {} 行，有歧义 {} 行，请点击保存生效。", "CSV import finished: {} rows matched ({} projects changed), {} unmatched, {} ambiguous. Click Save to apply."),
+    ("AppID「{}」不以数字结尾，无法自动递增，请逐个粘贴 AppID。", "AppID “{}” does not end with a number and cannot be incremented. Paste the AppIDs one by one."),
+    ("（其中 {} 个同时更新了 JS{}）", " ({} also updated in JS{})"),
+    ("，按末尾数字递增", ", incrementing the trailing number"),
+    (" ⚠ AppID 递增超出范围，之后的项目未应用", " ⚠ AppID increment overflowed; the remaining projects were not changed"),
+    ("项目名", "Project name"),
+    ("（{} 个选中项目没有 JS 配置，已跳过）", " ({} selected projects have no JS config and were skipped)"),
+    ("（输入中 {} 个重复的 ID 已去掉）", " ({} duplicate IDs removed from the input)"),
+    ("（⚠ {} 个 ID 不是纯数字：{}，保存前会再次确认）", " (⚠ {} IDs are not numeric: {}; you will be asked again before saving)"),
+    ("DouyinIDs（{}）", "DouyinIDs ({})"),
+    ("移除 DouyinIDs: {} 个项目已更新", "Remove DouyinIDs: {} projects updated"),
+    ("；{} 个项目不包含这些 ID", "; {} projects do not contain these IDs"),
+    ("；{} 个选中项目没有 JS 配置，已跳过", "; {} selected projects have no JS config and were skipped"),
+    ("已将模板「{}」应用到 {} 个项目，请点击保存生效。", "Applied profile “{}” to {} projects. Click Save to apply."),
+    ("已在开发者工具中打开: {}", "Opened in the developer tools: {}"),
+    ("无法启动开发者工具：{}", "Failed to launch the developer tools: {}"),
+    ("已有打包任务在进行中，请等待完成", "A packaging task is already running. Please wait for it to finish"),
+    ("错误：无法获取配置文件所在目录", "Error: cannot determine the folder of the config file"),
+    ("开始打包（{}：{}）...", "Packaging started ({}: {})..."),
+    ("打包意外中断", "Packaging stopped unexpectedly"),
+    ("打包成功（{}）: {}", "Packaged ({}): {}"),
+    ("打包失败: {}", "Packaging failed: {}"),
+    ("打包完成（{}，输出到 {}）：成功 {} 个，失败 {} 个", "Packaging finished ({}, output to {}): {} succeeded, {} failed"),
+    ("，{} 个同名压缩包已自动改名", ", {} ZIP files with duplicate names were renamed"),
+    ("。失败的项目：{}", ". Failed projects: {}"),
+    ("搜索完成，{} 个项目的 JS 包含「{}」", "Search finished: the JS of {} projects contains “{}”"),
+    ("CSV 导入失败: {}", "CSV import failed: {}"),
+    ("已选择根目录，调整扫描设置后点击「🔍 扫描」开始。", "Root folder selected. Adjust the scan settings, then click “🔍 Scan” to start."),
+    ("已清理 {} 张图片的缓存", "Cleared the cache of {} images"),
+    ("正在搜索「{}」...", "Searching for “{}”..."),
+    ("已去掉 {} 个重复的 DouyinID", "Removed {} duplicate DouyinIDs"),
+    ("项目名称", "Project name"),
+    ("、", ", "),
+    ("JS 配置:", "JS config:"),
+    ("DouyinIDs", "Douyin IDs"),
+    ("图片 #{}:", "Image #{}:"),
+    ("AppID “{}”：{}", "AppID “{}”: {}"),
+    ("DouyinIDs 中 {} 个不是纯数字：{}", "{} DouyinIDs are not numeric: {}"),
+    ("请选择字节跳动小游戏项目目录", "Select a ByteDance mini-game project directory"),
+    ("已恢复上次的目录，点击\"加载\"读取配置", "Restored the last directory; click “Load” to read the config"),
+    ("成功加载配置: {}", "Loaded config: {}"),
+    ("project.config.json 中 appid 为空，已使用 JS 文件中的 appId", "appid in project.config.json is empty; using the appId from the JS file"),
+    ("（{} 个条目无法访问，已跳过）", " ({} inaccessible entries skipped)"),
+    ("已从备份恢复 {} 个文件", "Restored {} files from backup"),
+    ("，{} 个文件恢复失败（如 {}）", ", {} files failed to restore (e.g. {})"),
+    ("请先选择项目目录", "Select a project directory first"),
+    ("修改配置失败: {}", "Failed to modify config: {}"),
+    ("成功修改 {} 个文件", "Modified {} files"),
+    ("，{} 个 JS 文件修改失败（如 {}: {}）", ", {} JS files failed (e.g. {}: {})"),
+    ("；{}", "; {}"),
+    ("已重置为原始值", "Reset to the original values"),
+    ("已选择目录，点击\"加载\"读取配置", "Directory selected; click “Load” to read the config"),
+    ("AppId:", "AppId:"),
+];
+
+/// 设置当前界面语言
+pub fn set_lang(lang: Lang) {
+    CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+/// 当前界面语言
+pub fn lang() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Lang::En,
+        _ => Lang::Zh,
+    }
+}
+
+/// 将中文原文翻译为指定语言，没有对应翻译时返回原文
+pub fn translate(lang: Lang, key: &'static str) -> &'static str {
+    static TABLE: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    match lang {
+        Lang::Zh => key,
+        Lang::En => TABLE.get_or_init(|| EN.iter().copied().collect()).get(key).copied().unwrap_or(key),
+    }
+}
+
+/// 按当前界面语言翻译界面文字
+pub fn tr(key: &'static str) -> &'static str {
+    translate(lang(), key)
+}
+
+/// 按当前界面语言翻译带 `{}` 占位符的文字，再依次填入 `args`
+/// 译文中占位符的数量和顺序须与原文一致；需要 `{:.2}` 等格式时先把参数格式化为字符串
+pub fn trf(key: &'static str, args: &[&dyn std::fmt::Display]) -> String {
+    fill(tr(key), args)
+}
+
+/// 把 `template` 中的 `{}` 依次替换为 `args`，多余的占位符保持原样
+fn fill(template: &str, args: &[&dyn std::fmt::Display]) -> String {
+    let mut parts = template.split("{}");
+    let mut output = parts.next().unwrap_or_default().to_string();
+    let mut args = args.iter();
+    for part in parts {
+        match args.next() {
+            Some(arg) => output.push_str(&arg.to_string()),
+            None => output.push_str("{}"),
+        }
+        output.push_str(part);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(translate(Lang::Zh, "应用"), "应用");
+        assert_eq!(translate(Lang::En, "应用"), "Apply");
+        // 没有翻译的文字保持原文
+        assert_eq!(translate(Lang::En, "未翻译的文字"), "未翻译的文字");
+        // 翻译表中的键不能重复，否则后面的翻译永远不会生效
+        let mut keys: Vec<&str> = EN.iter().map(|(k, _)| *k).collect();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), EN.len());
+        // 译文的占位符数量必须与原文一致
+        for (zh, en) in EN {
+            assert_eq!(zh.matches("{}").count(), en.matches("{}").count(), "{}", zh);
+        }
+    }
+
+    #[test]
+    fn test_fill_placeholders() {
+        assert_eq!(fill("第 {} / {} 页", &[&1, &"3"]), "第 1 / 3 页");
+        assert_eq!(fill("{} 个", &[]), "{} 个");
+        assert_eq!(fill("无占位符", &[&1]), "无占位符");
+    }
+}
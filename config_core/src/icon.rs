@@ -0,0 +1,30 @@
+/// 将图标文件（ico、png 等 image 支持的格式）解码为 RGBA 像素
+///
+/// 与具体 GUI 框架无关，调用方再转换为 `egui::IconData`。
+///
+/// # Returns
+/// * `Some((rgba, width, height))` - 解码成功
+/// * `None` - 数据不是可识别的图片
+pub fn decode_icon(bytes: &[u8]) -> Option<(Vec<u8>, u32, u32)> {
+    let image = image::load_from_memory(bytes).ok()?.into_rgba8();
+    let (width, height) = image.dimensions();
+    Some((image.into_raw(), width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_icon() {
+        let mut png = Vec::new();
+        image::RgbaImage::new(16, 8)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let (rgba, w, h) = decode_icon(&png).unwrap();
+        assert_eq!((w, h), (16, 8));
+        assert_eq!(rgba.len(), 16 * 8 * 4);
+
+        assert!(decode_icon(b"not an image").is_none());
+    }
+}
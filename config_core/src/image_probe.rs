@@ -0,0 +1,171 @@
+use crate::error::{ConfigError, Result};
+use std::path::Path;
+
+/// 预览图的默认宽度（像素）
+/// 这是一个特定的业务规则，用于识别项目中的预览图
+pub const PREVIEW_WIDTHS: &[u32] = &[750];
+
+/// 校验预览图宽高比时允许的相对误差（2%）
+/// 图片导出时常有一两个像素的舍入，如 750×1001 仍视为 3:4
+pub const ASPECT_TOLERANCE: f32 = 0.02;
+
+/// 判断文件扩展名是否为支持的图片格式（不区分大小写）
+pub fn is_image_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|ext| matches!(ext.to_lowercase().as_str(), "png" | "jpg" | "jpeg" | "bmp" | "webp"))
+        .unwrap_or(false)
+}
+
+/// 检查图片宽度是否在给定的宽度列表中
+/// 只读取文件头中的尺寸，不解码像素，大图也很快
+///
+/// # Arguments
+/// * `path` - 图片文件路径
+/// * `widths` - 允许的宽度列表（如 `PREVIEW_WIDTHS`）
+///
+/// # Returns
+/// * `Ok(bool)` - 图片宽度是否匹配
+/// * `Err(ConfigError::Image)` - 文件无法读取或不是有效的图片
+pub fn image_matches_width(path: &Path, widths: &[u32]) -> Result<bool> {
+    let (width, _) = image_size(path)?;
+    Ok(widths.contains(&width))
+}
+
+/// 读取图片尺寸（宽, 高），只解析文件头，不解码像素
+///
+/// # Returns
+/// * `Err(ConfigError::Image)` - 文件无法读取或不是有效的图片
+pub fn image_size(path: &Path) -> Result<(u32, u32)> {
+    image::image_dimensions(path).map_err(|source| ConfigError::Image {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// 把图片按比例缩小到不超过 `max_width` 宽，用于生成上传到 GPU 的缩略图
+/// 本身不超过该宽度的图片原样返回
+pub fn thumbnail(img: image::DynamicImage, max_width: u32) -> image::DynamicImage {
+    if img.width() <= max_width {
+        return img;
+    }
+    img.resize(max_width, u32::MAX, image::imageops::FilterType::Triangle)
+}
+
+/// 解析逗号分隔的宽高比列表，如 `3:4, 1:1`
+/// 无法解析或含 0 的项会被忽略
+pub fn parse_aspect_ratios(input: &str) -> Vec<(u32, u32)> {
+    input
+        .split(',')
+        .filter_map(|item| {
+            let (w, h) = item.trim().split_once(':')?;
+            let ratio = (w.trim().parse().ok()?, h.trim().parse().ok()?);
+            (ratio.0 > 0 && ratio.1 > 0).then_some(ratio)
+        })
+        .collect()
+}
+
+/// 检查图片尺寸是否符合任一允许的宽高比（相对误差在 `ASPECT_TOLERANCE` 以内）
+/// 比例列表为空时不做限制
+pub fn matches_aspect_ratio((width, height): (u32, u32), ratios: &[(u32, u32)]) -> bool {
+    if ratios.is_empty() {
+        return true;
+    }
+    if height == 0 {
+        return false;
+    }
+    let actual = width as f32 / height as f32;
+    ratios.iter().any(|&(w, h)| {
+        let expected = w as f32 / h as f32;
+        ((actual - expected) / expected).abs() <= ASPECT_TOLERANCE
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_image_matches_width() {
+        let dir = std::env::temp_dir().join("config_core_image_probe_test");
+        fs::create_dir_all(&dir).unwrap();
+
+        let matching = dir.join("preview.png");
+        let other = dir.join("icon.png");
+        let corrupt = dir.join("broken.png");
+        image::RgbImage::new(750, 20).save(&matching).unwrap();
+        image::RgbImage::new(128, 128).save(&other).unwrap();
+        fs::write(&corrupt, b"not an image").unwrap();
+
+        assert!(image_matches_width(&matching, PREVIEW_WIDTHS).unwrap());
+        assert!(!image_matches_width(&other, PREVIEW_WIDTHS).unwrap());
+        assert!(image_matches_width(&other, &[750, 128]).unwrap());
+        assert!(matches!(
+            image_matches_width(&corrupt, PREVIEW_WIDTHS),
+            Err(ConfigError::Image { .. })
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_width_filter_same_as_full_decode() {
+        let dir = std::env::temp_dir().join("config_core_width_filter_test");
+        fs::create_dir_all(&dir).unwrap();
+        image::RgbImage::new(750, 40).save(dir.join("a.png")).unwrap();
+        image::RgbImage::new(750, 30).save(dir.join("b.jpg")).unwrap();
+        image::RgbImage::new(749, 40).save(dir.join("c.png")).unwrap();
+        image::RgbImage::new(40, 750).save(dir.join("d.bmp")).unwrap();
+
+        // 只读文件头的判断与完整解码后取宽度的结果一致
+        let mut paths: Vec<_> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().path()).collect();
+        paths.sort();
+        let by_header: Vec<_> = paths.iter().filter(|p| image_matches_width(p, PREVIEW_WIDTHS).unwrap()).collect();
+        let by_decode: Vec<_> = paths
+            .iter()
+            .filter(|p| PREVIEW_WIDTHS.contains(&image::open(p).unwrap().width()))
+            .collect();
+        assert_eq!(by_header, by_decode);
+        assert_eq!(by_header, vec![&dir.join("a.png"), &dir.join("b.jpg")]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_aspect_ratio() {
+        let ratios = parse_aspect_ratios("3:4, 1:1, x:2, 0:1,");
+        assert_eq!(ratios, vec![(3, 4), (1, 1)]);
+        assert!(matches_aspect_ratio((750, 1000), &ratios));
+        assert!(matches_aspect_ratio((750, 1001), &ratios));
+        assert!(matches_aspect_ratio((750, 750), &ratios));
+        assert!(!matches_aspect_ratio((750, 1334), &ratios));
+        assert!(matches_aspect_ratio((750, 1334), &[]));
+
+        let dir = std::env::temp_dir().join("config_core_image_size_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cover.png");
+        image::RgbImage::new(30, 40).save(&path).unwrap();
+        assert_eq!(image_size(&path).unwrap(), (30, 40));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_thumbnail() {
+        let wide = image::DynamicImage::new_rgb8(750, 1000);
+        let thumb = thumbnail(wide, 256);
+        assert_eq!((thumb.width(), thumb.height()), (256, 341));
+
+        let small = image::DynamicImage::new_rgb8(128, 64);
+        let same = thumbnail(small, 256);
+        assert_eq!((same.width(), same.height()), (128, 64));
+    }
+
+    #[test]
+    fn test_is_image_file() {
+        assert!(is_image_file(Path::new("a/b.PNG")));
+        assert!(is_image_file(Path::new("cover.jpeg")));
+        assert!(!is_image_file(Path::new("index.js")));
+        assert!(!is_image_file(Path::new("noext")));
+    }
+}
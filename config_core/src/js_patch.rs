@@ -0,0 +1,338 @@
+//! JS 配置的读取与替换
+//!
+//! 两个程序都需要在打包后的 JS 中读取和修改 `.appId="..."` 与 `.douyinIds=[...]`，
+//! 这里是唯一的实现，保证两边对同一个文件得到相同的结果。
+//! 所有模式都以 `.` 开头（如 `d.appId=`），避免误匹配 `myappId=` 这类无关的标识符。
+
+use regex::Regex;
+use std::ops::Range;
+
+/// JS 中 AppID 字段的默认名称
+/// 部分打包产物使用 `aid`、`applicationId` 等其它名称，可在设置中修改
+pub const DEFAULT_APP_ID_FIELD: &str = "appId";
+
+/// 默认编辑的 ID 数组字段名
+pub const DOUYIN_IDS_FIELD: &str = "douyinIds";
+
+/// JS 中 ID 数组赋值的起始模式：`.xxxIds=[`
+/// 捕获组 1 为字段名（如 douyinIds、rewardIds、bannerIds）
+const ID_ARRAY_PATTERN: &str = r#"\.([A-Za-z_$][\w$]*Ids)\s*=\s*\["#;
+
+/// 用户设置的 AppID 字段名，为空时使用 `DEFAULT_APP_ID_FIELD`
+pub fn app_id_field(field: &str) -> &str {
+    let field = field.trim();
+    if field.is_empty() { DEFAULT_APP_ID_FIELD } else { field }
+}
+
+/// 构建 JS 中 AppID 赋值（如 `.appId="x"`）起始部分的正则
+/// 捕获组 1 为 `.appId=` 部分，捕获组 2 为开头的引号（`"`、`'` 或反引号）；字段名按字面匹配。
+/// 值的结束位置由 `find_closing_quote` 确定，以便处理值中被转义的引号
+pub fn app_id_regex(field: &str) -> Regex {
+    let pattern = format!(r#"(\.{}\s*=\s*)(["'`])"#, regex::escape(app_id_field(field)));
+    Regex::new(&pattern).unwrap()
+}
+
+/// 查找所有 AppID 赋值
+///
+/// 每找到一个值就从其结束引号之后继续查找，字符串值中形如 `.appId='x'` 的文本不会被当作新的赋值，
+/// 返回的范围互不重叠。
+///
+/// # Returns
+/// * `Vec<(Range<usize>, u8)>` - 每个值（不含引号、未反转义）的字节范围及其引号
+fn find_app_id_values(content: &str, field: &str) -> Vec<(Range<usize>, u8)> {
+    let re = app_id_regex(field);
+    let mut values = Vec::new();
+    let mut pos = 0;
+    while let Some(caps) = re.captures_at(content, pos) {
+        let quote = caps[2].as_bytes()[0];
+        let start = caps.get(0).unwrap().end();
+        match find_closing_quote(content, start, quote) {
+            Some(end) => {
+                values.push((start..end, quote));
+                pos = end + 1;
+            }
+            // 没有结束引号时之后的内容都在这个未闭合的字符串中
+            None => break,
+        }
+    }
+    values
+}
+
+/// 从字符串字面量开头引号之后的位置开始，查找未被转义的结束引号
+fn find_closing_quote(content: &str, start: usize, quote: u8) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1, // 跳过被转义的字符
+            b if b == quote => return Some(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// 去掉 JS 字符串字面量中的转义反斜杠，如 `a\"b` 得到 `a"b`
+pub fn unescape_js_string(raw: &str) -> String {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.extend(chars.next()),
+            c => value.push(c),
+        }
+    }
+    value
+}
+
+/// 把值写成以 `quote` 包裹的 JS 字符串内容：转义反斜杠和与包裹相同的引号
+fn escape_js_string(value: &str, quote: u8) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '\\' || c == quote as char {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 查找第一个非空的 AppID 赋值
+/// 值可以用双引号、单引号或反引号包裹，其中可以包含被转义的引号
+///
+/// # Returns
+/// * `Option<Range<usize>>` - AppID 值（不含引号、未反转义）的字节范围，用 `unescape_js_string` 取得实际的值
+pub fn find_app_id(content: &str, field: &str) -> Option<Range<usize>> {
+    find_app_id_values(content, field)
+        .into_iter()
+        .map(|(range, _)| range)
+        .find(|range| !range.is_empty())
+}
+
+/// 是否存在 AppID 赋值（包括值为空的赋值），即 `replace_app_id` 是否有可替换的位置
+pub fn has_app_id(content: &str, field: &str) -> bool {
+    !find_app_id_values(content, field).is_empty()
+}
+
+/// 将所有 AppID 赋值的值替换为 `value`，引号风格保持不变（值中与引号相同的字符会被转义）
+pub fn replace_app_id(content: &str, field: &str, value: &str) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut last = 0;
+    for (range, quote) in find_app_id_values(content, field) {
+        output.push_str(&content[last..range.start]);
+        output.push_str(&escape_js_string(value, quote));
+        last = range.end;
+    }
+    output.push_str(&content[last..]);
+    output
+}
+
+/// 查找所有 `.xxxIds=[...]` 数组赋值
+///
+/// 数组的结束位置通过括号匹配确定（跳过字符串中的内容），
+/// 因此元素中包含 `]` 或数组跨越多行时也能正确识别。
+///
+/// # Returns
+/// * `Vec<(String, Range<usize>)>` - 字段名和数组内容（不含方括号）的字节范围，同名字段只保留第一次出现
+pub fn find_id_arrays(content: &str) -> Vec<(String, Range<usize>)> {
+    let re = Regex::new(ID_ARRAY_PATTERN).unwrap();
+    let mut arrays: Vec<(String, Range<usize>)> = Vec::new();
+    for cap in re.captures_iter(content) {
+        let name = cap[1].to_string();
+        if arrays.iter().any(|(n, _)| *n == name) {
+            continue;
+        }
+        let start = cap.get(0).unwrap().end();
+        if let Some(end) = find_closing_bracket(content, start) {
+            arrays.push((name, start..end));
+        }
+    }
+    arrays
+}
+
+/// 是否存在指定字段名的 ID 数组赋值，即 `replace_id_array` 是否有可替换的位置
+pub fn has_id_array(content: &str, name: &str) -> bool {
+    find_id_arrays(content).iter().any(|(n, _)| n == name)
+}
+
+/// 从 `[` 之后的位置开始查找与之匹配的 `]`
+/// 会跳过字符串字面量（单引号、双引号、反引号）中的内容，并处理嵌套的方括号
+fn find_closing_bracket(content: &str, start: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut quote: Option<u8> = None;
+    let mut i = start;
+    while i < bytes.len() {
+        let b = bytes[i];
+        match quote {
+            Some(q) => {
+                if b == b'\\' {
+                    i += 1; // 跳过被转义的字符
+                } else if b == q {
+                    quote = None;
+                }
+            }
+            None => match b {
+                b'"' | b'\'' | b'`' => quote = Some(b),
+                b'[' => depth += 1,
+                b']' => {
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                    depth -= 1;
+                }
+                _ => {}
+            },
+        }
+        i += 1;
+    }
+    None
+}
+
+/// 将数组内容（如 `"id1","id2"`）解析为 ID 列表，清理引号并忽略空元素
+/// 数组可以跨越多行，末尾多余的逗号产生的空元素会被忽略
+pub fn parse_id_list(inner: &str) -> Vec<String> {
+    let trim_matches: &[_] = &['"', '\'', '`'];
+    inner.split(',')
+        .map(|part| part.trim().trim_matches(trim_matches))
+        .filter(|id| !id.is_empty())
+        .map(|id| id.to_string())
+        .collect()
+}
+
+/// 检测数组元素使用的引号风格：第一个元素以单引号开头时返回 `'`，否则（含空数组）返回 `"`
+fn detect_quote(inner: &str) -> char {
+    if inner.trim_start().starts_with('\'') { '\'' } else { '"' }
+}
+
+/// 检测数组元素之间的分隔符（如 `,` 或 `, `），取第一个逗号及其两侧的空白；没有逗号时返回 `,`
+fn detect_separator(inner: &str) -> &str {
+    let Some(comma) = inner.find(',') else {
+        return ",";
+    };
+    let start = inner[..comma].trim_end().len();
+    let end = inner.len() - inner[comma + 1..].trim_start().len();
+    &inner[start..end]
+}
+
+/// 将指定字段名的数组内容替换为新的 ID 列表（只替换第一次出现）
+/// 数组以外的内容保持不变；找不到该字段时原样返回
+/// 元素沿用原数组的引号风格、分隔符、末尾逗号和首尾空白（如多行数组的换行缩进），避免编辑后产生无关的差异；
+/// ID 列表未变化时原样返回，重复保存得到完全相同的内容
+pub fn replace_id_array(content: &str, name: &str, ids: &[String]) -> String {
+    let Some((_, range)) = find_id_arrays(content).into_iter().find(|(n, _)| n == name) else {
+        return content.to_string();
+    };
+    let inner = &content[range.clone()];
+    if parse_id_list(inner) == ids {
+        return content.to_string();
+    }
+    // 重新构建 JS 数组字符串： "id1","id2"
+    let quote = detect_quote(inner);
+    let leading = &inner[..inner.len() - inner.trim_start().len()];
+    let trailing = &inner[inner.trim_end().len()..];
+    let mut ids_str = ids.iter()
+        .map(|id| format!("{quote}{id}{quote}"))
+        .collect::<Vec<_>>()
+        .join(detect_separator(inner));
+    if inner.trim_end().ends_with(',') && !ids.is_empty() {
+        ids_str.push(',');
+    }
+    format!("{}{}{}{}{}", &content[..range.start], leading, ids_str, trailing, &content[range.end..])
+}
+
+/// 规范指定字段赋值语句的空格：`.name="x"` / `.name  =[` 统一为 `.name = "x"` / `.name = [`
+/// 只处理第一次出现的赋值
+pub fn format_assignment(content: &str, name: &str) -> String {
+    let re = Regex::new(&format!(r#"(\.{})\s*=\s*(["'\[])"#, regex::escape(name))).unwrap();
+    re.replace(content, "$1 = $2").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_id() {
+        let content = r#"var myappId="x";d.applicationId='tt1',d.appId="",e.appId = "tt2""#;
+        // 以 `.` 开头才匹配，空值被跳过
+        let range = find_app_id(content, DEFAULT_APP_ID_FIELD).unwrap();
+        assert_eq!(&content[range], "tt2");
+        let range = find_app_id(content, "applicationId").unwrap();
+        assert_eq!(&content[range], "tt1");
+        assert_eq!(app_id_field("  "), DEFAULT_APP_ID_FIELD);
+
+        assert_eq!(
+            replace_app_id(content, DEFAULT_APP_ID_FIELD, "tt9"),
+            r#"var myappId="x";d.applicationId='tt1',d.appId="tt9",e.appId = "tt9""#
+        );
+
+        // 字段名只是结尾相同（或仅大小写不同）的赋值不会被替换
+        let decoys = r#"likeNotAppId="x",d.someOtherAppId="y",d.notappId="z",d.appId="tt1""#;
+        assert_eq!(
+            replace_app_id(decoys, DEFAULT_APP_ID_FIELD, "tt9"),
+            r#"likeNotAppId="x",d.someOtherAppId="y",d.notappId="z",d.appId="tt9""#
+        );
+    }
+
+    #[test]
+    fn test_app_id_quotes() {
+        // 反引号包裹
+        let content = "d.appId=`ttX`,d.douyinIds=[]";
+        let range = find_app_id(content, DEFAULT_APP_ID_FIELD).unwrap();
+        assert_eq!(&content[range], "ttX");
+        assert_eq!(replace_app_id(content, DEFAULT_APP_ID_FIELD, "ttY"), "d.appId=`ttY`,d.douyinIds=[]");
+
+        // 值中包含被转义的引号时不会在转义处截断
+        let content = r#"d.appId="a\"b",x=1"#;
+        let range = find_app_id(content, DEFAULT_APP_ID_FIELD).unwrap();
+        assert_eq!(&content[range.clone()], r#"a\"b"#);
+        assert_eq!(unescape_js_string(&content[range]), r#"a"b"#);
+        // 写回时重新转义，结果仍是合法的字符串
+        assert_eq!(replace_app_id(content, DEFAULT_APP_ID_FIELD, r#"c"d"#), r#"d.appId="c\"d",x=1"#);
+        assert_eq!(replace_app_id("d.appId='a'", DEFAULT_APP_ID_FIELD, r#"c"d"#), r#"d.appId='c"d'"#);
+
+        // 字符串值中的赋值文本不算新的赋值
+        let content = r#"a.appId="x.appId='y'";"#;
+        assert_eq!(replace_app_id(content, DEFAULT_APP_ID_FIELD, "tt123"), r#"a.appId="tt123";"#);
+        let content = r#"a.appId="x.appId='y'",b.appId='z'"#;
+        assert_eq!(
+            replace_app_id(content, DEFAULT_APP_ID_FIELD, "tt1"),
+            r#"a.appId="tt1",b.appId='tt1'"#
+        );
+    }
+
+    #[test]
+    fn test_id_arrays() {
+        let content = r#"d.douyinIds=['a', 'b'],d.rewardIds=["r]1"],x.douyinIds=["z"],otherIds=["o"]"#;
+        let arrays = find_id_arrays(content);
+        let names: Vec<&str> = arrays.iter().map(|(n, _)| n.as_str()).collect();
+        // 同名字段只保留第一次出现；不以 `.` 开头的不匹配
+        assert_eq!(names, vec!["douyinIds", "rewardIds"]);
+        assert_eq!(parse_id_list(&content[arrays[1].1.clone()]), vec!["r]1"]);
+
+        let ids = vec!["c".to_string(), "d".to_string()];
+        assert_eq!(
+            replace_id_array(content, DOUYIN_IDS_FIELD, &ids),
+            r#"d.douyinIds=['c', 'd'],d.rewardIds=["r]1"],x.douyinIds=["z"],otherIds=["o"]"#
+        );
+        // 列表未变化时原样返回
+        let same = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(replace_id_array(content, DOUYIN_IDS_FIELD, &same), content);
+    }
+
+    #[test]
+    fn test_multiline_id_array() {
+        let content = "d.douyinIds=[\n    \"a\",\n    \"b\",\n],d.x=1";
+        let arrays = find_id_arrays(content);
+        assert_eq!(parse_id_list(&content[arrays[0].1.clone()]), vec!["a", "b"]);
+
+        // 写回时保留换行缩进和末尾逗号
+        let ids = vec!["c".to_string(), "d".to_string(), "e".to_string()];
+        let replaced = replace_id_array(content, DOUYIN_IDS_FIELD, &ids);
+        assert_eq!(replaced, "d.douyinIds=[\n    \"c\",\n    \"d\",\n    \"e\",\n],d.x=1");
+        let arrays = find_id_arrays(&replaced);
+        assert_eq!(parse_id_list(&replaced[arrays[0].1.clone()]), ids);
+    }
+}
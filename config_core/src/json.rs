@@ -0,0 +1,66 @@
+use crate::atomic::atomic_write;
+use crate::error::{ConfigError, Result};
+use crate::text::{keep_bom, read_text, strip_bom};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+
+/// 读取并解析 JSON 文件（如 project.config.json），开头的 BOM 会被忽略
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let content = read_text(path)?;
+    serde_json::from_str(strip_bom(&content)).map_err(|source| ConfigError::Json {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// 以 pretty print 格式原子地写入 JSON 文件
+/// 非 ASCII 字符原样写入，不会被转义为 `\uXXXX`；原文件以 BOM 开头时保留 BOM
+pub fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    let content = serde_json::to_string_pretty(value).map_err(|source| ConfigError::Json {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let content = keep_bom(path, content);
+    atomic_write(path, content).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn test_json_round_trip_and_errors() {
+        let dir = std::env::temp_dir().join("config_core_json_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("project.config.json");
+
+        let value: Value = serde_json::json!({ "appid": "tt1", "projectname": "游戏" });
+        write_json(&path, &value).unwrap();
+        assert_eq!(read_json::<Value>(&path).unwrap(), value);
+        assert!(std::fs::read_to_string(&path).unwrap().contains("游戏"));
+
+        // 以 BOM 开头的文件可以读取，写回时保留 BOM
+        std::fs::write(&path, "\u{FEFF}{\"appid\": \"tt_bom\"}").unwrap();
+        let mut bom_value: Value = read_json(&path).unwrap();
+        assert_eq!(bom_value["appid"], "tt_bom");
+        bom_value["appid"] = "tt2".into();
+        write_json(&path, &bom_value).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.starts_with('\u{FEFF}'));
+        assert_eq!(read_json::<Value>(&path).unwrap(), bom_value);
+        // 原文件没有 BOM 时不会添加
+        std::fs::write(&path, "{}").unwrap();
+        write_json(&path, &value).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().starts_with('{'));
+
+        std::fs::write(&path, "{ broken").unwrap();
+        assert!(matches!(read_json::<Value>(&path), Err(ConfigError::Json { .. })));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
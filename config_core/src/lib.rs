@@ -0,0 +1,23 @@
+//! 小游戏配置编辑器的共享逻辑
+//!
+//! douyin_config 和 change_appconfig 两个程序共用的、与 UI 无关的功能，
+//! 放在这里统一实现和测试，避免两边各写一份、行为不一致。
+//!
+//! 库函数返回 [`error::ConfigError`]，`anyhow` 只在两个程序的 UI 边界使用。
+
+pub mod appid;
+pub mod apply;
+pub mod atomic;
+pub mod backup;
+pub mod config_file;
+pub mod encoding;
+pub mod error;
+pub mod fonts;
+pub mod i18n;
+pub mod icon;
+pub mod image_probe;
+pub mod js_patch;
+pub mod json;
+pub mod settings;
+pub mod text;
+pub mod walk;
@@ -0,0 +1,105 @@
+//! 程序设置文件的读写
+//!
+//! 设置以 TOML 保存在系统的配置目录中，每个程序一个子目录：
+//! Windows 为 `%APPDATA%\<程序名>\config`，macOS 为 `~/Library/Application Support/<程序名>`，
+//! Linux 为 `$XDG_CONFIG_HOME/<程序名>`（默认 `~/.config/<程序名>`）。
+//! 设置结构体由各程序自己定义，这里只负责定位、读取和写入文件。
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use directories::ProjectDirs;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::atomic::atomic_write;
+use crate::error::{ConfigError, Result};
+
+/// 设置文件名
+pub const SETTINGS_FILE_NAME: &str = "settings.toml";
+
+/// 程序设置文件的路径：`<系统配置目录>/<app_name>/settings.toml`
+/// 无法确定用户主目录时返回 `None`，此时设置不会被保存
+pub fn settings_path(app_name: &str) -> Option<PathBuf> {
+    ProjectDirs::from("", "", app_name).map(|dirs| dirs.config_dir().join(SETTINGS_FILE_NAME))
+}
+
+/// 读取设置文件
+/// 文件不存在（首次启动）时返回默认值；
+/// 设置结构体应标注 `#[serde(default)]`，这样文件中缺少的字段（如旧版本写入的文件）各自取默认值
+pub fn load<T: DeserializeOwned + Default>(path: &Path) -> Result<T> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(T::default()),
+        Err(source) => return Err(ConfigError::Io { path: path.to_path_buf(), source }),
+    };
+    toml::from_str(&text).map_err(|e| ConfigError::Toml {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })
+}
+
+/// 写入设置文件，配置目录不存在时先创建
+/// 通过原子写入覆盖，退出时写入中断也不会留下半截的设置文件
+pub fn save<T: Serialize>(path: &Path, settings: &T) -> Result<()> {
+    let text = toml::to_string_pretty(settings).map_err(|e| ConfigError::Toml {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|source| ConfigError::Io { path: dir.to_path_buf(), source })?;
+    }
+    atomic_write(path, text).map_err(|source| ConfigError::Io { path: path.to_path_buf(), source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    struct TestSettings {
+        scale: f32,
+        last_dir: Option<PathBuf>,
+        patterns: Vec<String>,
+    }
+
+    impl Default for TestSettings {
+        fn default() -> Self {
+            Self { scale: 1.0, last_dir: None, patterns: vec!["*.psd".to_string()] }
+        }
+    }
+
+    #[test]
+    fn test_load_and_save() {
+        let dir = std::env::temp_dir().join("config_core_settings_test");
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("app").join(SETTINGS_FILE_NAME);
+
+        // 首次启动：文件不存在时使用默认值
+        assert_eq!(load::<TestSettings>(&path).unwrap(), TestSettings::default());
+
+        // 保存时创建配置目录，读回的值与保存的一致
+        let settings = TestSettings {
+            scale: 1.25,
+            last_dir: Some(PathBuf::from("/游戏/项目")),
+            patterns: Vec::new(),
+        };
+        save(&path, &settings).unwrap();
+        assert_eq!(load::<TestSettings>(&path).unwrap(), settings);
+
+        // 缺少的字段取默认值
+        fs::write(&path, "scale = 2.0\n").unwrap();
+        let partial: TestSettings = load(&path).unwrap();
+        assert_eq!(partial.scale, 2.0);
+        assert_eq!(partial.patterns, TestSettings::default().patterns);
+
+        // 无法解析时报错，由调用方决定是否回退到默认值
+        fs::write(&path, "scale = \"大\"\n").unwrap();
+        assert!(matches!(load::<TestSettings>(&path), Err(ConfigError::Toml { .. })));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
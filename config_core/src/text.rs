@@ -0,0 +1,248 @@
+use crate::error::{ConfigError, Result};
+use std::path::Path;
+
+/// 读取 UTF-8 文本文件
+///
+/// 与 `fs::read_to_string` 相同，但区分两种失败：文件无法读取返回 `ConfigError::Io`，
+/// 内容不是 UTF-8（如 GBK 编码的文件）返回 `ConfigError::Encoding`。
+pub fn read_text(path: &Path) -> Result<String> {
+    let bytes = std::fs::read(path).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    String::from_utf8(bytes).map_err(|_| ConfigError::Encoding {
+        path: path.to_path_buf(),
+    })
+}
+
+/// UTF-8 BOM（部分 Windows 编辑器保存时会在文件开头写入）
+pub const BOM: char = '\u{FEFF}';
+
+/// 去掉文本开头的 BOM，serde_json 等解析器遇到 BOM 会报错
+pub fn strip_bom(content: &str) -> &str {
+    content.strip_prefix(BOM).unwrap_or(content)
+}
+
+/// 磁盘上的文件以 BOM 开头时，在要写回的内容前补上 BOM，保持文件原有的编码标记
+pub fn keep_bom(path: &Path, content: String) -> String {
+    let has_bom = std::fs::read(path).is_ok_and(|bytes| bytes.starts_with("\u{FEFF}".as_bytes()));
+    if has_bom && !content.starts_with(BOM) {
+        format!("{}{}", BOM, content)
+    } else {
+        content
+    }
+}
+
+/// 去掉值两端多余的引号
+///
+/// 之前的错误编辑可能把引号写进了字段值本身（JSON 中表现为 `"\"Game\""`），
+/// 这里会反复去掉成对包裹在两端的 `"` 或 `'`，直到不再被引号包裹。
+/// 只出现在一端或中间的引号会原样保留。
+pub fn strip_wrapping_quotes(value: &str) -> &str {
+    let mut s = value.trim();
+    while s.len() >= 2
+        && ((s.starts_with('"') && s.ends_with('"')) || (s.starts_with('\'') && s.ends_with('\'')))
+    {
+        s = s[1..s.len() - 1].trim();
+    }
+    s
+}
+
+/// 将用户输入或粘贴的 DouyinIDs 拆分为 ID 列表
+///
+/// 按逗号分隔，去掉每个元素中的空白和两端的引号，并丢弃空元素，
+/// 因此 `id1, id2, ,id3,`、`"id1",,"id2"` 这类输入都能得到干净的列表。
+pub fn split_douyin_ids(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(|part| part.replace(char::is_whitespace, ""))
+        .map(|part| strip_wrapping_quotes(&part).to_string())
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+/// 按出现顺序去掉重复的 ID，保留第一次出现的位置
+///
+/// # Returns
+/// * 被去掉的重复 ID 数量
+pub fn dedup_ids(ids: &mut Vec<String>) -> usize {
+    let before = ids.len();
+    let mut seen = std::collections::HashSet::new();
+    ids.retain(|id| seen.insert(id.clone()));
+    before - ids.len()
+}
+
+/// 找出不是纯数字的 DouyinID（抖音号 ID 应为数字），空元素不计入
+///
+/// # Returns
+/// * 不合法的 ID，按原顺序
+pub fn invalid_douyin_ids(ids: &[String]) -> Vec<&str> {
+    ids.iter()
+        .map(String::as_str)
+        .filter(|id| !id.is_empty() && !id.chars().all(|c| c.is_ascii_digit()))
+        .collect()
+}
+
+/// 规范化 DouyinIDs 输入，返回逗号分隔、不含空元素的字符串（如 `id1,id2,id3`）
+pub fn normalize_douyin_ids(input: &str) -> String {
+    split_douyin_ids(input).join(",")
+}
+
+/// 让修改后的文本沿用原文件的换行符和末尾换行
+///
+/// 原文件统一使用 CRLF 时，修改中引入的单独 `\n` 会补成 `\r\n`；统一使用 LF 时去掉多出的 `\r`；
+/// 混用两种换行的文件保持原样。原文件以换行结尾时结果也以换行结尾，反之亦然，
+/// 这样写回后版本控制中的差异只包含实际修改的内容。
+pub fn match_line_endings(original: &str, content: String) -> String {
+    let crlf = original.matches("\r\n").count();
+    let lf = original.matches('\n').count();
+    let newline = if crlf > 0 && crlf == lf { "\r\n" } else { "\n" };
+    let mut content = match (crlf, lf) {
+        (0, 0) => content,
+        (0, _) => content.replace("\r\n", "\n"),
+        (c, l) if c == l => content.replace("\r\n", "\n").replace('\n', "\r\n"),
+        _ => content,
+    };
+
+    let ends_with_newline = original.ends_with('\n');
+    if ends_with_newline && !content.ends_with('\n') {
+        content.push_str(newline);
+    } else if !ends_with_newline {
+        let trimmed = content.trim_end_matches(['\r', '\n']).len();
+        content.truncate(trimmed);
+    }
+    content
+}
+
+/// 把新的 ID 追加到已有列表末尾
+///
+/// `dedup` 为 true 时跳过已经存在（或在新列表中重复出现）的 ID，保持原有顺序；
+/// 已有列表本身不做改动，其中原本重复的 ID 会保留。
+pub fn append_ids(existing: &[String], new: &[String], dedup: bool) -> Vec<String> {
+    let mut result = existing.to_vec();
+    for id in new {
+        if !dedup || !result.contains(id) {
+            result.push(id.clone());
+        }
+    }
+    result
+}
+
+/// 简单的通配符匹配（不区分大小写）
+///
+/// `*` 匹配任意数量的字符，`?` 匹配单个字符，其它字符按原样比较。
+/// 用于按文件名筛选（如 `preview.*`、`cover?.jpg`），不支持 `[...]` 和路径分隔。
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // 最近一个 `*` 的位置，以及它当时对应的文本位置，用于回溯
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // 让 `*` 多匹配一个字符后重试
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_text_errors() {
+        let dir = std::env::temp_dir().join("config_core_read_text_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let gbk = dir.join("gbk.js");
+        // "中文" 的 GBK 编码
+        std::fs::write(&gbk, [0xD6, 0xD0, 0xCE, 0xC4]).unwrap();
+
+        assert!(matches!(read_text(&gbk), Err(ConfigError::Encoding { .. })));
+        assert!(matches!(read_text(&dir.join("missing.js")), Err(ConfigError::Io { .. })));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_match_line_endings() {
+        // CRLF 文件中新写入的换行也使用 CRLF，并补回末尾换行
+        assert_eq!(match_line_endings("a\r\nb\r\n", "a\r\nb\nc".to_string()), "a\r\nb\r\nc\r\n");
+        assert_eq!(match_line_endings("a\nb\n", "a\r\nb\n".to_string()), "a\nb\n");
+        // 原文件没有末尾换行时不添加
+        assert_eq!(match_line_endings("a\r\nb", "a\r\nc\r\n".to_string()), "a\r\nc");
+        assert_eq!(match_line_endings("a;b", "a;c".to_string()), "a;c");
+        // 混用换行的文件保持原样
+        assert_eq!(match_line_endings("a\r\nb\nc", "a\r\nb\nd".to_string()), "a\r\nb\nd");
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("preview.*", "preview.png"));
+        assert!(wildcard_match("preview.*", "Preview.JPG"));
+        assert!(wildcard_match("*cover*", "game_cover_01.jpg"));
+        assert!(wildcard_match("cover?.jpg", "cover1.jpg"));
+        assert!(!wildcard_match("cover?.jpg", "cover.jpg"));
+        assert!(!wildcard_match("preview.*", "my_preview.png"));
+        assert!(wildcard_match("*", ""));
+    }
+
+    #[test]
+    fn test_normalize_douyin_ids() {
+        // 末尾逗号和连续逗号产生的空元素会被丢弃
+        assert_eq!(normalize_douyin_ids("id1, id2, ,id3,"), "id1,id2,id3");
+        assert_eq!(normalize_douyin_ids(",,id1,,id2,,"), "id1,id2");
+        assert_eq!(normalize_douyin_ids(" \"id1\",\n'id2' "), "id1,id2");
+        assert_eq!(normalize_douyin_ids(" , ,"), "");
+        assert_eq!(split_douyin_ids("id 1,id2,"), vec!["id1", "id2"]);
+        assert_eq!(split_douyin_ids(r#""id1", id2,,'id3',"#), vec!["id1", "id2", "id3"]);
+        assert!(split_douyin_ids("").is_empty());
+    }
+
+    #[test]
+    fn test_append_ids() {
+        let ids = |s: &str| split_douyin_ids(s);
+        assert_eq!(append_ids(&ids("a,b"), &ids("c,a"), false), ids("a,b,c,a"));
+        assert_eq!(append_ids(&ids("a,b"), &ids("c,a,c,d"), true), ids("a,b,c,d"));
+        assert_eq!(append_ids(&ids("a,a"), &ids("b"), true), ids("a,a,b"));
+        assert_eq!(append_ids(&[], &ids("x"), true), ids("x"));
+    }
+
+    #[test]
+    fn test_dedup_ids() {
+        let mut ids = split_douyin_ids("1,2,2,3,1");
+        assert_eq!(dedup_ids(&mut ids), 2);
+        assert_eq!(ids, split_douyin_ids("1,2,3"));
+        assert_eq!(dedup_ids(&mut ids), 0);
+    }
+
+    #[test]
+    fn test_invalid_douyin_ids() {
+        let ids = |s: &str| split_douyin_ids(s);
+        assert!(invalid_douyin_ids(&ids("123,4567890")).is_empty());
+        assert_eq!(invalid_douyin_ids(&ids("123,abc,45x,６７,\"89\"")), vec!["abc", "45x", "６７"]);
+        assert!(invalid_douyin_ids(&[String::new()]).is_empty());
+    }
+
+    #[test]
+    fn test_strip_wrapping_quotes() {
+        assert_eq!(strip_wrapping_quotes("\"Game\""), "Game");
+        assert_eq!(strip_wrapping_quotes("\"'\"游戏\"'\""), "游戏");
+        assert_eq!(strip_wrapping_quotes("Game"), "Game");
+        assert_eq!(strip_wrapping_quotes("say \"hi\""), "say \"hi\"");
+        assert_eq!(strip_wrapping_quotes("\"half"), "\"half");
+        assert_eq!(strip_wrapping_quotes("\"\""), "");
+        assert_eq!(strip_wrapping_quotes("\""), "\"");
+    }
+}
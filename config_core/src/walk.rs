@@ -0,0 +1,134 @@
+use walkdir::DirEntry;
+
+/// 逐条处理遍历错误的目录遍历
+///
+/// `filter_map(|e| e.ok())` 会静默丢弃出错的条目；这里改为记录错误并计数，
+/// 出错的条目被跳过，但同级的其它文件和目录仍会继续遍历。
+///
+/// # Arguments
+/// * `iter` - `WalkDir::new(..).into_iter()`（或再经过 `filter_entry`）得到的迭代器
+/// * `skipped` - 累加跳过的条目数量
+pub fn walk_logged<'a>(
+    iter: impl Iterator<Item = walkdir::Result<DirEntry>> + 'a,
+    skipped: &'a mut usize,
+) -> impl Iterator<Item = DirEntry> + 'a {
+    iter.filter_map(move |entry| match entry {
+        Ok(entry) => Some(entry),
+        Err(err) => {
+            eprintln!("跳过无法访问的条目: {}", err);
+            *skipped += 1;
+            None
+        }
+    })
+}
+
+/// 遍历中遇到的符号链接统计
+///
+/// 不跟随链接时，链接本身作为普通条目出现（`path_is_symlink`），调用方据此决定是否跳过；
+/// 跟随链接时，指向祖先目录的循环链接会以错误形式出现（`loop_ancestor`），不会无限遍历。
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SymlinkStats {
+    /// 遇到的符号链接数（不含循环链接）
+    pub links: usize,
+    /// 跟随链接时检测到并跳过的循环链接数
+    pub loops: usize,
+}
+
+impl SymlinkStats {
+    /// 记录一个遍历结果；应在 `walk_logged` 之前调用，循环链接的错误随后仍会被记录和计入跳过数
+    pub fn observe(&mut self, entry: &walkdir::Result<DirEntry>) {
+        match entry {
+            Ok(entry) if entry.depth() > 0 && entry.path_is_symlink() => self.links += 1,
+            Err(err) if err.loop_ancestor().is_some() => self.loops += 1,
+            _ => {}
+        }
+    }
+
+    /// 累加另一次遍历的统计
+    pub fn add(&mut self, other: SymlinkStats) {
+        self.links += other.links;
+        self.loops += other.loops;
+    }
+
+    /// 是否遇到过符号链接
+    pub fn is_empty(&self) -> bool {
+        self.links == 0 && self.loops == 0
+    }
+}
+
+/// 判断条目是否为隐藏文件或目录（名称以 `.` 开头，如 `.cache`、`.vscode`）
+/// 遍历的起点（depth 0）本身不算，即使用户选择的根目录名以 `.` 开头
+pub fn is_hidden(entry: &DirEntry) -> bool {
+    entry.depth() > 0 && entry.file_name().to_str().is_some_and(|name| name.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use walkdir::WalkDir;
+
+    #[test]
+    fn test_walk_logged_counts_errors() {
+        let mut skipped = 0;
+        let missing = std::env::temp_dir().join("config_core_walk_missing_dir");
+        let entries: Vec<_> = walk_logged(WalkDir::new(&missing).into_iter(), &mut skipped).collect();
+        assert!(entries.is_empty());
+        assert_eq!(skipped, 1);
+
+        let dir = std::env::temp_dir().join("config_core_walk_test");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("a.txt"), "a").unwrap();
+        fs::write(dir.join("sub/b.txt"), "b").unwrap();
+        let mut skipped = 0;
+        let files = walk_logged(WalkDir::new(&dir).into_iter(), &mut skipped)
+            .filter(|e| e.file_type().is_file())
+            .count();
+        assert_eq!(files, 2);
+        assert_eq!(skipped, 0);
+
+        // 跳过隐藏目录时不会进入 .cache
+        fs::create_dir_all(dir.join(".cache")).unwrap();
+        fs::write(dir.join(".cache/c.txt"), "c").unwrap();
+        let iter = WalkDir::new(&dir).into_iter().filter_entry(|e| !is_hidden(e));
+        let files = walk_logged(iter, &mut skipped)
+            .filter(|e| e.file_type().is_file())
+            .count();
+        assert_eq!(files, 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_stats() {
+        let dir = std::env::temp_dir().join("config_core_walk_symlink_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("real")).unwrap();
+        fs::write(dir.join("real/a.txt"), "a").unwrap();
+        std::os::unix::fs::symlink(dir.join("real"), dir.join("link")).unwrap();
+        // 指向根目录的循环链接
+        std::os::unix::fs::symlink(&dir, dir.join("real/loop")).unwrap();
+
+        // 不跟随：两个链接都作为条目出现，不进入其中
+        let mut stats = SymlinkStats::default();
+        let mut skipped = 0;
+        let files = walk_logged(WalkDir::new(&dir).into_iter().inspect(|e| stats.observe(e)), &mut skipped)
+            .filter(|e| e.file_type().is_file())
+            .count();
+        assert_eq!(files, 1);
+        assert_eq!(stats, SymlinkStats { links: 2, loops: 0 });
+
+        // 跟随：进入 link，循环链接被检测到并跳过，遍历能正常结束
+        let mut stats = SymlinkStats::default();
+        let mut skipped = 0;
+        let iter = WalkDir::new(&dir).follow_links(true).into_iter().inspect(|e| stats.observe(e));
+        let files = walk_logged(iter, &mut skipped)
+            .filter(|e| e.file_type().is_file())
+            .count();
+        assert_eq!(files, 2);
+        assert_eq!(stats.links, 1);
+        assert_eq!(stats.loops, 2);
+        assert_eq!(skipped, 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,13 +1,161 @@
-use crate::model::ProjectItem;
-use crate::scanner;
+use crate::channel;
+use crate::ide::IdeLauncher;
+use crate::image_viewer::ImageViewer;
+use crate::impact::{self, FileImpact};
+use crate::import::{self, ImportProgress, ImportReport, RowMatch};
+use crate::integrity;
+use crate::model::{CachedTexture, ProjectItem};
+use crate::notes;
+use crate::packager::{self, ZipCompression, ZipEvent, ZipJob, ZipOptions};
+use crate::profile::ConfigProfile;
+use crate::regex_debug::RegexDebug;
+use crate::rename;
+use crate::scanner::{self, ProbeCache, SaveOptions, ScanOptions};
+use crate::search::{self, SearchHit};
+use crate::settings::{Settings, DEFAULT_ASPECT_RATIOS, DEFAULT_WINDOW_SIZE};
+use crate::texture_lru::{self, MAX_TEXTURE_PIXELS};
+use crate::tree::{self, DirNode};
+use crate::undo::{UndoStack, UndoStep};
+use config_core::appid;
+use config_core::encoding::TextEncoding;
+use config_core::backup::{self, Backups};
+use config_core::fonts;
+use config_core::i18n::{self, tr, trf, Lang};
+use config_core::image_probe::{matches_aspect_ratio, parse_aspect_ratios, thumbnail};
+use config_core::js_patch::DEFAULT_APP_ID_FIELD;
+use config_core::text::{append_ids, dedup_ids, invalid_douyin_ids, normalize_douyin_ids, split_douyin_ids};
+use config_core::walk::SymlinkStats;
 use eframe::egui;
 use rfd::FileDialog;
-use std::path::{Path, PathBuf};
-use std::fs::{self, File};
-use std::io::{Read, Write};
-use walkdir::WalkDir;
-use zip::write::SimpleFileOptions;
-use zip::ZipWriter;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// 找不到系统中文字体时使用的内置字体 `EMBEDDED_FONT`，由 build.rs 生成
+// 仅在启用 `embedded-font` 特性时编译进程序，避免默认构建的体积增大
+include!(concat!(env!("OUT_DIR"), "/embedded_font.rs"));
+
+/// 预览图的最大显示高度
+/// 超高的长图（如 750×8000）会按比例缩小到此高度内，避免单列被拉得过长
+const PREVIEW_MAX_HEIGHT: f32 = 600.0;
+
+/// 上传到 GPU 的预览图缩略图的最大宽度
+/// 缩略图按加载时列宽对应的物理像素生成，缩小后再上传可大幅减少显存占用和上传耗时，原图路径仍保留在 `image_paths` 中；
+/// 此值只限制超宽窗口下的缩略图大小
+const THUMBNAIL_MAX_WIDTH: u32 = 1024;
+
+/// 预览图加载失败后，再次显示时重新尝试加载的间隔
+/// 图片可能正被外部工具写入，稍后即可正常读取
+const TEXTURE_RETRY_DELAY: Duration = Duration::from_secs(3);
+
+/// 界面缩放比例的可调范围
+const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.75..=2.0;
+
+/// 选中项目超过此数量时，批量操作需要二次确认
+const BATCH_CONFIRM_THRESHOLD: usize = 20;
+/// 项目数量超过此值时，先提示用户筛选或切换到紧凑列表，再渲染全部卡片
+const LARGE_PROJECT_THRESHOLD: usize = 300;
+/// 分页显示时每页的项目卡片数量
+const PAGE_SIZE: usize = 50;
+
+/// 批量修改操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BatchOp {
+    AppId,
+    Name,
+    DouyinIds,
+    /// 从选中项目中移除指定的 DouyinIDs
+    RemoveDouyinIds,
+}
+
+/// 批量应用 DouyinIDs 的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum DouyinIdsMode {
+    /// 用输入的 ID 替换原有列表
+    #[default]
+    Replace,
+    /// 追加到每个项目原有列表的末尾
+    Append,
+    /// 追加，但跳过项目中已有的 ID
+    AppendDedup,
+}
+
+impl DouyinIdsMode {
+    const ALL: [DouyinIdsMode; 3] = [DouyinIdsMode::Replace, DouyinIdsMode::Append, DouyinIdsMode::AppendDedup];
+
+    /// 下拉框中显示的名称
+    fn label(self) -> &'static str {
+        match self {
+            DouyinIdsMode::Replace => "替换",
+            DouyinIdsMode::Append => "追加",
+            DouyinIdsMode::AppendDedup => "去重追加",
+        }
+    }
+}
+
+/// 按是否找到 JS 配置筛选项目列表
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum JsFilter {
+    /// 显示全部项目
+    #[default]
+    All,
+    /// 只显示有 JS 配置的项目
+    HasJs,
+    /// 只显示缺少 JS 配置的项目（不受 DouyinIDs 等 JS 字段的批量修改影响）
+    MissingJs,
+}
+
+impl JsFilter {
+    /// 项目是否应在列表中显示
+    fn matches(self, item: &ProjectItem) -> bool {
+        match self {
+            JsFilter::All => true,
+            JsFilter::HasJs => item.js_config.is_some(),
+            JsFilter::MissingJs => item.js_config.is_none(),
+        }
+    }
+}
+
+impl BatchOp {
+    /// 撤销记录中显示的操作名称
+    fn label(self) -> &'static str {
+        match self {
+            BatchOp::AppId => "批量应用 AppID",
+            BatchOp::Name => "批量应用项目名",
+            BatchOp::DouyinIds => "批量应用 DouyinIDs",
+            BatchOp::RemoveDouyinIds => "批量移除 DouyinIDs",
+        }
+    }
+}
+
+/// 在 UI 线程中同步执行的耗时操作
+/// 先绘制一帧忙碌遮罩再执行，避免界面无反馈地卡住，也防止执行期间的误点击
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BusyOp {
+    Save,
+}
+
+impl BusyOp {
+    /// 遮罩上显示的操作说明
+    fn label(self) -> &'static str {
+        match self {
+            BusyOp::Save => "正在保存更改...",
+        }
+    }
+}
+
+/// 批量打包中单个项目的结果
+struct ZipResult {
+    /// 项目名称
+    name: String,
+    /// 成功时为最终的输出路径，失败时为错误信息
+    result: Result<PathBuf, String>,
+    /// 是否因与其他项目同名而自动改名
+    renamed: bool,
+}
 
 /// 应用程序的主状态结构体
 /// 维护了整个应用程序的生命周期、数据和 UI 状态
@@ -23,28 +171,224 @@ pub struct MyApp {
     
     /// 批量修改的目标 AppID
     batch_appid: String,
+    /// 批量应用 AppID 时是否按末尾数字自动递增（第 1 个项目使用原值）
+    batch_appid_increment: bool,
     /// 批量修改的目标项目名称
     batch_projectname: String,
     /// 批量修改的目标 DouyinIDs (逗号分隔字符串)
     batch_douyin_ids: String,
+    /// 批量应用 DouyinIDs 时替换还是追加到原有列表
+    batch_douyin_mode: DouyinIdsMode,
+    /// 要从选中项目中移除的 DouyinIDs (逗号分隔字符串)
+    batch_remove_ids: String,
     
     /// 底部状态栏显示的提示消息
     status_msg: String,
+
+    /// 扫描选项（在"扫描设置"中调整）
+    scan_options: ScanOptions,
+    /// 预览图文件名模式的输入框内容（逗号分隔），编辑后同步到 `scan_options.name_patterns`
+    preview_patterns_input: String,
+    /// 打包排除模式的输入框内容（逗号分隔），编辑后同步到 `zip_options.exclude_patterns`
+    zip_excludes_input: String,
+    /// 是否校验预览图的宽高比，不符合时在项目卡片上提示
+    aspect_check: bool,
+    /// 允许的宽高比输入框内容（逗号分隔，如 `3:4, 1:1`），编辑后同步到 `aspect_ratios`
+    aspect_ratios_input: String,
+    /// 解析后的允许宽高比列表
+    aspect_ratios: Vec<(u32, u32)>,
+    /// 打包选项
+    zip_options: ZipOptions,
+    /// 批量打包的输出目录，每次打包前由用户选择，下次作为对话框的初始目录（持久化）
+    zip_output_dir: Option<PathBuf>,
+    /// 选择的输出路径已存在、等待确认是否覆盖的打包（项目下标、输出路径）
+    confirm_zip_overwrite: Option<(usize, PathBuf)>,
+    /// 输出目录中已有同名压缩包、等待确认是否覆盖的批量打包（全部任务、已存在的输出路径）
+    confirm_batch_zip_overwrite: Option<(Vec<ZipJob>, Vec<PathBuf>)>,
+    /// 后台打包线程的事件接收端，打包进行中时为 Some
+    zip_rx: Option<Receiver<ZipEvent>>,
+    /// 正在进行的打包任务
+    zip_jobs: Vec<ZipJob>,
+    /// 当前打包进度：(任务下标, 已写入的文件数, 文件总数)
+    zip_progress: (usize, usize, usize),
+    /// 正在进行的是否为批量打包（结束后弹出结果对话框），否则为单个项目打包（结束后打开所在文件夹）
+    zip_batch: bool,
+    /// 批量打包中因同名而自动改名的任务下标
+    zip_renamed: Vec<usize>,
+    /// 打包期间请求了退出，打包结束后再关闭窗口
+    close_after_zip: bool,
+    /// 保存选项
+    save_options: SaveOptions,
+    /// 保存前是否先把原文件备份为 `.bak`（默认开启，持久化）
+    backup_before_save: bool,
+    /// 本次会话的备份记录，每个文件只在第一次被覆盖前备份
+    backups: Backups,
+    /// 抖音开发者工具的启动配置
+    ide_launcher: IdeLauncher,
+    /// 界面语言（持久化）
+    lang: Lang,
+    /// 界面缩放比例（在系统 DPI 缩放基础上再乘以此值，持久化）
+    ui_scale: f32,
+    /// 窗口大小（逻辑像素，不含界面缩放；每帧同步，退出时保存）
+    window_size: [f32; 2],
+    /// 是否正在拖动缩放滑块（拖动期间不从 egui 同步缩放值）
+    ui_scale_dragging: bool,
+    /// 上一次扫描的图片尺寸缓存，刷新时跳过未变化的图片
+    probe_cache: ProbeCache,
+    /// 上一次扫描的总耗时，以及其中目录遍历和图片探测各自的耗时
+    last_scan_duration: Option<(Duration, Duration, Duration)>,
+    /// 选择根目录后是否立即扫描
+    /// 关闭后可先调整扫描设置，再手动点击"扫描"，避免误扫超大目录
+    scan_on_pick: bool,
+    /// 渠道所在的目录层级（相对根目录，从 1 开始），0 表示不按目录约定识别渠道
+    channel_depth: usize,
+
+    // --- 目录树 ---
+    /// 是否在左侧显示目录树面板
+    show_tree: bool,
+    /// 根据扫描结果构建的目录树，扫描后重建
+    dir_tree: Option<DirNode>,
+    /// 需要滚动到的项目下标（由目录树点击触发，滚动后清空）
+    scroll_to_project: Option<usize>,
+    /// 上一次点击复选框的项目下标，用于 Shift+点击范围选择
+    last_clicked: Option<usize>,
+
+    /// 共享同一个 JS 文件的项目分组（扫描后计算）
+    shared_js_groups: Vec<Vec<usize>>,
+    /// 每个项目的"配置与文件夹不符"提示，下标与项目列表一致（扫描后计算）
+    mismatch_hints: Vec<Option<String>>,
+    /// 每个项目所属的渠道，下标与项目列表一致（扫描后按 `channel_depth` 计算）
+    project_channels: Vec<Option<String>>,
+
+    // --- 列表显示 ---
+    /// 按路径（含文件夹名）、AppID 或项目名筛选项目（不区分大小写）
+    /// 只影响显示，隐藏项目的选中状态和待保存修改保持不变，批量操作仍作用于所有选中项目
+    project_filter: String,
+    /// 按是否有 JS 配置筛选项目
+    js_filter: JsFilter,
+    /// 只显示指定渠道的项目，None 表示全部
+    channel_filter: Option<String>,
+    /// 是否使用紧凑列表（每个项目一行），项目很多时更流畅
+    compact_view: bool,
+    /// 项目数量过多时，用户是否已确认仍要渲染全部卡片（重新扫描后重置）
+    large_list_confirmed: bool,
+    /// 是否分页显示项目卡片，每页 `PAGE_SIZE` 个
+    /// 仅影响显示，选中状态和批量操作仍作用于全部项目
+    paginate: bool,
+    /// 当前页码（从 0 开始）
+    page: usize,
+
+    // --- JS 内容搜索 ---
+    /// 搜索关键字
+    search_query: String,
+    /// 上一次搜索的结果
+    search_results: Vec<SearchHit>,
+    /// 正在后台进行的搜索，完成后从此接收结果
+    search_rx: Option<Receiver<Vec<SearchHit>>>,
+    /// 正在后台进行的扫描，项目逐个从此接收
+    scan_rx: Option<Receiver<scanner::ScanEvent>>,
+    /// 本次扫描开始前的项目列表，扫描完成后用于比较磁盘变化
+    scan_previous: Vec<ProjectItem>,
+    /// 扫描进度：(已处理的配置文件数, 配置文件总数)
+    scan_progress: Option<(usize, usize)>,
+    /// 当前扫描的取消标志
+    scan_cancel: Arc<AtomicBool>,
+    /// 正在后台查找预览图的项目，以配置文件路径为键，完成后从此接收结果
+    probe_rx: HashMap<PathBuf, Receiver<scanner::ProbeOutcome>>,
+
+    // --- CSV 映射导入 ---
+    /// 后台导入完成后接收结果的通道，导入进行中时为 Some
+    import_rx: Option<Receiver<anyhow::Result<ImportReport>>>,
+    /// 后台导入的进度
+    import_progress: Option<Arc<ImportProgress>>,
+    /// 最近一次导入的结果，非空时弹出结果对话框
+    import_report: Option<ImportReport>,
+
+    // --- 配置模板 ---
+    /// 已保存的配置模板列表（持久化）
+    profiles: Vec<ConfigProfile>,
+    /// 正在编辑的新模板
+    profile_draft: ConfigProfile,
+
+    /// JS 正则调试面板（在设置的"调试"中打开）
+    regex_debug: RegexDebug,
+    /// 点击预览图打开的原图查看窗口
+    image_viewer: ImageViewer,
+
+    /// 批量操作前是否需要确认（选中项目较多时）
+    batch_confirm: bool,
+    /// 等待用户确认的批量操作
+    pending_batch: Option<BatchOp>,
+    /// 批量修改前是否先列出会被写入的文件及新值，确认后才应用
+    batch_preview: bool,
+    /// 等待确认的批量操作及其影响（试运行得到），非空时弹出影响报告
+    pending_impact: Option<(BatchOp, Vec<FileImpact>)>,
+
+    /// 是否正在显示"全部还原"确认对话框
+    confirm_revert_all: bool,
+    /// 是否正在显示"重命名文件夹"确认对话框
+    confirm_rename_folders: bool,
+    /// 是否正在显示"退出前保存"确认对话框
+    confirm_close: bool,
+    /// 已有较新备份、等待用户确认是否覆盖的文件，非空时弹出确认对话框
+    confirm_backup_overwrite: Vec<PathBuf>,
+    /// 是否正在显示"恢复备份"确认对话框
+    confirm_restore_backups: bool,
+    /// 格式不正确（AppID 或 DouyinIDs）、等待用户确认是否仍然保存的项目（路径、问题描述），非空时弹出确认对话框
+    confirm_invalid_entries: Vec<(PathBuf, String)>,
+    /// 等待执行的耗时操作，执行前先显示一帧忙碌遮罩
+    busy_op: Option<BusyOp>,
+    /// `busy_op` 的遮罩是否已经绘制过，下一帧即可执行
+    busy_overlay_shown: bool,
+    /// 用户已确认退出，下一次关闭请求不再拦截
+    close_confirmed: bool,
+    /// 由"保存后退出"发起的保存，全部保存成功后关闭窗口；保存流程中取消时清除
+    close_after_save: bool,
+    /// 最近一次批量打包的结果，非空时弹出结果对话框
+    zip_report: Vec<ZipResult>,
+    /// 批量应用 AppID 后检测到的重复 AppID 及出现次数，非空时弹出提示
+    duplicate_appids: Vec<(String, usize)>,
+    /// 重新扫描后发现磁盘上的值被外部修改的项目（下标及变化的字段），非空时弹出提示
+    disk_changes: Vec<(usize, Vec<&'static str>)>,
+    /// 批量修改的撤销 / 重做记录，重新扫描时清空
+    undo: UndoStack,
 }
 
 impl MyApp {
     /// 清空当前所有数据和缓存
     fn clear_data(&mut self) {
         self.projects.clear();
+        self.probe_cache = ProbeCache::default();
+        // 丢弃旧项目的预览图查找，避免结果按旧的扫描设置写入新项目
+        self.probe_rx.clear();
         self.batch_appid.clear();
+        self.duplicate_appids.clear();
+        self.disk_changes.clear();
         self.batch_projectname.clear();
         self.batch_douyin_ids.clear();
+        self.batch_remove_ids.clear();
         self.status_msg.clear();
+        self.dir_tree = None;
+        self.scroll_to_project = None;
+        self.last_clicked = None;
+        self.shared_js_groups.clear();
+        self.mismatch_hints.clear();
+        self.project_channels.clear();
+        self.channel_filter = None;
+        self.search_results.clear();
+        self.search_rx = None;
+        self.import_rx = None;
+        self.import_report = None;
+        self.scan_cancel.store(true, Ordering::Relaxed);
+        self.scan_rx = None;
+        self.scan_progress = None;
+        self.scan_previous.clear();
+        self.undo.clear();
     }
 
     /// 应用程序初始化
     /// 在此配置 egui 上下文、字体和安装必要的扩展（如图片加载器）
-    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(cc: &eframe::CreationContext<'_>, settings: Settings) -> Self {
         // 安装图片加载器，支持 png, jpeg 等格式的显示
         // 如果不安装，egui::Image 将无法加载本地文件
         egui_extras::install_image_loaders(&cc.egui_ctx);
@@ -52,39 +396,111 @@ impl MyApp {
         // 配置自定义字体（主要为了支持中文字符）
         Self::configure_fonts(&cc.egui_ctx);
         
-        // 返回默认状态
-        Self::default()
+        // 返回默认状态，并恢复设置文件中保存的选项和配置模板
+        let mut app = Self::default();
+        app.apply_settings(settings);
+        i18n::set_lang(app.lang);
+        // 使用缩放系数而非直接设置 pixels_per_point，保留系统的 DPI 缩放
+        cc.egui_ctx.set_zoom_factor(app.ui_scale.clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end()));
+        app
     }
 
     /// 配置字体
-    /// 尝试加载系统中的 "微软雅黑" 字体，以确保中文能正常显示
+    /// 按操作系统依次尝试常见的中文字体（Windows 微软雅黑、macOS 苹方、Linux Noto CJK / 文泉驿），
+    /// 以确保中文能正常显示；都找不到时使用内置字体（需启用 `embedded-font` 特性）
     fn configure_fonts(ctx: &egui::Context) {
         let mut fonts = egui::FontDefinitions::default();
 
-        // 尝试加载系统字体 (Windows: 微软雅黑)
-        // 注意：这里硬编码了路径，仅适用于 Windows。跨平台需要更复杂的逻辑。
-        // TODO: 在非 Windows 平台上添加 fallback 逻辑
-        let font_path = "C:\\Windows\\Fonts\\msyh.ttc";
-        
-        if let Ok(font_data) = fs::read(font_path) {
+        let font_data = fonts::load_system_cjk_font()
+            .map(|(_, data)| egui::FontData::from_owned(data))
+            .or_else(|| EMBEDDED_FONT.map(egui::FontData::from_static));
+        if let Some(font_data) = font_data {
             // 将字体数据加载到 egui 的字体系统中
-            fonts.font_data.insert(
-                "Microsoft YaHei".to_owned(),
-                egui::FontData::from_owned(font_data),
-            );
+            fonts.font_data.insert("CJK".to_owned(), font_data);
 
             // 设置为 Proportional (非等宽) 和 Monospace (等宽) 的首选字体
             if let Some(vec) = fonts.families.get_mut(&egui::FontFamily::Proportional) {
-                vec.insert(0, "Microsoft YaHei".to_owned());
+                vec.insert(0, "CJK".to_owned());
             }
             if let Some(vec) = fonts.families.get_mut(&egui::FontFamily::Monospace) {
-                vec.insert(0, "Microsoft YaHei".to_owned());
+                vec.insert(0, "CJK".to_owned());
             }
 
             // 应用新的字体配置
             ctx.set_fonts(fonts);
         } else {
-            eprintln!("Warning: Failed to load Microsoft YaHei font from {}", font_path);
+            eprintln!("Warning: Failed to load a CJK font from {:?}", fonts::FONT_CANDIDATES);
+        }
+    }
+
+    /// 把设置文件中的选项应用到界面状态
+    fn apply_settings(&mut self, settings: Settings) {
+        self.window_size = settings.window_size;
+        self.lang = settings.lang;
+        self.ui_scale = settings.ui_scale;
+        // 只恢复路径，不自动扫描：上次的目录可能很大，或已不是想编辑的目录
+        self.root_path = settings.last_root_path.filter(|path| path.is_dir());
+        if let Some(path) = &self.root_path {
+            self.status_msg = trf("已恢复上次的根目录 {}，点击「🔍 扫描」开始。", &[&path.display()]);
+        }
+        self.scan_on_pick = settings.scan_on_pick;
+        self.channel_depth = settings.channel_depth;
+        self.scan_options.probe_threads = settings.probe_threads;
+        self.scan_options.skip_hidden = settings.skip_hidden;
+        self.scan_options.js_only = settings.js_only;
+        self.scan_options.follow_links = settings.follow_links;
+        self.zip_options.follow_links = settings.follow_links;
+        self.scan_options.match_width = settings.match_width;
+        self.scan_options.lazy_images = settings.lazy_images;
+        self.preview_patterns_input = settings.preview_name_patterns.join(", ");
+        self.scan_options.name_patterns = settings.preview_name_patterns;
+        self.aspect_check = settings.aspect_check;
+        self.aspect_ratios = parse_aspect_ratios(&settings.aspect_ratios);
+        self.aspect_ratios_input = settings.aspect_ratios;
+        self.scan_options.app_id_field = settings.app_id_field.clone();
+        self.save_options.app_id_field = settings.app_id_field;
+        self.save_options.format_assignments = settings.format_js_assignments;
+        self.backup_before_save = settings.backup_before_save;
+        self.batch_confirm = settings.batch_confirm;
+        self.batch_preview = settings.batch_preview;
+        self.zip_options.skip_zip_files = settings.skip_zip_files;
+        self.zip_options.compression = settings.zip_compression;
+        self.zip_excludes_input = settings.zip_exclude_patterns.join(", ");
+        self.zip_options.exclude_patterns = settings.zip_exclude_patterns;
+        self.zip_output_dir = settings.zip_output_dir;
+        self.ide_launcher = settings.ide_launcher;
+        self.profiles = settings.profiles;
+    }
+
+    /// 收集当前需要保存的设置
+    fn settings(&self) -> Settings {
+        Settings {
+            window_size: self.window_size,
+            lang: self.lang,
+            ui_scale: self.ui_scale,
+            last_root_path: self.root_path.clone(),
+            scan_on_pick: self.scan_on_pick,
+            channel_depth: self.channel_depth,
+            probe_threads: self.scan_options.probe_threads,
+            skip_hidden: self.scan_options.skip_hidden,
+            js_only: self.scan_options.js_only,
+            follow_links: self.scan_options.follow_links,
+            match_width: self.scan_options.match_width,
+            lazy_images: self.scan_options.lazy_images,
+            preview_name_patterns: self.scan_options.name_patterns.clone(),
+            aspect_check: self.aspect_check,
+            aspect_ratios: self.aspect_ratios_input.clone(),
+            app_id_field: self.scan_options.app_id_field.clone(),
+            format_js_assignments: self.save_options.format_assignments,
+            backup_before_save: self.backup_before_save,
+            batch_confirm: self.batch_confirm,
+            batch_preview: self.batch_preview,
+            skip_zip_files: self.zip_options.skip_zip_files,
+            zip_compression: self.zip_options.compression,
+            zip_exclude_patterns: self.zip_options.exclude_patterns.clone(),
+            zip_output_dir: self.zip_output_dir.clone(),
+            ide_launcher: self.ide_launcher.clone(),
+            profiles: self.profiles.clone(),
         }
     }
 }
@@ -95,9 +511,87 @@ impl Default for MyApp {
             root_path: None,
             projects: Vec::new(),
             batch_appid: String::new(),
+            batch_appid_increment: false,
             batch_projectname: String::new(),
             batch_douyin_ids: String::new(),
-            status_msg: "准备就绪。请选择包含小游戏项目的文件夹。".to_owned(),
+            batch_douyin_mode: DouyinIdsMode::default(),
+            batch_remove_ids: String::new(),
+            status_msg: tr("准备就绪。请选择包含小游戏项目的文件夹。").to_owned(),
+            scan_options: ScanOptions::default(),
+            preview_patterns_input: String::new(),
+            zip_excludes_input: packager::DEFAULT_EXCLUDE_PATTERNS.join(", "),
+            aspect_check: false,
+            aspect_ratios_input: DEFAULT_ASPECT_RATIOS.to_string(),
+            aspect_ratios: parse_aspect_ratios(DEFAULT_ASPECT_RATIOS),
+            zip_options: ZipOptions::default(),
+            zip_output_dir: None,
+            confirm_zip_overwrite: None,
+            confirm_batch_zip_overwrite: None,
+            zip_rx: None,
+            zip_jobs: Vec::new(),
+            zip_progress: (0, 0, 0),
+            zip_batch: false,
+            close_after_zip: false,
+            zip_renamed: Vec::new(),
+            save_options: SaveOptions::default(),
+            backup_before_save: true,
+            backups: Backups::default(),
+            ide_launcher: IdeLauncher::default(),
+            lang: Lang::default(),
+            ui_scale: 1.0,
+            window_size: DEFAULT_WINDOW_SIZE,
+            ui_scale_dragging: false,
+            probe_cache: ProbeCache::default(),
+            last_scan_duration: None,
+            scan_on_pick: true,
+            channel_depth: 0,
+            show_tree: false,
+            dir_tree: None,
+            scroll_to_project: None,
+            last_clicked: None,
+            shared_js_groups: Vec::new(),
+            mismatch_hints: Vec::new(),
+            project_channels: Vec::new(),
+            project_filter: String::new(),
+            js_filter: JsFilter::default(),
+            channel_filter: None,
+            compact_view: false,
+            large_list_confirmed: false,
+            paginate: false,
+            page: 0,
+            search_query: String::new(),
+            search_results: Vec::new(),
+            search_rx: None,
+            scan_rx: None,
+            scan_previous: Vec::new(),
+            scan_progress: None,
+            scan_cancel: Arc::new(AtomicBool::new(false)),
+            probe_rx: HashMap::new(),
+            import_rx: None,
+            import_progress: None,
+            import_report: None,
+            profiles: Vec::new(),
+            profile_draft: ConfigProfile::default(),
+            regex_debug: RegexDebug::default(),
+            image_viewer: ImageViewer::default(),
+            batch_confirm: true,
+            pending_batch: None,
+            batch_preview: true,
+            pending_impact: None,
+            confirm_revert_all: false,
+            confirm_close: false,
+            confirm_backup_overwrite: Vec::new(),
+            confirm_invalid_entries: Vec::new(),
+            confirm_restore_backups: false,
+            busy_op: None,
+            busy_overlay_shown: false,
+            close_confirmed: false,
+            close_after_save: false,
+            confirm_rename_folders: false,
+            zip_report: Vec::new(),
+            duplicate_appids: Vec::new(),
+            disk_changes: Vec::new(),
+            undo: UndoStack::default(),
         }
     }
 }
@@ -105,11 +599,149 @@ impl Default for MyApp {
 impl MyApp {
     /// 执行扫描操作
     /// 调用 scanner 模块扫描 root_path 下的所有项目
-    fn scan(&mut self) {
+    /// 在后台线程中开始扫描，项目逐个加入列表，扫描结束后由 `finish_scan` 收尾
+    /// 扫描途中再次调用会丢弃尚未完成的扫描，重新开始
+    fn start_scan(&mut self) {
+        let Some(path) = self.root_path.clone() else { return };
+        // 保存时必须使用与扫描相同的字段名，因此只在开始扫描时同步
+        self.save_options.app_id_field = self.scan_options.app_id_field.clone();
+        let projects = std::mem::take(&mut self.projects);
+        // 被取代的扫描只产生了部分结果，磁盘变化仍与更早的完整结果比较
+        if self.scan_rx.is_none() {
+            self.scan_previous = projects;
+        }
+        self.dir_tree = None;
+        self.scroll_to_project = None;
+        self.large_list_confirmed = false;
+        self.page = 0;
+        self.last_clicked = None;
+        // 搜索结果等按下标记录的数据已失效
+        self.search_results.clear();
+        self.search_rx = None;
+        self.import_rx = None;
+        self.import_report = None;
+        self.disk_changes.clear();
+        self.shared_js_groups.clear();
+        self.mismatch_hints.clear();
+        self.project_channels.clear();
+        // 撤销记录按下标对应项目，重新扫描后失效
+        self.undo.clear();
+        self.status_msg = tr("正在扫描...").to_string();
+        // 通知被取代的扫描线程停止
+        self.scan_cancel.store(true, Ordering::Relaxed);
+        self.scan_cancel = Arc::new(AtomicBool::new(false));
+        self.scan_progress = None;
+        self.scan_rx = Some(scanner::spawn_scan(
+            path,
+            self.scan_options.clone(),
+            self.probe_cache.clone(),
+            self.scan_cancel.clone(),
+        ));
+    }
+
+    /// 接收后台扫描的结果，扫描完成时执行收尾工作
+    fn poll_scan(&mut self, ctx: &egui::Context) {
+        let Some(rx) = &self.scan_rx else { return };
+        let mut finished = None;
+        loop {
+            match rx.try_recv() {
+                Ok(scanner::ScanEvent::Progress(done, total)) => self.scan_progress = Some((done, total)),
+                Ok(scanner::ScanEvent::Project(item)) => self.projects.push(*item),
+                Ok(scanner::ScanEvent::Done(report)) => {
+                    finished = Some(Some(report));
+                    break;
+                }
+                Err(TryRecvError::Empty) => break,
+                // 扫描线程意外退出
+                Err(TryRecvError::Disconnected) => {
+                    finished = Some(None);
+                    break;
+                }
+            }
+        }
+        match finished {
+            Some(report) => {
+                self.scan_rx = None;
+                self.scan_progress = None;
+                match report {
+                    Some(report) => self.finish_scan(report),
+                    None => self.status_msg = tr("扫描意外中断").to_string(),
+                }
+                self.refresh_channels();
+            }
+            None => {
+                self.status_msg = trf("正在扫描... 已找到 {} 个项目", &[&self.projects.len()]);
+                ctx.request_repaint();
+            }
+        }
+    }
+
+    /// 扫描完成后的收尾：与上一次结果比较、构建目录树、读取备注并汇总状态
+    fn finish_scan(&mut self, mut report: scanner::ScanReport) {
         if let Some(path) = &self.root_path {
-            self.status_msg = "正在扫描...".to_string();
-            self.projects = scanner::scan_directory(path);
-            self.status_msg = format!("扫描完成，共找到 {} 个配置文件", self.projects.len());
+            let previous = std::mem::take(&mut self.scan_previous);
+            // 与上一次扫描比较，提示哪些项目在磁盘上被外部修改过
+            self.disk_changes = scanner::diff_disk_changes(&previous, &self.projects);
+            self.dir_tree = Some(tree::build_tree(path, &self.projects));
+            self.shared_js_groups = scanner::find_shared_js(&self.projects);
+            self.mismatch_hints = integrity::mismatch_hints(&self.projects);
+            let duplicate_groups = appid::group_duplicates(
+                self.projects.iter().map(|p| (p.config.appid.as_str(), &p.path)),
+            ).len();
+            let notes_result = notes::load_notes(path, &mut self.projects);
+            // 取消的扫描只探测了部分图片、延迟查找的扫描不探测图片，其余图片保留上一次的缓存
+            if report.cancelled || self.scan_options.lazy_images {
+                self.probe_cache.merge(std::mem::take(&mut report.probe_cache));
+            } else {
+                self.probe_cache = std::mem::take(&mut report.probe_cache);
+            }
+            self.last_scan_duration = Some((report.total_duration(), report.walk_duration, report.probe_duration));
+            self.status_msg = trf(
+                "{}，共找到 {} 个配置文件（耗时 {}s：目录遍历 {}s，图片探测 {}s；图片探测线程: {}）",
+                &[
+                    &tr(if report.cancelled { "扫描已取消" } else { "扫描完成" }),
+                    &self.projects.len(),
+                    &format!("{:.2}", report.total_duration().as_secs_f32()),
+                    &format!("{:.2}", report.walk_duration.as_secs_f32()),
+                    &format!("{:.2}", report.probe_duration.as_secs_f32()),
+                    &report.probe_threads,
+                ],
+            );
+            if report.reused_probes > 0 {
+                self.status_msg.push_str(&trf("，{} 张图片未变化，复用上次结果", &[&report.reused_probes]));
+            }
+            if report.skipped_entries > 0 {
+                self.status_msg.push_str(&trf("。{} 个条目无法访问，已跳过", &[&report.skipped_entries]));
+            }
+            self.status_msg.push_str(&symlink_note(report.symlinks, self.scan_options.follow_links));
+            if report.skipped_without_js > 0 {
+                self.status_msg.push_str(&trf("。{} 个项目没有 JS 配置，已按设置跳过", &[&report.skipped_without_js]));
+            }
+            let missing_js = self.projects.iter().filter(|p| p.js_config.is_none()).count();
+            if missing_js > 0 {
+                self.status_msg.push_str(&trf("。⚠ {} 个项目缺少 JS 配置，无法写入 DouyinIDs", &[&missing_js]));
+            }
+            let missing_app_id = self.projects.iter()
+                .filter(|p| p.js_config.as_ref().is_some_and(|js| js.app_id.is_empty()))
+                .count();
+            if missing_app_id > 0 {
+                self.status_msg.push_str(&trf("。⚠ {} 个项目的 JS 未识别到 AppID", &[&missing_app_id]));
+            }
+            if !self.shared_js_groups.is_empty() {
+                self.status_msg.push_str(&trf(
+                    "。⚠ 发现 {} 个 JS 文件被多个项目共享，修改其一会影响其他项目",
+                    &[&self.shared_js_groups.len()],
+                ));
+            }
+            if duplicate_groups > 0 {
+                self.status_msg.push_str(&trf("。⚠ 发现 {} 组重复 AppID", &[&duplicate_groups]));
+            }
+            if !self.disk_changes.is_empty() {
+                self.status_msg.push_str(&trf("。{} 个项目的配置在磁盘上已被修改", &[&self.disk_changes.len()]));
+            }
+            if let Err(e) = notes_result {
+                self.status_msg.push_str(&trf("。⚠ 读取备注失败: {}", &[&e]));
+            }
         }
     }
 
@@ -118,196 +750,1224 @@ impl MyApp {
     fn save_all(&mut self) {
         let mut success = 0;
         let mut fail = 0;
-        
-        for item in &mut self.projects {
+        let mut missing = 0;
+        // 保存成功的项目在保存前的磁盘内容，撤销"保存"后这些值成为未保存的修改，再次保存即可写回
+        let mut saved = Vec::new();
+        let mut first_error = None;
+
+        for (idx, item) in self.projects.iter_mut().enumerate() {
             if item.is_modified {
-                match scanner::save_project_item(item) {
+                // 文件在打开期间被移动或删除时跳过，避免写出难以理解的错误
+                if !item.check_files_exist() {
+                    missing += 1;
+                    continue;
+                }
+                // 保存前去掉名称两端多余的引号，避免把错误写回文件
+                item.config.normalize_name();
+                let backups = self.backup_before_save.then_some(&mut self.backups);
+                match scanner::save_project_item(item, &self.save_options, backups) {
                     Ok(_) => {
-                        item.is_modified = false;
+                        saved.push((idx, item.original.clone()));
+                        item.mark_saved();
                         success += 1;
                     }
                     Err(e) => {
                         eprintln!("保存失败 {:?}: {}", item.path, e);
+                        first_error.get_or_insert_with(|| e.to_string());
                         fail += 1;
                     }
                 }
             }
         }
-        self.status_msg = format!("保存结束：成功 {} 个，失败 {} 个", success, fail);
+        self.undo.record(UndoStep::from_snapshots(tr("保存更改"), saved), &self.projects);
+        self.status_msg = trf("保存结束：成功 {} 个，失败 {} 个", &[&success, &fail]);
+        if let Some(e) = first_error {
+            self.status_msg.push_str(&trf("（如 {}）", &[&e]));
+        }
+        if missing > 0 {
+            self.status_msg.push_str(&trf(
+                "，{} 个项目的文件已不存在（可能被移动或删除），已跳过，请重新扫描",
+                &[&missing],
+            ));
+        }
     }
     
+    /// 待保存项目中格式不正确的 AppID 和 DouyinIDs
+    ///
+    /// # Returns
+    /// * `Vec<(PathBuf, String)>` - 项目路径及问题描述
+    fn invalid_entries(&self) -> Vec<(PathBuf, String)> {
+        let mut entries = Vec::new();
+        for p in self.projects.iter().filter(|p| p.is_modified) {
+            if let Err(reason) = appid::validate_appid(&p.config.appid) {
+                entries.push((p.path.clone(), trf("AppID “{}”：{}", &[&p.config.appid, &reason])));
+            }
+            if let Some(js) = &p.js_config {
+                let invalid = invalid_douyin_ids(&js.douyin_ids);
+                if !invalid.is_empty() {
+                    entries.push((p.path.clone(), trf(
+                        "DouyinIDs 中 {} 个不是纯数字：{}",
+                        &[&invalid.len(), &invalid.join(", ")],
+                    )));
+                }
+            }
+        }
+        entries
+    }
+
+    /// 点击"保存"：待保存项目中有格式不正确的 AppID 或 DouyinIDs 时先请用户确认，再检查备份
+    /// `close_after` 为 true 时（"保存后退出"），全部保存成功后关闭窗口
+    fn request_save(&mut self, close_after: bool) {
+        if self.app_id_field_stale() {
+            self.status_msg = tr("AppID 字段名已修改，请重新扫描后再保存").to_string();
+            return;
+        }
+        self.close_after_save = close_after;
+        self.confirm_invalid_entries = self.invalid_entries();
+        if self.confirm_invalid_entries.is_empty() {
+            self.confirm_backups_then_save();
+        }
+    }
+
+    /// 设置中的 AppID 字段名与当前项目扫描时使用的不同
+    /// 此时 JS 中的 AppID 是按旧字段名读取的，用新字段名保存会写到别的字段上
+    fn app_id_field_stale(&self) -> bool {
+        !self.projects.is_empty() && self.scan_options.app_id_field != self.save_options.app_id_field
+    }
+
+    /// 将被覆盖的文件已有较新的 `.bak` 时先请用户确认，否则直接保存
+    fn confirm_backups_then_save(&mut self) {
+        if self.backup_before_save {
+            let files = self.projects.iter()
+                .filter(|p| p.is_modified)
+                .flat_map(|p| std::iter::once(p.path.as_path()).chain(p.js_path.as_deref()));
+            self.confirm_backup_overwrite = self.backups.pending_newer(files);
+            if !self.confirm_backup_overwrite.is_empty() {
+                return;
+            }
+        }
+        self.start_busy(BusyOp::Save);
+    }
+
+    /// 用 `.bak` 备份覆盖所有项目的配置文件和 JS 文件，然后重新扫描
+    fn restore_backups(&mut self) {
+        let mut files: Vec<PathBuf> = self.projects.iter()
+            .flat_map(|p| std::iter::once(p.path.clone()).chain(p.js_path.clone()))
+            .collect();
+        // 多个项目可能共用同一个 JS 文件
+        files.sort();
+        files.dedup();
+        let mut restored = 0;
+        let mut failed = 0;
+        for file in &files {
+            match backup::restore_backup(file) {
+                Ok(true) => restored += 1,
+                Ok(false) => {}
+                Err(e) => {
+                    eprintln!("恢复备份失败 {:?}: {}", file, e);
+                    failed += 1;
+                }
+            }
+        }
+        self.start_scan();
+        self.status_msg = trf("已从备份恢复 {} 个文件，失败 {} 个，正在重新扫描...", &[&restored, &failed]);
+    }
+
+    /// 安排一个耗时操作：本帧先显示忙碌遮罩，下一帧再执行
+    fn start_busy(&mut self, op: BusyOp) {
+        self.busy_op = Some(op);
+        self.busy_overlay_shown = false;
+    }
+
+    /// 执行等待中的耗时操作（遮罩已至少绘制一帧后）
+    fn run_busy_op(&mut self, ctx: &egui::Context) {
+        let Some(op) = self.busy_op else { return };
+        if !self.busy_overlay_shown {
+            self.busy_overlay_shown = true;
+            ctx.request_repaint();
+            return;
+        }
+        self.busy_op = None;
+        match op {
+            BusyOp::Save => {
+                self.save_all();
+                // 有项目保存失败时留在程序中，状态栏会显示失败数量
+                if std::mem::take(&mut self.close_after_save) && !self.projects.iter().any(|p| p.is_modified) {
+                    self.close_confirmed = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+            }
+        }
+    }
+
+    /// 将所有已修改的项目还原到原始快照
+    fn revert_all(&mut self) {
+        let step = UndoStep::capture(&self.projects, 0..self.projects.len(), tr("全部还原"));
+        let mut count = 0;
+        for item in &mut self.projects {
+            if item.is_modified {
+                item.revert();
+                count += 1;
+            }
+        }
+        self.undo.record(step, &self.projects);
+        self.status_msg = trf("已还原 {} 个项目的未保存修改", &[&count]);
+    }
+
+    /// 撤销最近一次批量修改
+    fn undo(&mut self) {
+        self.status_msg = match self.undo.undo(&mut self.projects) {
+            Some(label) => trf("已撤销：{}", &[&label]),
+            None => tr("没有可撤销的操作").to_string(),
+        };
+    }
+
+    /// 重做最近撤销的批量修改
+    fn redo(&mut self) {
+        self.status_msg = match self.undo.redo(&mut self.projects) {
+            Some(label) => trf("已重做：{}", &[&label]),
+            None => tr("没有可重做的操作").to_string(),
+        };
+    }
+
+    /// 将选中项目所在的文件夹重命名为项目名称
+    /// 重命名后同步更新所有位于该文件夹下的项目路径（包括嵌套在其中的其他项目），并重建目录树
+    fn rename_selected_folders(&mut self) {
+        let mut renamed = 0;
+        let mut failed = Vec::new();
+        for idx in 0..self.projects.len() {
+            if !self.projects[idx].selected {
+                continue;
+            }
+            // 不重命名扫描根目录本身
+            if self.projects[idx].path.parent() == self.root_path.as_deref() {
+                continue;
+            }
+            match rename::rename_project_folder(&mut self.projects[idx]) {
+                Ok(Some((old, new))) => {
+                    self.invalidate_textures(idx);
+                    for other in 0..self.projects.len() {
+                        if rename::rebase_item_paths(&mut self.projects[other], &old, &new) {
+                            self.invalidate_textures(other);
+                        }
+                    }
+                    renamed += 1;
+                }
+                Ok(None) => {}
+                Err(e) => failed.push(format!("{}: {}", self.projects[idx].path.display(), e)),
+            }
+        }
+        if let Some(root) = &self.root_path {
+            self.dir_tree = Some(tree::build_tree(root, &self.projects));
+        }
+        self.mismatch_hints = integrity::mismatch_hints(&self.projects);
+        self.refresh_channels();
+        self.status_msg = trf("已重命名 {} 个项目文件夹", &[&renamed]);
+        if !failed.is_empty() {
+            self.status_msg.push_str(&trf("，{} 个失败：{}", &[&failed.len(), &failed.join("; ")]));
+        }
+        // 备注以目录路径为键，重命名后需要按新路径重新写入
+        if renamed > 0 {
+            self.save_notes();
+        }
+    }
+
+    /// 按当前的渠道层级重新计算每个项目的渠道
+    /// 筛选的渠道已不存在时取消筛选
+    fn refresh_channels(&mut self) {
+        self.project_channels = match &self.root_path {
+            Some(root) => channel::project_channels(root, &self.projects, self.channel_depth),
+            None => Vec::new(),
+        };
+        if let Some(filter) = &self.channel_filter {
+            if !self.project_channels.iter().flatten().any(|c| c == filter) {
+                self.channel_filter = None;
+            }
+        }
+    }
+
+    /// 通过当前筛选条件（搜索框、JS 筛选、渠道）的项目下标，按列表顺序
+    fn visible_indices(&self) -> Vec<usize> {
+        let filter = self.project_filter.to_lowercase();
+        (0..self.projects.len())
+            .filter(|&idx| project_matches(&self.projects[idx], &filter))
+            .filter(|&idx| self.js_filter.matches(&self.projects[idx]))
+            .filter(|&idx| {
+                self.channel_filter.is_none()
+                    || self.project_channels.get(idx).is_some_and(|c| *c == self.channel_filter)
+            })
+            .collect()
+    }
+
+    /// 修改当前筛选结果中所有项目的选中状态，被筛选隐藏的项目保持不变
+    ///
+    /// # Arguments
+    /// * `select` - 根据项目原来的选中状态计算新状态（全选、全不选或反选）
+    fn update_visible_selection(&mut self, select: impl Fn(bool) -> bool) {
+        for idx in self.visible_indices() {
+            let item = &mut self.projects[idx];
+            item.selected = select(item.selected);
+        }
+    }
+
+    /// 只选中指定渠道的项目，使批量操作作用于整个渠道
+    fn select_channel(&mut self, channel: &str) {
+        let mut count = 0;
+        for (item, item_channel) in self.projects.iter_mut().zip(&self.project_channels) {
+            item.selected = item_channel.as_deref() == Some(channel);
+            count += item.selected as usize;
+        }
+        self.status_msg = trf("已选中渠道「{}」的 {} 个项目", &[&channel, &count]);
+    }
+
+    /// 将所有项目的备注写入根目录下的备注文件，失败时在状态栏提示
+    fn save_notes(&mut self) {
+        let Some(root) = &self.root_path else { return };
+        if let Err(e) = notes::save_notes(root, &self.projects) {
+            self.status_msg = trf("保存备注失败: {}", &[&e]);
+        }
+    }
+
+    /// 选择 CSV 映射文件并在后台读取、匹配
+    fn start_csv_import(&mut self) {
+        let Some(path) = FileDialog::new().add_filter("CSV", &["csv"]).pick_file() else { return };
+        let (rx, progress) = import::spawn_import(path, &self.projects);
+        self.import_rx = Some(rx);
+        self.import_progress = Some(progress);
+        self.status_msg = tr("正在导入 CSV 映射...").to_string();
+    }
+
+    /// 导入完成：应用唯一匹配的行，保留结果以便在对话框中处理未匹配和有歧义的行
+    fn finish_csv_import(&mut self, report: ImportReport) {
+        let matched_rows = report.rows.iter().filter_map(|(_, matched)| match matched {
+            RowMatch::Matched(idx) => Some(*idx),
+            _ => None,
+        });
+        let step = UndoStep::capture(&self.projects, matched_rows, tr("CSV 导入"));
+        let mut changed = 0;
+        for (row, matched) in &report.rows {
+            if let RowMatch::Matched(idx) = matched {
+                if import::apply_row(&mut self.projects[*idx], row) {
+                    changed += 1;
+                }
+            }
+        }
+        self.undo.record(step, &self.projects);
+        let (matched, unmatched, ambiguous) = report.counts();
+        self.status_msg = trf(
+            "CSV 导入完成：匹配 {} 行（{} 个项目有修改），未匹配 {} 行，有歧义 {} 行，请点击保存生效。",
+            &[&matched, &changed, &unmatched, &ambiguous],
+        );
+        self.import_report = Some(report);
+    }
+
+    /// 项目的路径发生变化（如文件夹被重命名）后调用
+    /// 清空以旧路径为键的纹理缓存，并在后台重新查找预览图，避免预览显示旧的或已失效的图片
+    /// 尚未查找过预览图的项目保持不变，展开时再查找
+    fn invalidate_textures(&mut self, index: usize) {
+        let Some(item) = self.projects.get_mut(index) else { return };
+        item.texture_cache.clear();
+        if !item.images_probed {
+            return;
+        }
+        item.images_probed = false;
+        item.image_paths.clear();
+        item.image_sizes.clear();
+        self.request_probe(index);
+    }
+
+    /// 在后台查找项目的预览图，同一项目已在查找中时不重复启动
+    fn request_probe(&mut self, index: usize) {
+        let Some(item) = self.projects.get_mut(index) else { return };
+        let Some(dir) = item.path.parent() else {
+            item.images_probed = true;
+            return;
+        };
+        if !self.probe_rx.contains_key(&item.path) {
+            let rx = scanner::spawn_probe(dir.to_path_buf(), self.scan_options.clone(), self.probe_cache.clone());
+            self.probe_rx.insert(item.path.clone(), rx);
+        }
+    }
+
+    /// 接收后台查找到的预览图，写回对应的项目
+    fn poll_probes(&mut self, ctx: &egui::Context) {
+        let mut finished = Vec::new();
+        for (path, rx) in &self.probe_rx {
+            match rx.try_recv() {
+                Ok(outcome) => finished.push((path.clone(), Some(outcome))),
+                Err(TryRecvError::Empty) => ctx.request_repaint(),
+                Err(TryRecvError::Disconnected) => finished.push((path.clone(), None)),
+            }
+        }
+        for (path, outcome) in finished {
+            self.probe_rx.remove(&path);
+            let Some(outcome) = outcome else { continue };
+            self.probe_cache.merge(outcome.cache);
+            if let Some(item) = self.projects.iter_mut().find(|p| p.path == path) {
+                item.image_paths = outcome.image_paths;
+                item.image_sizes = outcome.image_sizes;
+                item.images_probed = true;
+            }
+        }
+    }
+
+    /// 清空所有项目的图片纹理缓存，释放对应的 GPU 纹理
+    /// 项目再次显示时会按需重新加载
+    ///
+    /// # Returns
+    /// * `usize` - 清理的缓存条目数
+    fn clear_texture_cache(&mut self) -> usize {
+        self.projects
+            .iter_mut()
+            .map(|item| std::mem::take(&mut item.texture_cache).len())
+            .sum()
+    }
+
+    /// 缓存的纹理像素总数超过 `MAX_TEXTURE_PIXELS` 时，释放最久未显示的纹理
+    /// `frame_start` 之后显示过的纹理（当前帧可见的）不会被释放
+    fn evict_textures(&mut self, frame_start: Instant) {
+        let pixels = |cached: &CachedTexture| cached.texture.as_ref().map_or(0, |t| t.size()[0] * t.size()[1]);
+        let total: usize = self.projects.iter().flat_map(|item| item.texture_cache.values()).map(pixels).sum();
+        if total <= MAX_TEXTURE_PIXELS {
+            return;
+        }
+        let entries = self.projects
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, item)| {
+                item.texture_cache.iter().map(move |(path, cached)| (idx, path.clone(), cached.last_shown, pixels(cached)))
+            })
+            .collect();
+        for (idx, path) in texture_lru::lru_evictions(entries, MAX_TEXTURE_PIXELS, frame_start) {
+            self.projects[idx].texture_cache.remove(&path);
+        }
+    }
+
+    /// 请求执行批量操作
+    /// 开启确认且选中项目超过阈值时，先弹出确认对话框；否则直接执行
+    fn request_batch(&mut self, op: BatchOp) {
+        if self.batch_preview && !self.batch_value(op).trim().is_empty() {
+            let (impacts, trial_status) = self.batch_impact(op);
+            if impacts.is_empty() {
+                // 没有任何文件需要写入（值已相同或输入无效），直接显示试运行的结果说明
+                self.status_msg = trial_status;
+            } else {
+                self.pending_impact = Some((op, impacts));
+            }
+            return;
+        }
+        let selected = self.projects.iter().filter(|p| p.selected).count();
+        if self.batch_confirm && selected > BATCH_CONFIRM_THRESHOLD && !self.batch_value(op).trim().is_empty() {
+            self.pending_batch = Some(op);
+        } else {
+            self.run_batch(op);
+        }
+    }
+
+    /// 执行批量操作，执行前记录选中项目的配置以便撤销
+    fn run_batch(&mut self, op: BatchOp) {
+        let selected = (0..self.projects.len()).filter(|&idx| self.projects[idx].selected);
+        let step = UndoStep::capture(&self.projects, selected, tr(op.label()));
+        match op {
+            BatchOp::AppId => self.apply_batch_appid(),
+            BatchOp::Name => self.apply_batch_name(),
+            BatchOp::DouyinIds => self.apply_batch_douyin_ids(),
+            BatchOp::RemoveDouyinIds => self.remove_batch_douyin_ids(),
+        }
+        self.undo.record(step, &self.projects);
+    }
+
+    /// 在项目列表上试运行批量操作，列出会被写入的文件及新值
+    /// 使用与实际应用相同的逻辑，完成后恢复项目列表和提示状态，不产生任何修改
+    ///
+    /// # Returns
+    /// * `(Vec<FileImpact>, String)` - 文件影响列表，以及试运行产生的状态栏提示
+    fn batch_impact(&mut self, op: BatchOp) -> (Vec<FileImpact>, String) {
+        let before = self.projects.clone();
+        let status_msg = std::mem::take(&mut self.status_msg);
+        let duplicate_appids = std::mem::take(&mut self.duplicate_appids);
+        let undo = std::mem::take(&mut self.undo);
+        self.run_batch(op);
+        let after = std::mem::replace(&mut self.projects, before);
+        let trial_status = std::mem::replace(&mut self.status_msg, status_msg);
+        self.duplicate_appids = duplicate_appids;
+        self.undo = undo;
+        (impact::batch_impact(&self.projects, &after), trial_status)
+    }
+
+    /// 获取批量操作对应的输入值
+    fn batch_value(&self, op: BatchOp) -> &str {
+        match op {
+            BatchOp::AppId => &self.batch_appid,
+            BatchOp::Name => &self.batch_projectname,
+            BatchOp::DouyinIds => &self.batch_douyin_ids,
+            BatchOp::RemoveDouyinIds => &self.batch_remove_ids,
+        }
+    }
+
     /// 批量应用 AppID
     /// 将 batch_appid 的值应用到所有选中的项目
+    /// 开启自动递增时，按选中顺序依次使用 基础值、基础值+1 ...；
+    /// 应用后若出现重复的 AppID，会弹出提示
     fn apply_batch_appid(&mut self) {
-        if self.batch_appid.trim().is_empty() { return; }
+        let base = self.batch_appid.trim().to_string();
+        if base.is_empty() { return; }
+
+        if self.batch_appid_increment && appid::increment_appid(&base, 0).is_none() {
+            self.status_msg = trf("AppID「{}」不以数字结尾，无法自动递增，请逐个粘贴 AppID。", &[&base]);
+            return;
+        }
+
+        let mut offset = 0u64;
+        let (mut updated, mut js_updated, mut overflow) = (0, 0, false);
         for item in &mut self.projects {
             if item.selected {
+                let value = if self.batch_appid_increment {
+                    match appid::increment_appid(&base, offset) {
+                        Some(v) => v,
+                        None => {
+                            overflow = true;
+                            break;
+                        }
+                    }
+                } else {
+                    base.clone()
+                };
+                offset += 1;
+                let mut changed = false;
                 // 更新 JSON 配置中的 appid
-                item.config.appid = self.batch_appid.clone();
+                if item.config.appid != value {
+                    item.config.appid = value.clone();
+                    changed = true;
+                }
                 // 同时更新 JS 中的 AppID
                 if let Some(js) = &mut item.js_config {
-                    js.app_id = self.batch_appid.clone();
+                    if js.app_id != value {
+                        js.app_id = value;
+                        js_updated += 1;
+                        changed = true;
+                    }
+                }
+                if changed {
+                    updated += 1;
+                    item.refresh_modified();
                 }
-                item.is_modified = true;
             }
         }
-        self.status_msg = "已批量应用 AppID (含JS)，请点击保存生效。".to_string();
+        self.status_msg = batch_summary(
+            "AppID",
+            updated,
+            offset as usize - updated,
+            &trf(
+                "（其中 {} 个同时更新了 JS{}）",
+                &[&js_updated, &if self.batch_appid_increment { tr("，按末尾数字递增") } else { "" }],
+            ),
+        );
+        if overflow {
+            self.status_msg.push_str(tr(" ⚠ AppID 递增超出范围，之后的项目未应用"));
+        }
+
+        self.duplicate_appids = appid::find_duplicates(
+            self.projects.iter().map(|p| p.config.appid.as_str()),
+        );
     }
 
     /// 批量应用项目名称
     fn apply_batch_name(&mut self) {
         if self.batch_projectname.trim().is_empty() { return; }
+        let (mut updated, mut unchanged) = (0, 0);
         for item in &mut self.projects {
             if item.selected {
+                if item.config.projectname == self.batch_projectname {
+                    unchanged += 1;
+                    continue;
+                }
                 item.config.projectname = self.batch_projectname.clone();
-                item.is_modified = true;
+                item.refresh_modified();
+                updated += 1;
             }
         }
-        self.status_msg = "已批量应用项目名称，请点击保存生效。".to_string();
+        self.status_msg = batch_summary(tr("项目名"), updated, unchanged, "");
     }
 
     /// 批量应用 DouyinIDs
-    /// 仅针对存在 JS 配置的项目
+    /// 仅针对存在 JS 配置的项目；按 `batch_douyin_mode` 替换或追加到各项目原有的列表
     fn apply_batch_douyin_ids(&mut self) {
         if self.batch_douyin_ids.trim().is_empty() { return; }
         
-        // 移除所有空格和换行
-        let cleaned_ids = self.batch_douyin_ids.replace(|c: char| c.is_whitespace(), "");
+        // 移除所有空格和换行，丢弃多余逗号产生的空元素，并按顺序去掉重复的 ID
+        let mut new_ids = split_douyin_ids(&self.batch_douyin_ids);
+        let removed_duplicates = dedup_ids(&mut new_ids);
+        let cleaned_ids = new_ids.join(",");
         self.batch_douyin_ids = cleaned_ids.clone();
 
+        let (mut updated, mut unchanged, mut no_js) = (0, 0, 0);
         for item in &mut self.projects {
             if item.selected {
-                if let Some(js) = &mut item.js_config {
-                    js.douyin_ids_str = cleaned_ids.clone();
-                    item.is_modified = true;
+                let Some(js) = &mut item.js_config else {
+                    no_js += 1;
+                    continue;
+                };
+                let before = js.douyin_ids.clone();
+                match self.batch_douyin_mode {
+                    DouyinIdsMode::Replace => {
+                        js.douyin_ids_str = cleaned_ids.clone();
+                        js.sync_ids_from_str();
+                    }
+                    DouyinIdsMode::Append | DouyinIdsMode::AppendDedup => {
+                        let dedup = self.batch_douyin_mode == DouyinIdsMode::AppendDedup;
+                        js.douyin_ids = append_ids(&before, &new_ids, dedup);
+                        js.sync_str_from_ids();
+                    }
+                }
+                if js.douyin_ids == before {
+                    unchanged += 1;
+                } else {
+                    updated += 1;
+                    item.refresh_modified();
                 }
             }
         }
-        self.status_msg = "已批量应用 DouyinIDs (仅JS)，请点击保存生效。".to_string();
+        let mut note = if no_js > 0 { trf("（{} 个选中项目没有 JS 配置，已跳过）", &[&no_js]) } else { String::new() };
+        if removed_duplicates > 0 {
+            note.push_str(&trf("（输入中 {} 个重复的 ID 已去掉）", &[&removed_duplicates]));
+        }
+        let invalid = invalid_douyin_ids(&new_ids);
+        if !invalid.is_empty() {
+            note.push_str(&trf("（⚠ {} 个 ID 不是纯数字：{}，保存前会再次确认）", &[&invalid.len(), &invalid.join(", ")]));
+        }
+        let field = trf("DouyinIDs（{}）", &[&tr(self.batch_douyin_mode.label())]);
+        self.status_msg = batch_summary(&field, updated, unchanged, &note);
     }
 
-    /// 将项目目录打包为 ZIP 压缩包
-    fn build_zip(&mut self, index: usize) {
-        let item = &self.projects[index];
-        // 获取 project.config.json 所在的目录
-        let config_dir = match item.path.parent() {
-            Some(p) => p,
-            None => {
-                self.status_msg = "错误：无法获取配置文件所在目录".to_string();
-                return;
-            }
-        };
+    /// 从所有选中项目的 DouyinIDs 中移除 batch_remove_ids 中列出的 ID
+    fn remove_batch_douyin_ids(&mut self) {
+        let ids = split_douyin_ids(&self.batch_remove_ids);
+        if ids.is_empty() { return; }
+        self.batch_remove_ids = ids.join(",");
 
-        // 打包父目录：获取 config_dir 的父目录
-        let project_root = match config_dir.parent() {
-            Some(p) => p,
-            None => {
-                // 如果没有父目录（即 config_dir 已经是根目录），则回退到 config_dir
-                config_dir
+        let (mut updated, mut unchanged, mut no_js) = (0, 0, 0);
+        for item in self.projects.iter_mut().filter(|p| p.selected) {
+            if item.js_config.is_none() {
+                no_js += 1;
+            } else if item.remove_douyin_ids(&ids) {
+                updated += 1;
+            } else {
+                unchanged += 1;
             }
-        };
-
-        let project_name = if item.config.projectname.is_empty() {
-            project_root.file_name()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_else(|| "project".to_string())
-        } else {
-            item.config.projectname.clone()
-        };
-
-        let zip_filename = format!("{}.zip", project_name);
-        // 压缩包放在 project_root 的同级目录下
-        let zip_path = match project_root.parent() {
-            Some(p) => p.join(&zip_filename),
-            None => project_root.join(&zip_filename),
-        };
-
-        self.status_msg = format!("正在打包父目录: {} ...", zip_filename);
+        }
+        let mut msg = trf("移除 DouyinIDs: {} 个项目已更新", &[&updated]);
+        if unchanged > 0 {
+            msg.push_str(&trf("；{} 个项目不包含这些 ID", &[&unchanged]));
+        }
+        if no_js > 0 {
+            msg.push_str(&trf("；{} 个选中项目没有 JS 配置，已跳过", &[&no_js]));
+        }
+        if updated > 0 {
+            msg.push_str(tr("，请点击保存生效。"));
+        }
+        self.status_msg = msg;
+    }
 
-        match self.create_zip(project_root, &zip_path) {
-            Ok(_) => {
-                self.status_msg = format!("打包成功: {}", zip_path.display());
-                // 自动打开所在的文件夹
-                if let Some(parent) = zip_path.parent() {
-                    let _ = open::that(parent);
+    /// 将指定的配置模板应用到所有选中的项目
+    /// 选中项目按列表顺序编号，用于替换模板中的 `{index}` 占位符
+    fn apply_profile(&mut self, profile_index: usize) {
+        let Some(profile) = self.profiles.get(profile_index).cloned() else { return };
+        let selected = (0..self.projects.len()).filter(|&idx| self.projects[idx].selected);
+        let step = UndoStep::capture(&self.projects, selected, tr("应用配置模板"));
+        let mut index = 0;
+        let mut changed = 0;
+        for item in &mut self.projects {
+            if item.selected {
+                index += 1;
+                if profile.apply_to(item, index) {
+                    changed += 1;
                 }
             }
-            Err(e) => {
-                self.status_msg = format!("打包失败: {}", e);
-            }
         }
+        self.undo.record(step, &self.projects);
+        self.status_msg = trf("已将模板「{}」应用到 {} 个项目，请点击保存生效。", &[&profile.name, &changed]);
     }
 
-    /// 创建 ZIP 文件的辅助函数
-    fn create_zip(&self, src_dir: &Path, dst_file: &Path) -> anyhow::Result<()> {
-        let file = File::create(dst_file)?;
-        let mut zip = ZipWriter::new(file);
-        let options = SimpleFileOptions::default()
-            .compression_method(zip::CompressionMethod::Deflated)
-            .unix_permissions(0o755);
-
-        let mut buffer = Vec::new();
-        let mut it = WalkDir::new(src_dir).into_iter();
-
-        while let Some(entry) = it.next() {
-            let entry = entry?;
-            let path = entry.path();
-            let name = path.strip_prefix(src_dir)?;
-
-            if name.as_os_str().is_empty() {
-                continue;
+    /// 处理项目复选框的点击
+    /// 按住 Shift 点击时，把上次点击的项目到本项目之间（仅限当前可见的项目）
+    /// 都设为与本项目相同的选中状态
+    fn on_select_clicked(&mut self, idx: usize, shift: bool, visible: &[usize]) {
+        if let (true, Some(last)) = (shift, self.last_clicked) {
+            let (from, to) = (last.min(idx), last.max(idx));
+            let selected = self.projects[idx].selected;
+            for &i in visible.iter().filter(|&&i| (from..=to).contains(&i)) {
+                self.projects[i].selected = selected;
             }
+        }
+        self.last_clicked = Some(idx);
+    }
 
-            // 跳过一些不必要的文件夹和文件
-            if path.is_dir() {
-                let dir_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
-                if dir_name == "node_modules" || dir_name == ".git" || dir_name == ".svn" {
-                    it.skip_current_dir();
-                    continue;
-                }
-                
-                zip.add_directory(name.to_string_lossy(), options)?;
-            } else {
-                // 跳过当前的 zip 文件（如果它碰巧在源目录中）
-                if path == dst_file {
-                    continue;
-                }
+    /// 以紧凑列表显示项目，每个项目一行
+    /// 只绘制可见的行（`show_rows`），项目数量很大时也能保持流畅
+    fn show_compact_list(&mut self, ui: &mut egui::Ui, visible: &[usize]) {
+        let row_height = ui.spacing().interact_size.y;
+        let mut select_click = None;
+        egui::ScrollArea::vertical().show_rows(ui, row_height, visible.len(), |ui, rows| {
+            for &idx in &visible[rows] {
+                let item = &mut self.projects[idx];
+                ui.push_id(idx, |ui| {
+                    ui.horizontal(|ui| {
+                        if ui.checkbox(&mut item.selected, "").on_hover_text(tr("按住 Shift 点击可选择一段范围")).clicked() {
+                            select_click = Some((idx, ui.input(|i| i.modifiers.shift)));
+                        }
+                        let display_name = item.path.parent()
+                            .and_then(|p| p.file_name())
+                            .map(|s| s.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        if let Some(Some(channel)) = self.project_channels.get(idx) {
+                            ui.label(egui::RichText::new(format!("[{}]", channel)).weak());
+                        }
+                        if ui.link(display_name).on_hover_text(tr("切换到卡片视图并定位到此项目")).clicked() {
+                            self.compact_view = false;
+                            self.large_list_confirmed = true;
+                            self.scroll_to_project = Some(idx);
+                        }
+                        ui.label(tr("AppID:"));
+                        let appid_changed = item.config.appid != item.original.config.appid;
+                        if highlighted_text_edit(ui, &mut item.config.appid, appid_changed).changed() {
+                            item.refresh_modified();
+                        }
+                        if let Err(reason) = appid::validate_appid(&item.config.appid) {
+                            ui.label(egui::RichText::new("⚠").color(egui::Color32::RED)).on_hover_text(reason);
+                        }
+                        if item.is_modified {
+                            ui.label(egui::RichText::new(tr("● 待保存")).color(egui::Color32::RED));
+                        }
+                    });
+                });
+            }
+        });
+        if let Some((idx, shift)) = select_click {
+            self.on_select_clicked(idx, shift, visible);
+        }
+    }
+
+    /// 在抖音开发者工具中打开项目（project.config.json 所在目录）
+    fn open_in_ide(&mut self, index: usize) {
+        let Some(project_dir) = self.projects[index].path.parent() else { return };
+        self.status_msg = match self.ide_launcher.launch(project_dir) {
+            Ok(()) => trf("已在开发者工具中打开: {}", &[&project_dir.display()]),
+            Err(e) => trf("无法启动开发者工具：{}", &[&e]),
+        };
+    }
+
+    /// 点击"打包"：在保存对话框中选择输出路径（默认为被打包目录同级的 `<项目名>.zip`），
+    /// 路径已存在时先请用户确认是否覆盖
+    fn request_zip(&mut self, index: usize) {
+        if self.zip_rx.is_some() {
+            self.status_msg = tr("已有打包任务在进行中，请等待完成").to_string();
+            return;
+        }
+        let item = &self.projects[index];
+        let Some((_, default_path)) = packager::zip_plan(&item.path, &item.config.projectname) else {
+            self.status_msg = tr("错误：无法获取配置文件所在目录").to_string();
+            return;
+        };
+        let mut dialog = FileDialog::new()
+            .set_title(tr("保存压缩包"))
+            .add_filter("ZIP", &["zip"])
+            .set_file_name(default_path.file_name().unwrap_or_default().to_string_lossy());
+        if let Some(dir) = default_path.parent() {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(path) = dialog.save_file() else { return };
+        let path = packager::with_zip_extension(path);
+        if path.exists() {
+            self.confirm_zip_overwrite = Some((index, path));
+        } else {
+            self.build_zip(index, path);
+        }
+    }
+
+    /// 在后台线程中开始打包，并在状态栏说明当前压缩方式的取舍
+    fn start_zip(&mut self, jobs: Vec<ZipJob>, batch: bool) {
+        let compression = self.zip_options.compression;
+        self.status_msg = trf("开始打包（{}：{}）...", &[&tr(compression.label()), &tr(compression.tradeoff())]);
+        self.zip_rx = Some(packager::spawn_zip(jobs.clone(), self.zip_options.clone()));
+        self.zip_jobs = jobs;
+        self.zip_progress = (0, 0, 0);
+        self.zip_batch = batch;
+    }
+
+    /// 打包 project.config.json 所在目录的父目录，输出到 `request_zip` 中选择的路径
+    fn build_zip(&mut self, index: usize, zip_path: PathBuf) {
+        let item = &self.projects[index];
+        let Some((project_root, _)) = packager::zip_plan(&item.path, &item.config.projectname) else {
+            self.status_msg = tr("错误：无法获取配置文件所在目录").to_string();
+            return;
+        };
+        let job = ZipJob { name: item.config.projectname.clone(), src_dir: project_root, dst_file: zip_path };
+        self.start_zip(vec![job], false);
+    }
 
-                zip.start_file(name.to_string_lossy(), options)?;
-                let mut f = File::open(path)?;
-                f.read_to_end(&mut buffer)?;
-                zip.write_all(&buffer)?;
-                buffer.clear();
+    /// 依次打包所有选中的项目，压缩包统一放在 `zip_output_dir` 中
+    /// 同名项目的输出路径会自动加上 AppID 或序号，避免后一个覆盖前一个；结果在对话框中逐项列出
+    /// 输出目录中已有同名压缩包时先请用户确认是否覆盖
+    fn build_zip_selected(&mut self) {
+        let Some(out_dir) = self.zip_output_dir.clone() else { return };
+        let mut plans = Vec::new();
+        for item in self.projects.iter().filter(|p| p.selected) {
+            if let Some((root, zip_path)) = packager::zip_plan(&item.path, &item.config.projectname) {
+                let zip_path = out_dir.join(zip_path.file_name().unwrap_or_default());
+                plans.push((item.config.projectname.clone(), item.config.appid.clone(), root, zip_path));
             }
         }
+        let mut zip_paths: Vec<PathBuf> = plans.iter().map(|(_, _, _, path)| path.clone()).collect();
+        let suffixes: Vec<&str> = plans.iter().map(|(_, appid, _, _)| appid.as_str()).collect();
+        self.zip_renamed = packager::dedup_zip_paths(&mut zip_paths, &suffixes);
+        let jobs = plans
+            .into_iter()
+            .zip(zip_paths)
+            .map(|((name, _, src_dir, _), dst_file)| ZipJob { name, src_dir, dst_file })
+            .collect::<Vec<_>>();
+        let existing: Vec<PathBuf> = jobs.iter().map(|job| job.dst_file.clone()).filter(|path| path.exists()).collect();
+        if existing.is_empty() {
+            self.start_zip(jobs, true);
+        } else {
+            self.confirm_batch_zip_overwrite = Some((jobs, existing));
+        }
+    }
+
+    /// 接收后台打包的进度和结果，全部完成时汇总到状态栏
+    fn poll_zip(&mut self, ctx: &egui::Context) {
+        let Some(rx) = &self.zip_rx else { return };
+        let mut finished = None;
+        loop {
+            match rx.try_recv() {
+                Ok(ZipEvent::Progress(job, done, total)) => self.zip_progress = (job, done, total),
+                Ok(ZipEvent::Done(results)) => {
+                    finished = Some(Some(results));
+                    break;
+                }
+                Err(TryRecvError::Empty) => break,
+                // 打包线程意外退出
+                Err(TryRecvError::Disconnected) => {
+                    finished = Some(None);
+                    break;
+                }
+            }
+        }
+        let Some(results) = finished else {
+            ctx.request_repaint();
+            return;
+        };
+        self.zip_rx = None;
+        // 打包期间请求过退出：压缩包已写完，重新发起关闭（仍会经过未保存修改的确认）
+        if std::mem::take(&mut self.close_after_zip) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            return;
+        }
+        let jobs = std::mem::take(&mut self.zip_jobs);
+        let Some(results) = results else {
+            self.status_msg = tr("打包意外中断").to_string();
+            return;
+        };
+        if self.zip_batch {
+            self.finish_zip_selected(jobs, results);
+            return;
+        }
+        match results.into_iter().next() {
+            Some(Ok((zip_path, symlinks))) => {
+                self.status_msg = trf("打包成功（{}）: {}", &[&tr(self.zip_options.compression.label()), &zip_path.display()]);
+                self.status_msg.push_str(&symlink_note(symlinks, self.zip_options.follow_links));
+                // 自动打开所在的文件夹
+                if let Some(parent) = zip_path.parent() {
+                    let _ = open::that(parent);
+                }
+            }
+            Some(Err(e)) => self.status_msg = trf("打包失败: {}", &[&e]),
+            None => {}
+        }
+    }
 
-        zip.finish()?;
-        Ok(())
+    /// 批量打包完成：在对话框中逐项列出结果，并在状态栏汇总
+    fn finish_zip_selected(&mut self, jobs: Vec<ZipJob>, results: Vec<Result<(PathBuf, SymlinkStats), String>>) {
+        let renamed = std::mem::take(&mut self.zip_renamed);
+        let mut symlinks = SymlinkStats::default();
+        self.zip_report = jobs
+            .into_iter()
+            .zip(results)
+            .enumerate()
+            .map(|(i, (job, result))| {
+                let result = result.map(|(path, stats)| {
+                    symlinks.add(stats);
+                    path
+                });
+                ZipResult { name: job.name, result, renamed: renamed.contains(&i) }
+            })
+            .collect();
+        let out_dir = self.zip_output_dir.clone().unwrap_or_default();
+        let failed = self.zip_report.iter().filter(|r| r.result.is_err()).count();
+        self.status_msg = trf(
+            "打包完成（{}，输出到 {}）：成功 {} 个，失败 {} 个",
+            &[
+                &tr(self.zip_options.compression.label()),
+                &out_dir.display(),
+                &(self.zip_report.len() - failed),
+                &failed,
+            ],
+        );
+        if !renamed.is_empty() {
+            self.status_msg.push_str(&trf("，{} 个同名压缩包已自动改名", &[&renamed.len()]));
+        }
+        self.status_msg.push_str(&symlink_note(symlinks, self.zip_options.follow_links));
+        let failures: Vec<String> = self.zip_report.iter()
+            .filter_map(|r| r.result.as_ref().err().map(|e| format!("{}（{}）", r.name, e)))
+            .collect();
+        if !failures.is_empty() {
+            self.status_msg.push_str(&trf("。失败的项目：{}", &[&failures.join(", ")]));
+        }
+    }
+}
+
+/// 遇到符号链接时附加在状态栏中的说明，说明链接是被跟随还是被忽略；没有遇到时为空
+fn symlink_note(symlinks: SymlinkStats, followed: bool) -> String {
+    if symlinks.is_empty() {
+        return String::new();
+    }
+    let mut note = if followed {
+        trf("。已跟随 {} 个符号链接", &[&symlinks.links])
+    } else {
+        trf("。{} 个符号链接未跟随（可在设置中开启「跟随符号链接」）", &[&symlinks.links])
+    };
+    if symlinks.loops > 0 {
+        note.push_str(&trf("，跳过 {} 个循环链接", &[&symlinks.loops]));
+    }
+    note
+}
+
+/// 生成批量操作的结果摘要，如 "AppID: 12 个项目已更新；3 个项目的值本来就相同"
+/// `note` 附加在更新数量之后，用于说明字段特有的情况
+fn batch_summary(field: &str, updated: usize, unchanged: usize, note: &str) -> String {
+    let mut msg = trf("{}: {} 个项目已更新{}", &[&field, &updated, &note]);
+    if unchanged > 0 {
+        msg.push_str(&trf("；{} 个项目的值本来就相同", &[&unchanged]));
+    }
+    if updated > 0 {
+        msg.push_str(tr("，请点击保存生效。"));
     }
+    msg
+}
+
+/// 判断项目是否匹配筛选条件（`filter` 需已转为小写，为空时全部匹配）
+fn project_matches(item: &ProjectItem, filter: &str) -> bool {
+    filter.is_empty()
+        || item.path.to_string_lossy().to_lowercase().contains(filter)
+        || item.config.appid.to_lowercase().contains(filter)
+        || item.config.projectname.to_lowercase().contains(filter)
+}
+
+/// 绘制单行输入框，内容与原始值不同时以浅黄色背景高亮
+/// 让用户一眼看出哪些字段会在保存时写入
+fn highlighted_text_edit(ui: &mut egui::Ui, text: &mut String, changed: bool) -> egui::Response {
+    ui.scope(|ui| {
+        if changed {
+            ui.visuals_mut().extreme_bg_color = if ui.visuals().dark_mode {
+                egui::Color32::from_rgb(90, 80, 20)
+            } else {
+                egui::Color32::from_rgb(255, 245, 180)
+            };
+        }
+        ui.text_edit_singleline(text)
+    })
+    .inner
+}
+
+/// 逐条编辑 ID 列表，每个 ID 独占一行，带移除和添加按钮
+/// 返回列表是否被修改
+fn id_list_editor(ui: &mut egui::Ui, ids: &mut Vec<String>) -> bool {
+    let mut changed = false;
+    let mut remove_index = None;
+    for (id_idx, id) in ids.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(format!("#{}", id_idx + 1)).small());
+            if ui.text_edit_singleline(id).changed() {
+                *id = id.replace(|c: char| c.is_whitespace() || c == ',', "");
+                changed = true;
+            }
+            if !invalid_douyin_ids(std::slice::from_ref(id)).is_empty() {
+                ui.label(egui::RichText::new("⚠").color(egui::Color32::RED)).on_hover_text(tr("DouyinID 应为纯数字"));
+            }
+            if ui.small_button("🗑").on_hover_text(tr("移除此 ID")).clicked() {
+                remove_index = Some(id_idx);
+            }
+        });
+    }
+    if let Some(i) = remove_index {
+        ids.remove(i);
+        changed = true;
+    }
+    if ui.small_button(tr("➕ 添加")).clicked() {
+        ids.push(String::new());
+        changed = true;
+    }
+    changed
+}
+
+/// DouyinIDs 中有非数字的 ID 时显示红色警告，悬停列出这些 ID
+fn invalid_ids_warning(ui: &mut egui::Ui, ids: &[String]) {
+    let invalid = invalid_douyin_ids(ids);
+    if !invalid.is_empty() {
+        ui.label(egui::RichText::new(trf("⚠ {} 个非数字 ID", &[&invalid.len()])).small().color(egui::Color32::RED))
+            .on_hover_text(trf("DouyinID 应为纯数字，以下 ID 格式不正确：\n{}", &[&invalid.join("\n")]));
+    }
+}
+
+/// 绘制覆盖整个窗口的半透明忙碌遮罩，中间显示加载动画和当前操作
+/// 遮罩位于最上层并吞掉点击，操作进行中无法与下层界面交互
+fn show_busy_overlay(ctx: &egui::Context, text: &str) {
+    let screen = ctx.screen_rect();
+    egui::Area::new(egui::Id::new("busy_overlay"))
+        .order(egui::Order::Foreground)
+        .fixed_pos(screen.min)
+        .show(ctx, |ui| {
+            ui.allocate_rect(screen, egui::Sense::click_and_drag());
+            ui.painter().rect_filled(screen, 0.0, egui::Color32::from_black_alpha(140));
+            let content = egui::Rect::from_center_size(screen.center(), egui::vec2(320.0, 80.0));
+            ui.allocate_new_ui(egui::UiBuilder::new().max_rect(content), |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add(egui::Spinner::new().size(32.0));
+                    ui.label(egui::RichText::new(text).strong().color(egui::Color32::WHITE));
+                });
+            });
+        });
+}
+
+/// 递归绘制目录树节点
+/// 包含 project.config.json 的文件夹会高亮显示，点击后将列表滚动到对应项目
+fn show_tree_node(ui: &mut egui::Ui, node: &DirNode, depth: usize, scroll_to: &mut Option<usize>) {
+    let text = if node.project.is_some() {
+        egui::RichText::new(format!("📄 {}", node.name))
+            .color(egui::Color32::from_rgb(0, 150, 80))
+            .strong()
+    } else {
+        egui::RichText::new(format!("📁 {}", node.name))
+    };
+
+    // 叶子节点：直接显示为可点击的标签
+    if node.children.is_empty() {
+        if ui.selectable_label(false, text).clicked() {
+            if let Some(idx) = node.project {
+                *scroll_to = Some(idx);
+            }
+        }
+        return;
+    }
+
+    // 默认展开前两层，避免大型目录一次性铺满面板
+    egui::CollapsingHeader::new(text)
+        .default_open(depth < 2)
+        .show(ui, |ui| {
+            // 文件夹本身也是项目时，提供一个定位入口
+            if let Some(idx) = node.project {
+                if ui.selectable_label(false, tr("➡ 定位到此项目")).clicked() {
+                    *scroll_to = Some(idx);
+                }
+            }
+            for child in &node.children {
+                show_tree_node(ui, child, depth + 1, scroll_to);
+            }
+        });
 }
 
 impl eframe::App for MyApp {
+    /// 退出时把设置写入设置文件
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.settings().save();
+    }
+
     /// 每一帧的 UI 更新函数
     /// 这里定义了整个应用程序的 UI 布局
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let frame_start = Instant::now();
         let mut zip_index = None;
+        let mut ide_index = None;
+        // 本帧展开、需要在后台查找预览图的项目
+        let mut probe_indices = Vec::new();
+        let mut note_edited = false;
+
+        // 正在打包时先取消关闭，等压缩包写完再退出，避免中途退出留下不完整的文件
+        // 关闭窗口时如有未保存的修改，先取消关闭并询问用户
+        let close_requested = ctx.input(|i| i.viewport().close_requested());
+        if close_requested && self.zip_rx.is_some() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.close_after_zip = true;
+        } else if close_requested
+            && !self.close_confirmed
+            && self.projects.iter().any(|p| p.is_modified)
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.confirm_close = true;
+        }
+
+        // egui 自带 Ctrl+= / Ctrl+- / Ctrl+0 缩放快捷键，这里同步当前缩放以便显示和持久化
+        if !self.ui_scale_dragging {
+            self.ui_scale = ctx.zoom_factor();
+        }
+        // 记录窗口大小供下次启动使用；最大化时保留之前的大小，避免还原后窗口占满屏幕
+        let viewport = ctx.input(|i| (i.viewport().inner_rect, i.viewport().maximized));
+        if let (Some(rect), Some(false) | None) = viewport {
+            self.window_size = (rect.size() * ctx.zoom_factor()).into();
+        }
+
+        self.poll_scan(ctx);
+        self.poll_zip(ctx);
+        self.poll_probes(ctx);
+
+        // Ctrl+Z 撤销、Ctrl+Y / Ctrl+Shift+Z 重做批量修改；输入框获得焦点时留给文本编辑自身的撤销
+        if ctx.memory(|m| m.focused().is_none()) {
+            let (undo, redo) = ctx.input_mut(|i| {
+                let redo = i.consume_key(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z)
+                    || i.consume_key(egui::Modifiers::COMMAND, egui::Key::Y);
+                (i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z), redo)
+            });
+            if undo {
+                self.undo();
+            }
+            if redo {
+                self.redo();
+            }
+        }
+
+        // 检查后台搜索是否完成
+        if let Some(rx) = &self.search_rx {
+            match rx.try_recv() {
+                Ok(hits) => {
+                    self.status_msg = trf("搜索完成，{} 个项目的 JS 包含「{}」", &[&hits.len(), &self.search_query]);
+                    self.search_results = hits;
+                    self.search_rx = None;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => ctx.request_repaint(),
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => self.search_rx = None,
+            }
+        }
+
+        // 检查后台 CSV 导入是否完成
+        if let Some(rx) = &self.import_rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.import_rx = None;
+                    self.import_progress = None;
+                    match result {
+                        Ok(report) => self.finish_csv_import(report),
+                        Err(e) => self.status_msg = trf("CSV 导入失败: {}", &[&e]),
+                    }
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => ctx.request_repaint(),
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => self.import_rx = None,
+            }
+        }
+
+        if self.regex_debug.open {
+            self.regex_debug.show(ctx, &self.scan_options.app_id_field);
+        }
+
+        // --- 左侧目录树面板 ---
+        if self.show_tree {
+            if let Some(dir_tree) = &self.dir_tree {
+                egui::SidePanel::left("dir_tree_panel")
+                    .resizable(true)
+                    .default_width(220.0)
+                    .show(ctx, |ui| {
+                        ui.label(egui::RichText::new(tr("目录树")).strong());
+                        ui.separator();
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            show_tree_node(ui, dir_tree, 0, &mut self.scroll_to_project);
+                        });
+                    });
+            }
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             // --- 顶部工具栏 ---
             ui.horizontal(|ui| {
-                ui.heading("🛠️ 字节小游戏配置助手");
+                ui.heading(tr("🛠️ 字节小游戏配置助手"));
                 // 右对齐按钮
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if ui.button("📂 选择根目录").clicked() {
+                    let lang_before = self.lang;
+                    egui::ComboBox::from_id_salt("lang")
+                        .selected_text(self.lang.label())
+                        .width(80.0)
+                        .show_ui(ui, |ui| {
+                            for lang in Lang::ALL {
+                                ui.selectable_value(&mut self.lang, lang, lang.label());
+                            }
+                        });
+                    if self.lang != lang_before {
+                        i18n::set_lang(self.lang);
+                    }
+                    if ui.button(tr("📂 选择根目录")).clicked() {
                         // 打开文件夹选择对话框
                         if let Some(path) = FileDialog::new().pick_folder() {
                             self.clear_data();
                             self.root_path = Some(path);
-                            self.scan();
+                            if self.scan_on_pick {
+                                self.start_scan();
+                            } else {
+                                self.status_msg = tr("已选择根目录，调整扫描设置后点击「🔍 扫描」开始。").to_string();
+                            }
                         }
                     }
-                    // 仅当已选择路径时显示刷新按钮
-                    if self.root_path.is_some() && ui.button("🔄 刷新列表").clicked() {
-                        self.scan();
+                    // 仅当已选择路径时显示扫描/刷新按钮
+                    if self.root_path.is_some() {
+                        let scanning = self.scan_rx.is_some();
+                        let label = if self.dir_tree.is_some() || scanning { tr("🔄 刷新列表") } else { tr("🔍 扫描") };
+                        if ui.button(label).clicked() {
+                            self.start_scan();
+                        }
+                        if scanning {
+                            let (done, total) = self.scan_progress.unwrap_or((0, 0));
+                            let fraction = if total == 0 { 0.0 } else { done as f32 / total as f32 };
+                            ui.add(
+                                egui::ProgressBar::new(fraction)
+                                    .desired_width(160.0)
+                                    .text(format!("{} / {}", done, total)),
+                            );
+                            if ui.button(tr("取消")).clicked() {
+                                self.scan_cancel.store(true, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                    // 后台打包进度：当前项目及其已写入的文件数
+                    if self.zip_rx.is_some() {
+                        let (job, done, total) = self.zip_progress;
+                        let fraction = if total == 0 { 0.0 } else { done as f32 / total as f32 };
+                        let name = self.zip_jobs.get(job).map(|j| j.name.as_str()).unwrap_or_default();
+                        ui.label(trf("📦 正在打包 {} ({}/{})", &[&name, &(job + 1), &self.zip_jobs.len()]));
+                        ui.add(
+                            egui::ProgressBar::new(fraction)
+                                .desired_width(160.0)
+                                .text(format!("{} / {}", done, total)),
+                        );
+                    }
+                    // 撤销 / 重做批量修改（Ctrl+Z / Ctrl+Y）
+                    let undo_label = self.undo.undo_label().map(str::to_string);
+                    if ui.add_enabled(undo_label.is_some(), egui::Button::new(tr("↶ 撤销")))
+                        .on_hover_text(trf("撤销：{}（Ctrl+Z）", &[&undo_label.as_deref().unwrap_or("-")]))
+                        .clicked()
+                    {
+                        self.undo();
+                    }
+                    let redo_label = self.undo.redo_label().map(str::to_string);
+                    if ui.add_enabled(redo_label.is_some(), egui::Button::new(tr("↷ 重做")))
+                        .on_hover_text(trf("重做：{}（Ctrl+Y）", &[&redo_label.as_deref().unwrap_or("-")]))
+                        .clicked()
+                    {
+                        self.redo();
+                    }
+                    // 存在未保存修改时提供"全部还原"入口，需二次确认
+                    if self.projects.iter().any(|p| p.is_modified) && ui.button(tr("↩ 全部还原")).clicked() {
+                        self.confirm_revert_all = true;
+                    }
+                    // 有扫描结果时才允许切换目录树
+                    if self.dir_tree.is_some() {
+                        ui.toggle_value(&mut self.show_tree, tr("🌲 目录树"));
                     }
                 });
             });
@@ -315,61 +1975,443 @@ impl eframe::App for MyApp {
             // 显示当前路径
             if let Some(path) = &self.root_path {
                 ui.horizontal(|ui| {
-                    ui.small(format!("当前路径: {}", path.display()));
-                    if ui.button("📁 打开").clicked() {
+                    ui.small(trf("当前路径: {}", &[&path.display()]));
+                    if ui.button(tr("📁 打开")).clicked() {
                         // 使用系统默认文件管理器打开目录
                         let _ = open::that(path);
                     }
                 });
             }
             
+            // --- 设置 ---
+            ui.collapsing(tr("⚙ 设置"), |ui| {
+                ui.label(egui::RichText::new(tr("扫描")).strong());
+                ui.checkbox(&mut self.scan_on_pick, tr("选择后立即扫描"))
+                    .on_hover_text(tr("关闭后，选择根目录只记录路径，需手动点击「扫描」"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("图片探测线程数:"));
+                    ui.add(egui::DragValue::new(&mut self.scan_options.probe_threads).range(1..=64));
+                    if ui.small_button(tr("默认")).on_hover_text(tr("恢复为 CPU 核心数")).clicked() {
+                        self.scan_options.probe_threads = scanner::default_probe_threads();
+                    }
+                });
+                ui.label(egui::RichText::new(tr("机械硬盘上建议调低线程数，避免频繁寻道拖慢扫描")).small().weak());
+                ui.checkbox(&mut self.scan_options.skip_hidden, tr("跳过隐藏目录"))
+                    .on_hover_text(tr("不进入以 . 开头的文件夹（如 .cache、.vscode、.git），扫描更快，也不会显示其中的图片"));
+                let follow = ui.checkbox(&mut self.scan_options.follow_links, tr("跟随符号链接"))
+                    .on_hover_text(tr("扫描和打包时进入符号链接（目录联接）指向的目录；关闭时忽略链接本身。指向上级目录的循环链接总会被跳过"));
+                if follow.changed() {
+                    // 扫描和打包使用同一设置，避免扫描到的项目打包时缺少内容
+                    self.zip_options.follow_links = self.scan_options.follow_links;
+                }
+                ui.checkbox(&mut self.scan_options.js_only, tr("仅含 JS 配置的项目"))
+                    .on_hover_text(tr("扫描时跳过没有找到 JS 配置的项目，只批量编辑 JS 时减少干扰"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("JS AppID 字段名:"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.scan_options.app_id_field)
+                            .hint_text(DEFAULT_APP_ID_FIELD)
+                            .desired_width(120.0),
+                    ).on_hover_text(tr("部分打包产物使用 aid、applicationId 等字段名；修改后需重新扫描"));
+                    if self.app_id_field_stale() {
+                        ui.label(egui::RichText::new(tr("⚠ 需重新扫描")).color(egui::Color32::from_rgb(220, 140, 0)))
+                            .on_hover_text(tr("当前项目是按旧字段名扫描的，重新扫描前无法保存"));
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("渠道目录层级:"));
+                    let depth = ui.add(egui::DragValue::new(&mut self.channel_depth).range(0..=8))
+                        .on_hover_text(tr("按目录约定识别渠道：1 表示 根目录/<渠道>/<游戏>/project.config.json；0 表示不识别"));
+                    if depth.changed() {
+                        self.refresh_channels();
+                    }
+                });
+                ui.checkbox(&mut self.scan_options.match_width, tr("按宽度 (750px) 识别预览图"));
+                ui.checkbox(&mut self.scan_options.lazy_images, tr("展开卡片时再查找预览图"))
+                    .on_hover_text(tr("扫描时只解析配置，不遍历素材目录；宽高比校验只对已查找过预览图的项目生效"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("预览图文件名:"));
+                    let edit = ui.add(
+                        egui::TextEdit::singleline(&mut self.preview_patterns_input).hint_text("preview.*, cover.jpg"),
+                    ).on_hover_text(tr("逗号分隔的通配符模式（* 任意字符，? 单个字符，不区分大小写），与宽度规则满足其一即可"));
+                    if edit.changed() {
+                        self.scan_options.name_patterns = self.preview_patterns_input
+                            .split(',')
+                            .map(|p| p.trim().to_string())
+                            .filter(|p| !p.is_empty())
+                            .collect();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.aspect_check, tr("校验预览图宽高比:"));
+                    let edit = ui.add_enabled(
+                        self.aspect_check,
+                        egui::TextEdit::singleline(&mut self.aspect_ratios_input)
+                            .hint_text(DEFAULT_ASPECT_RATIOS)
+                            .desired_width(120.0),
+                    ).on_hover_text(tr("逗号分隔的宽高比，如 3:4, 1:1（允许 2% 误差）；不符合任一比例的预览图会在项目上提示"));
+                    if edit.changed() {
+                        self.aspect_ratios = parse_aspect_ratios(&self.aspect_ratios_input);
+                    }
+                });
+                if let Some((total, walk, probe)) = self.last_scan_duration {
+                    ui.label(trf(
+                        "上次扫描耗时: {}s（目录遍历 {}s，图片探测 {}s）",
+                        &[
+                            &format!("{:.2}", total.as_secs_f32()),
+                            &format!("{:.2}", walk.as_secs_f32()),
+                            &format!("{:.2}", probe.as_secs_f32()),
+                        ],
+                    ));
+                }
+
+                ui.separator();
+                ui.label(egui::RichText::new(tr("界面")).strong());
+                ui.horizontal(|ui| {
+                    ui.label(tr("缩放:"));
+                    let slider = ui.add(egui::Slider::new(&mut self.ui_scale, UI_SCALE_RANGE).step_by(0.05));
+                    // 拖动过程中不立即生效，避免界面缩放导致滑块在指针下来回跳动
+                    self.ui_scale_dragging = slider.dragged();
+                    if slider.drag_stopped() || (slider.changed() && !slider.dragged()) {
+                        ctx.set_zoom_factor(self.ui_scale);
+                    }
+                    if ui.small_button(tr("重置")).clicked() {
+                        self.ui_scale = 1.0;
+                        ctx.set_zoom_factor(1.0);
+                    }
+                });
+                ui.label(egui::RichText::new(tr("快捷键：Ctrl+= 放大，Ctrl+- 缩小，Ctrl+0 重置")).small().weak());
+                ui.horizontal(|ui| {
+                    let cached: usize = self.projects.iter().map(|p| p.texture_cache.len()).sum();
+                    ui.label(trf("已缓存图片: {}", &[&cached]));
+                    if ui.add_enabled(cached > 0, egui::Button::new(tr("🧹 清理图片缓存")))
+                        .on_hover_text(tr("释放已加载的预览图以减少内存占用，再次查看项目时会重新加载"))
+                        .clicked()
+                    {
+                        let freed = self.clear_texture_cache();
+                        self.status_msg = trf("已清理 {} 张图片的缓存", &[&freed]);
+                    }
+                });
+
+                ui.separator();
+                ui.label(egui::RichText::new(tr("保存")).strong());
+                ui.checkbox(&mut self.save_options.format_assignments, tr("规范 JS 赋值的空格"))
+                    .on_hover_text(tr("保存时把修改的字段写成 .appId = \"x\"、.douyinIds = [...] 的形式，不会格式化文件的其他部分"));
+                ui.checkbox(&mut self.backup_before_save, tr("保存前备份为 .bak"))
+                    .on_hover_text(tr("每个文件在本次运行中第一次被覆盖前，把原内容复制为同目录下的 <文件名>.bak"));
+                if ui.add_enabled(!self.projects.is_empty(), egui::Button::new(tr("恢复备份"))).clicked() {
+                    self.confirm_restore_backups = true;
+                }
+
+                ui.separator();
+                ui.label(egui::RichText::new(tr("开发者工具")).strong());
+                ui.horizontal(|ui| {
+                    ui.label(tr("路径:"));
+                    ui.add(egui::TextEdit::singleline(&mut self.ide_launcher.path).hint_text(tr("抖音开发者工具或其 CLI 的可执行文件")));
+                    if ui.button(tr("浏览")).clicked() {
+                        if let Some(path) = FileDialog::new().pick_file() {
+                            self.ide_launcher.path = path.display().to_string();
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("参数:"));
+                    ui.text_edit_singleline(&mut self.ide_launcher.args)
+                        .on_hover_text(tr("以空格分隔，{project} 会被替换为项目目录"));
+                });
+
+                ui.separator();
+                ui.label(egui::RichText::new(tr("打包")).strong());
+                ui.checkbox(&mut self.zip_options.skip_zip_files, tr("跳过源目录中已有的 .zip 文件"))
+                    .on_hover_text(tr("避免把之前打出的压缩包嵌套进新的压缩包；输出文件本身始终会被排除"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("压缩方式:"));
+                    egui::ComboBox::from_id_salt("zip_compression")
+                        .selected_text(tr(self.zip_options.compression.label()))
+                        .show_ui(ui, |ui| {
+                            for compression in ZipCompression::ALL {
+                                ui.selectable_value(&mut self.zip_options.compression, compression, tr(compression.label()))
+                                    .on_hover_text(tr(compression.tradeoff()));
+                            }
+                        });
+                    ui.label(egui::RichText::new(tr(self.zip_options.compression.tradeoff())).small().weak());
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("排除:"));
+                    let edit = ui.add(
+                        egui::TextEdit::singleline(&mut self.zip_excludes_input).hint_text("node_modules, .git, *.psd"),
+                    ).on_hover_text(tr("逗号分隔的通配符模式（* 任意字符，? 单个字符，不区分大小写），与每个文件和文件夹的名称比较，匹配的文件夹整个跳过"));
+                    if edit.changed() {
+                        self.zip_options.exclude_patterns = self.zip_excludes_input
+                            .split(',')
+                            .map(|p| p.trim().to_string())
+                            .filter(|p| !p.is_empty())
+                            .collect();
+                    }
+                    if ui.small_button(tr("默认")).on_hover_text(packager::DEFAULT_EXCLUDE_PATTERNS.join(", ")).clicked() {
+                        self.zip_options.exclude_patterns = ZipOptions::default().exclude_patterns;
+                        self.zip_excludes_input = self.zip_options.exclude_patterns.join(", ");
+                    }
+                });
+
+                ui.separator();
+                ui.label(egui::RichText::new(tr("调试")).strong());
+                ui.checkbox(&mut self.regex_debug.open, tr("🧪 正则调试面板"))
+                    .on_hover_text(tr("粘贴 JS 片段，查看 AppID / DouyinIDs 的匹配和替换结果，不会修改任何文件"));
+            });
+
+            // --- JS 内容搜索 ---
+            if !self.projects.is_empty() {
+                ui.collapsing(tr("🔎 搜索 JS 内容"), |ui| {
+                    ui.horizontal(|ui| {
+                        let edit = ui.add(egui::TextEdit::singleline(&mut self.search_query).hint_text(tr("如旧的 SDK Key")));
+                        let submitted = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        let searching = self.search_rx.is_some();
+                        if searching {
+                            ui.spinner();
+                        } else if (ui.button(tr("搜索")).clicked() || submitted) && !self.search_query.is_empty() {
+                            self.search_rx = Some(search::spawn_search(&self.projects, &self.search_query));
+                            self.status_msg = trf("正在搜索「{}」...", &[&self.search_query]);
+                        }
+                    });
+                    for hit in &self.search_results {
+                        let Some(item) = self.projects.get(hit.project) else { continue };
+                        ui.horizontal(|ui| {
+                            if ui.link(trf("{} ({} 处)", &[&item.config.projectname, &hit.count])).clicked() {
+                                self.scroll_to_project = Some(hit.project);
+                            }
+                            ui.label(egui::RichText::new(format!("…{}", hit.first.before)).monospace().small().weak());
+                            ui.label(egui::RichText::new(&hit.first.matched).monospace().small()
+                                .color(egui::Color32::from_rgb(255, 140, 0)));
+                            ui.label(egui::RichText::new(format!("{}…", hit.first.after)).monospace().small().weak());
+                        });
+                    }
+                });
+            }
+
             ui.separator();
 
             // --- 批量操作区 ---
             // 仅在有项目时显示
             if !self.projects.is_empty() {
                 ui.group(|ui| {
-                    ui.label(egui::RichText::new("批量修改 (仅针对选中项目)").strong());
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(tr("批量修改 (仅针对选中项目)")).strong());
+                        let hint = tr("作用于当前筛选结果中的项目，被筛选隐藏的项目保持不变");
+                        if ui.small_button(tr("全选")).on_hover_text(hint).clicked() {
+                            self.update_visible_selection(|_| true);
+                        }
+                        if ui.small_button(tr("全不选")).on_hover_text(hint).clicked() {
+                            self.update_visible_selection(|_| false);
+                        }
+                        if ui.small_button(tr("反选")).on_hover_text(hint).clicked() {
+                            self.update_visible_selection(|selected| !selected);
+                        }
+                        let selected = self.projects.iter().filter(|p| p.selected).count();
+                        ui.label(trf("已选 {} / 共 {}", &[&selected, &self.projects.len()]));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.checkbox(&mut self.batch_confirm, tr("批量操作需确认"))
+                                .on_hover_text(trf("选中超过 {} 个项目时，批量应用前弹出确认", &[&BATCH_CONFIRM_THRESHOLD]));
+                            ui.checkbox(&mut self.batch_preview, tr("应用前预览影响"))
+                                .on_hover_text(tr("先列出会被写入的配置文件、JS 文件及新值，确认后才修改"));
+                        });
+                    });
                     
                     let label_width = 90.0; // 固定标签宽度以对齐输入框
                     
                     // Row 1: AppID
                     ui.horizontal(|ui| {
-                        ui.add_sized([label_width, 20.0], egui::Label::new("统一 AppID:"));
+                        ui.add_sized([label_width, 20.0], egui::Label::new(tr("统一 AppID:")));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            if ui.button("应用").clicked() { self.apply_batch_appid(); }
+                            if ui.button(tr("应用")).clicked() { self.request_batch(BatchOp::AppId); }
+                            ui.checkbox(&mut self.batch_appid_increment, tr("自动递增"))
+                                .on_hover_text(tr("按选中顺序为每个项目的 AppID 末尾数字依次加 1，例如 tt0001、tt0002 ..."));
                             ui.add(egui::TextEdit::singleline(&mut self.batch_appid).desired_width(f32::INFINITY));
                         });
                     });
                     
                     // Row 2: Project Name
                     ui.horizontal(|ui| {
-                        ui.add_sized([label_width, 20.0], egui::Label::new("统一项目名:"));
+                        ui.add_sized([label_width, 20.0], egui::Label::new(tr("统一项目名:")));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            if ui.button("应用").clicked() { self.apply_batch_name(); }
+                            if ui.button(tr("应用")).clicked() { self.request_batch(BatchOp::Name); }
                             ui.add(egui::TextEdit::singleline(&mut self.batch_projectname).desired_width(f32::INFINITY));
                         });
                     });
 
                     // Row 3: DouyinIDs
+                    // 选中的项目都没有 JS 配置时禁用，避免应用后没有任何效果
+                    let has_js = self.projects.iter().any(|p| p.selected && p.js_config.is_some());
+                    let no_js_hint = tr("选中的项目都没有 JS 配置，DouyinIDs 只会写入 JS 文件");
+                    ui.horizontal(|ui| {
+                        ui.add_sized([label_width, 20.0], egui::Label::new(tr("统一 DouyinIDs:")));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.add_enabled(has_js, egui::Button::new(tr("应用"))).on_disabled_hover_text(no_js_hint).clicked() {
+                                self.request_batch(BatchOp::DouyinIds);
+                            }
+                            egui::ComboBox::from_id_salt("batch_douyin_mode")
+                                .width(80.0)
+                                .selected_text(tr(self.batch_douyin_mode.label()))
+                                .show_ui(ui, |ui| {
+                                    for mode in DouyinIdsMode::ALL {
+                                        ui.selectable_value(&mut self.batch_douyin_mode, mode, tr(mode.label()));
+                                    }
+                                })
+                                .response
+                                .on_hover_text(tr("替换：用输入的 ID 覆盖原有列表；追加：加到每个项目原有列表的末尾；去重追加：跳过项目中已有的 ID"));
+                            invalid_ids_warning(ui, &split_douyin_ids(&self.batch_douyin_ids));
+                            ui.add_enabled(has_js, egui::TextEdit::singleline(&mut self.batch_douyin_ids).desired_width(f32::INFINITY))
+                                .on_disabled_hover_text(no_js_hint);
+                        });
+                    });
+
+                    // Row 4: 移除 DouyinIDs
                     ui.horizontal(|ui| {
-                        ui.add_sized([label_width, 20.0], egui::Label::new("统一 DouyinIDs:"));
+                        ui.add_sized([label_width, 20.0], egui::Label::new(tr("移除 DouyinIDs:")));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            if ui.button("应用").clicked() { self.apply_batch_douyin_ids(); }
-                            ui.add(egui::TextEdit::singleline(&mut self.batch_douyin_ids).desired_width(f32::INFINITY));
+                            if ui.add_enabled(has_js, egui::Button::new(tr("移除"))).on_disabled_hover_text(no_js_hint).clicked() {
+                                self.request_batch(BatchOp::RemoveDouyinIds);
+                            }
+                            ui.add_enabled(has_js, egui::TextEdit::singleline(&mut self.batch_remove_ids)
+                                .hint_text(tr("逗号分隔，从选中项目中删除这些 ID"))
+                                .desired_width(f32::INFINITY))
+                                .on_disabled_hover_text(no_js_hint);
                         });
                     });
+
+                    ui.horizontal(|ui| {
+                        if let Some(progress) = &self.import_progress {
+                            ui.spinner();
+                            ui.label(trf(
+                                "正在匹配 CSV：{} / {} 行",
+                                &[&progress.processed.load(Ordering::Relaxed), &progress.total.load(Ordering::Relaxed)],
+                            ));
+                        } else if ui.button(tr("📥 导入 CSV 映射"))
+                            .on_hover_text(tr("按文件夹名称匹配项目，表头为 folder、appid、projectname、douyinIds；\nfolder 中包含 / 时按路径结尾匹配"))
+                            .clicked()
+                        {
+                            self.start_csv_import();
+                        }
+                    });
+
+                    // 按渠道整体选中，之后的批量修改即作用于整个渠道
+                    let channel_groups = channel::group_by_channel(&self.project_channels);
+                    if !channel_groups.is_empty() {
+                        let mut select = None;
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr("按渠道选中:"));
+                            for (name, indices) in &channel_groups {
+                                if ui.small_button(format!("{} ({})", name, indices.len()))
+                                    .on_hover_text(tr("只选中此渠道下的项目"))
+                                    .clicked()
+                                {
+                                    select = Some(name.clone());
+                                }
+                            }
+                        });
+                        if let Some(name) = select {
+                            self.select_channel(&name);
+                        }
+                    }
+
+                    if ui.add_enabled(self.zip_rx.is_none(), egui::Button::new(tr("📦 打包全部选中")))
+                        .on_hover_text(tr("选择输出目录后逐个打包选中的项目，同名项目的压缩包会自动加上 AppID 区分"))
+                        .on_disabled_hover_text(tr("正在打包，请等待完成"))
+                        .clicked()
+                    {
+                        let mut dialog = FileDialog::new().set_title(tr("选择压缩包输出目录"));
+                        if let Some(dir) = &self.zip_output_dir {
+                            dialog = dialog.set_directory(dir);
+                        }
+                        if let Some(dir) = dialog.pick_folder() {
+                            self.zip_output_dir = Some(dir);
+                            self.build_zip_selected();
+                        }
+                    }
+
+                    if ui.button(tr("📁 文件夹按项目名重命名"))
+                        .on_hover_text(tr("把选中项目所在的文件夹重命名为项目名称，名称冲突时自动加后缀"))
+                        .clicked()
+                    {
+                        self.confirm_rename_folders = true;
+                    }
                     
+                    // 配置模板：保存常用配置，一键应用到选中项目
+                    ui.collapsing(tr("📋 配置模板"), |ui| {
+                        let mut apply_index = None;
+                        let mut remove_index = None;
+                        for (i, profile) in self.profiles.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(&profile.name).strong());
+                                ui.label(egui::RichText::new(trf(
+                                    "AppID: {} | 名称: {} | DouyinIDs: {}",
+                                    &[&profile.appid, &profile.name_pattern, &profile.douyin_ids],
+                                )).small().weak());
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    if ui.small_button("🗑").on_hover_text(tr("删除模板")).clicked() {
+                                        remove_index = Some(i);
+                                    }
+                                    if ui.small_button(tr("应用")).clicked() {
+                                        apply_index = Some(i);
+                                    }
+                                });
+                            });
+                        }
+                        if let Some(i) = apply_index {
+                            self.apply_profile(i);
+                        }
+                        if let Some(i) = remove_index {
+                            self.profiles.remove(i);
+                        }
+
+                        ui.separator();
+                        egui::Grid::new("profile_draft_grid").num_columns(2).show(ui, |ui| {
+                            ui.label(tr("模板名称:"));
+                            ui.text_edit_singleline(&mut self.profile_draft.name);
+                            ui.end_row();
+                            ui.label(tr("AppID:"));
+                            ui.text_edit_singleline(&mut self.profile_draft.appid);
+                            ui.end_row();
+                            ui.label(tr("项目名:"));
+                            ui.text_edit_singleline(&mut self.profile_draft.name_pattern);
+                            ui.end_row();
+                            ui.label("DouyinIDs:");
+                            ui.text_edit_singleline(&mut self.profile_draft.douyin_ids);
+                            ui.end_row();
+                        });
+                        ui.label(egui::RichText::new(tr("留空的字段不会被修改；支持占位符 {folder}（文件夹名）和 {index}（选中序号）")).small().weak());
+                        ui.horizontal(|ui| {
+                            if ui.button(tr("从批量输入填充")).clicked() {
+                                self.profile_draft.appid = self.batch_appid.clone();
+                                self.profile_draft.name_pattern = self.batch_projectname.clone();
+                                self.profile_draft.douyin_ids = self.batch_douyin_ids.clone();
+                            }
+                            let can_save = !self.profile_draft.name.trim().is_empty();
+                            if ui.add_enabled(can_save, egui::Button::new(tr("💾 保存为模板"))).clicked() {
+                                let mut profile = std::mem::take(&mut self.profile_draft);
+                                profile.name = profile.name.trim().to_string();
+                                // 同名模板直接覆盖
+                                match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+                                    Some(existing) => *existing = profile,
+                                    None => self.profiles.push(profile),
+                                }
+                            }
+                        });
+                    });
+
                     ui.add_space(5.0);
                     
                     // 保存按钮，使用醒目的颜色和大小
-                    if ui.add_sized(
-                        [ui.available_width(), 30.0],
-                        egui::Button::new(egui::RichText::new("💾 保存所有更改").heading().color(egui::Color32::WHITE))
-                        .fill(egui::Color32::from_rgb(0, 100, 200))
-                    ).clicked() 
+                    let stale = self.app_id_field_stale();
+                    if ui.add_enabled_ui(!stale, |ui| {
+                        ui.add_sized(
+                            [ui.available_width(), 30.0],
+                            egui::Button::new(egui::RichText::new(tr("💾 保存所有更改")).heading().color(egui::Color32::WHITE))
+                            .fill(egui::Color32::from_rgb(0, 100, 200))
+                        )
+                    }).inner.on_disabled_hover_text(tr("AppID 字段名已修改，请重新扫描后再保存")).clicked()
                     {
-                        self.save_all();
+                        self.request_save(false);
                     }
                 });
             }
@@ -377,140 +2419,502 @@ impl eframe::App for MyApp {
             ui.add_space(10.0);
 
             // --- 列表显示区 ---
-            // 使用 ScrollArea 支持滚动
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                if self.projects.is_empty() {
-                    ui.vertical_centered(|ui| {
-                        ui.add_space(50.0);
-                        ui.label("暂无项目，请选择正确的根目录。");
+            if !self.projects.is_empty() {
+                ui.horizontal(|ui| {
+                    ui.label(tr("筛选:"));
+                    ui.add(egui::TextEdit::singleline(&mut self.project_filter).hint_text(tr("路径、AppID 或项目名")).desired_width(200.0));
+                    if !self.project_filter.is_empty() && ui.small_button("✖").clicked() {
+                        self.project_filter.clear();
+                    }
+                    let missing_js = self.projects.iter().filter(|p| p.js_config.is_none()).count();
+                    let has_js = self.projects.len() - missing_js;
+                    // 再次点击已选中的筛选按钮时恢复显示全部
+                    for (filter, label, hint) in [
+                        (JsFilter::HasJs, format!("{} ({})", tr("仅显示有JS"), has_js), tr("只显示找到了 JS 配置的项目")),
+                        (JsFilter::MissingJs, format!("{} ({})", tr("仅显示缺JS"), missing_js), tr("只显示没有找到 JS 配置的项目，这些项目不受 DouyinIDs 等 JS 字段的批量修改影响")),
+                    ] {
+                        if ui.selectable_label(self.js_filter == filter, label).on_hover_text(hint).clicked() {
+                            self.js_filter = if self.js_filter == filter { JsFilter::All } else { filter };
+                        }
+                    }
+                    if missing_js > 0 {
+                        ui.label(egui::RichText::new(trf("⚠ {} 个项目缺少 JS", &[&missing_js])).color(egui::Color32::from_rgb(220, 140, 0)))
+                            .on_hover_text(tr("这些项目没有找到 index.js / application.js 等 JS 配置，DouyinIDs 和 JS 中的 AppID 无法批量写入"));
+                        if ui.small_button(tr("全选")).on_hover_text(tr("只选中缺少 JS 配置的项目")).clicked() {
+                            for item in &mut self.projects {
+                                item.selected = item.js_config.is_none();
+                            }
+                        }
+                    }
+                    let channel_groups = channel::group_by_channel(&self.project_channels);
+                    if !channel_groups.is_empty() {
+                        egui::ComboBox::from_id_salt("channel_filter")
+                            .selected_text(self.channel_filter.as_deref().unwrap_or(tr("全部渠道")))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut self.channel_filter, None, tr("全部渠道"));
+                                for (name, indices) in channel_groups {
+                                    let label = format!("{} ({})", name, indices.len());
+                                    ui.selectable_value(&mut self.channel_filter, Some(name), label);
+                                }
+                            });
+                    }
+                    ui.toggle_value(&mut self.compact_view, tr("☰ 紧凑列表"))
+                        .on_hover_text(tr("每个项目只显示一行，项目很多时更流畅"));
+                    ui.toggle_value(&mut self.paginate, tr("📄 分页"))
+                        .on_hover_text(trf("卡片每页显示 {} 个项目，选中和批量操作仍作用于所有页", &[&PAGE_SIZE]));
+                });
+            }
+            let filter = self.project_filter.to_lowercase();
+            let visible = self.visible_indices();
+
+            // 分页时只绘制当前页的卡片；需要定位的项目不在当前页时先翻页
+            let page_count = visible.len().div_ceil(PAGE_SIZE).max(1);
+            if self.paginate && !self.compact_view {
+                if let Some(pos) = self.scroll_to_project.and_then(|idx| visible.binary_search(&idx).ok()) {
+                    self.page = pos / PAGE_SIZE;
+                }
+            }
+            self.page = self.page.min(page_count - 1);
+            let shown: &[usize] = if self.paginate {
+                let start = self.page * PAGE_SIZE;
+                &visible[start..(start + PAGE_SIZE).min(visible.len())]
+            } else {
+                &visible
+            };
+
+            if self.projects.len() > LARGE_PROJECT_THRESHOLD
+                && !self.large_list_confirmed
+                && !self.compact_view
+                && !self.paginate
+                && self.js_filter == JsFilter::All
+                && self.channel_filter.is_none()
+                && filter.is_empty()
+            {
+                // 项目过多时，立即模式下逐帧绘制全部卡片会明显卡顿，先让用户选择
+                ui.group(|ui| {
+                    ui.label(egui::RichText::new(trf(
+                        "⚠ 扫描到 {} 个项目，全部以卡片显示可能导致界面卡顿。",
+                        &[&self.projects.len()],
+                    )).color(egui::Color32::from_rgb(220, 140, 0)));
+                    ui.label(tr("可以在上方输入筛选条件，或切换到紧凑列表。"));
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("☰ 切换到紧凑列表")).clicked() {
+                            self.compact_view = true;
+                        }
+                        if ui.button(tr("📄 分页显示")).clicked() {
+                            self.paginate = true;
+                        }
+                        if ui.button(tr("仍然全部显示")).clicked() {
+                            self.large_list_confirmed = true;
+                        }
                     });
-                } else {
-                    for (idx, item) in self.projects.iter_mut().enumerate() {
-                        // 使用 push_id 确保每个组件 ID 唯一
-                        ui.push_id(idx, |ui| {
-                            ui.group(|ui| {
-                                // 项目标题行
-                                ui.horizontal(|ui| {
-                                    ui.checkbox(&mut item.selected, "");
+                });
+            } else if self.compact_view {
+                self.show_compact_list(ui, &visible);
+            } else {
+                if self.paginate {
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(self.page > 0, egui::Button::new(tr("◀ 上一页"))).clicked() {
+                            self.page -= 1;
+                        }
+                        ui.label(trf("第 {} / {} 页（共 {} 个项目）", &[&(self.page + 1), &page_count, &visible.len()]));
+                        if ui.add_enabled(self.page + 1 < page_count, egui::Button::new(tr("下一页 ▶"))).clicked() {
+                            self.page += 1;
+                        }
+                    });
+                }
+                let mut select_click = None;
+                // 本帧编辑结束时从 DouyinIDs 中去掉的重复 ID 数量
+                let mut removed_duplicates = 0;
+                // 使用 ScrollArea 支持滚动
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    if self.projects.is_empty() {
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(50.0);
+                            ui.label(tr("暂无项目，请选择正确的根目录。"));
+                        });
+                    } else {
+                        // 预先计算共享 JS 的提示文本，避免在遍历可变列表时再借用
+                        let shared_js_hints: Vec<Option<String>> = (0..self.projects.len())
+                            .map(|idx| {
+                                let group = self.shared_js_groups.iter().find(|g| g.contains(&idx))?;
+                                let names: Vec<String> = group.iter()
+                                    .filter(|&&other| other != idx)
+                                    .map(|&other| self.projects[other].path.display().to_string())
+                                    .collect();
+                                Some(trf("此项目的 JS 文件同时被以下项目使用，修改会互相影响：\n{}", &[&names.join("\n")]))
+                            })
+                            .collect();
+                        // 按当前（含未保存修改的）AppID 分组，编辑后徽标会立即更新
+                        let duplicate_appids = appid::group_duplicates(
+                            self.projects.iter().enumerate().map(|(idx, p)| (p.config.appid.as_str(), idx)),
+                        );
+                        let duplicate_appid_hints: Vec<Option<String>> = (0..self.projects.len())
+                            .map(|idx| {
+                                let group = duplicate_appids.get(self.projects[idx].config.appid.trim())?;
+                                let names: Vec<String> = group.iter()
+                                    .filter(|&&other| other != idx)
+                                    .map(|&other| self.projects[other].path.display().to_string())
+                                    .collect();
+                                Some(trf("以下项目使用了相同的 AppID：\n{}", &[&names.join("\n")]))
+                            })
+                            .collect();
+
+                        for (idx, item) in self.projects.iter_mut().enumerate() {
+                            if shown.binary_search(&idx).is_err() {
+                                continue;
+                            }
+                            // 使用 push_id 确保每个组件 ID 唯一
+                            let card = ui.push_id(idx, |ui| {
+                                ui.group(|ui| {
+                                    // 项目标题行
+                                    ui.horizontal(|ui| {
+                                        if ui.checkbox(&mut item.selected, "").on_hover_text(tr("按住 Shift 点击可选择一段范围")).clicked() {
+                                            select_click = Some((idx, ui.input(|i| i.modifiers.shift)));
+                                        }
                                     
-                                    // 显示相对路径或文件夹名作为标题
-                                    let display_name = item.path.parent()
-                                        .and_then(|p| p.file_name())
-                                        .map(|s| s.to_string_lossy())
-                                        .unwrap_or_default();
+                                        // 显示相对路径或文件夹名作为标题
+                                        let display_name = item.path.parent()
+                                            .and_then(|p| p.file_name())
+                                            .map(|s| s.to_string_lossy())
+                                            .unwrap_or_default();
                                         
-                                    ui.heading(display_name);
+                                        ui.heading(display_name);
+                                        if let Some(Some(channel)) = self.project_channels.get(idx) {
+                                            ui.label(egui::RichText::new(format!("[{}]", channel)).weak())
+                                                .on_hover_text(tr("按目录约定识别的渠道"));
+                                        }
                                     
-                                    if item.is_modified {
-                                        ui.label(egui::RichText::new("● 待保存").color(egui::Color32::RED));
-                                    }
+                                        if item.is_modified {
+                                            ui.label(egui::RichText::new(tr("● 待保存")).color(egui::Color32::RED));
+                                        }
+
+                                        if item.files_missing {
+                                            ui.label(egui::RichText::new(tr("⚠ 文件已不存在")).color(egui::Color32::RED))
+                                                .on_hover_text(tr("配置文件或 JS 文件已被移动或删除，保存时会跳过此项目，请重新扫描"));
+                                        }
+
+                                        // JS 文件存在但没有识别到 appId 赋值
+                                        if item.original.js_config.as_ref().is_some_and(|js| js.app_id.is_empty()) {
+                                            ui.label(egui::RichText::new(tr("⚠ JS 未识别 AppID")).color(egui::Color32::from_rgb(220, 140, 0)))
+                                                .on_hover_text(tr("JS 文件中没有找到 .appId=\"...\" 赋值，保存时不会写入 JS 的 AppID"));
+                                        }
+
+                                        if let Some(hint) = &shared_js_hints[idx] {
+                                            ui.label(egui::RichText::new(tr("⚠ 共享 JS")).color(egui::Color32::from_rgb(220, 140, 0)))
+                                                .on_hover_text(hint);
+                                        }
+
+                                        if let Some(hint) = &duplicate_appid_hints[idx] {
+                                            ui.label(egui::RichText::new(tr("⚠ AppID 重复")).color(egui::Color32::RED))
+                                                .on_hover_text(hint);
+                                        }
+
+                                        // 宽度符合但比例不符合商店要求的预览图
+                                        if self.aspect_check {
+                                            let bad: Vec<String> = item.image_paths.iter()
+                                                .zip(&item.image_sizes)
+                                                .filter_map(|(path, size)| {
+                                                    let (w, h) = (*size)?;
+                                                    (!matches_aspect_ratio((w, h), &self.aspect_ratios)).then(|| format!(
+                                                        "{} ({}×{})",
+                                                        path.file_name().unwrap_or_default().to_string_lossy(), w, h
+                                                    ))
+                                                })
+                                                .collect();
+                                            if !bad.is_empty() {
+                                                ui.label(egui::RichText::new(tr("⚠ 预览图比例")).color(egui::Color32::from_rgb(220, 140, 0)))
+                                                    .on_hover_text(trf(
+                                                        "以下预览图不符合允许的宽高比（{}）：\n{}",
+                                                        &[&self.aspect_ratios_input, &bad.join("\n")],
+                                                    ));
+                                            }
+                                        }
+
+                                        // 仅作提示：配置可能是复制项目时误带过来的
+                                        if let Some(Some(hint)) = self.mismatch_hints.get(idx) {
+                                            ui.label(egui::RichText::new(tr("ℹ 配置与文件夹不符")).color(egui::Color32::from_rgb(90, 140, 220)))
+                                                .on_hover_text(hint);
+                                        }
                                     
-                                    ui.add_space(5.0);
-                                    if ui.button("📦 打包").clicked() {
-                                        zip_index = Some(idx);
-                                    }
-                                });
+                                        ui.add_space(5.0);
+                                        if ui.button(tr("📦 打包")).clicked() {
+                                            zip_index = Some(idx);
+                                        }
+                                        if ui.button(tr("🧩 IDE")).on_hover_text(tr("在抖音开发者工具中打开此项目")).clicked() {
+                                            ide_index = Some(idx);
+                                        }
+                                        if item.is_modified && ui.button(tr("↩ 还原")).on_hover_text(tr("丢弃此项目未保存的修改")).clicked() {
+                                            item.revert();
+                                        }
+                                    });
                                 
-                                // 基础信息编辑
-                                ui.horizontal(|ui| {
-                                    ui.label("AppID:");
-                                    if ui.text_edit_singleline(&mut item.config.appid).changed() {
-                                        item.is_modified = true;
-                                    }
+                                    // 基础信息编辑
+                                    ui.horizontal(|ui| {
+                                        ui.label(tr("AppID:"));
+                                        let appid_changed = item.config.appid != item.original.config.appid;
+                                        if highlighted_text_edit(ui, &mut item.config.appid, appid_changed).changed() {
+                                            item.refresh_modified();
+                                        }
+                                        if let Err(reason) = appid::validate_appid(&item.config.appid) {
+                                            ui.label(egui::RichText::new("⚠").color(egui::Color32::RED)).on_hover_text(reason);
+                                        }
                                     
-                                    ui.add_space(20.0);
+                                        ui.add_space(20.0);
                                     
-                                    ui.label("Name:");
-                                    if ui.text_edit_singleline(&mut item.config.projectname).changed() {
-                                        item.is_modified = true;
-                                    }
-                                });
-
-                                // JS 配置编辑（如果存在）
-                                if let Some(js_config) = &mut item.js_config {
-                                    ui.separator();
-                                    ui.horizontal(|ui| {
-                                        ui.label(egui::RichText::new("JS Config:").small().strong());
-                                        ui.label(egui::RichText::new("AppID").small());
-                                        if ui.text_edit_singleline(&mut js_config.app_id).changed() {
-                                            item.is_modified = true;
+                                        ui.label(tr("项目名:"));
+                                        let name_changed = item.config.projectname != item.original.config.projectname;
+                                        if highlighted_text_edit(ui, &mut item.config.projectname, name_changed).changed() {
+                                            item.refresh_modified();
                                         }
-                                        ui.label(egui::RichText::new("Douyin IDs").small());
-                                        if ui.text_edit_singleline(&mut js_config.douyin_ids_str).changed() {
-                                            // 自动移除空格和换行
-                                            js_config.douyin_ids_str = js_config.douyin_ids_str.replace(|c: char| c.is_whitespace(), "");
-                                            item.is_modified = true;
+
+                                        ui.add_space(20.0);
+
+                                        // 备注不属于项目配置，编辑结束后直接写入备注文件
+                                        ui.label(tr("备注:"));
+                                        let note_edit = ui.add(
+                                            egui::TextEdit::singleline(&mut item.note)
+                                                .hint_text(tr("如 待审核 / 已提交"))
+                                                .desired_width(120.0),
+                                        );
+                                        if note_edit.lost_focus() {
+                                            note_edited = true;
                                         }
                                     });
-                                }
+
+                                    // JS 配置编辑（如果存在）
+                                    let mut js_edited = false;
+                                    if let Some(js_config) = &mut item.js_config {
+                                        let original_js = item.original.js_config.as_ref();
+                                        ui.separator();
+                                        ui.horizontal(|ui| {
+                                            ui.label(egui::RichText::new(tr("JS 配置:")).small().strong());
+                                            // 非 UTF-8 的文件标出编码，保存时按原编码写回
+                                            if js_config.encoding != TextEncoding::Utf8 {
+                                                ui.label(egui::RichText::new(js_config.encoding.label()).small()
+                                                    .color(egui::Color32::from_rgb(220, 140, 0)))
+                                                    .on_hover_text(tr("该 JS 文件不是 UTF-8 编码，保存时按原编码写回；无法用该编码表示的字符会导致保存失败"));
+                                            }
+                                            ui.label(egui::RichText::new("AppID").small());
+                                            let js_appid_changed = original_js.is_none_or(|o| o.app_id != js_config.app_id);
+                                            if highlighted_text_edit(ui, &mut js_config.app_id, js_appid_changed).changed() {
+                                                js_edited = true;
+                                            }
+                                            ui.label(egui::RichText::new(tr("DouyinIDs")).small());
+                                            let ids_changed = original_js.is_none_or(|o| o.douyin_ids != js_config.douyin_ids);
+                                            let ids_edit = highlighted_text_edit(ui, &mut js_config.douyin_ids_str, ids_changed);
+                                            if ids_edit.changed() {
+                                                // 输入时只移除空格和换行，保留末尾逗号以便继续输入
+                                                js_config.douyin_ids_str = js_config.douyin_ids_str.replace(|c: char| c.is_whitespace(), "");
+                                                js_config.sync_ids_from_str();
+                                                js_edited = true;
+                                            }
+                                            if ids_edit.lost_focus() {
+                                                // 编辑结束后与批量输入一样规范化，去掉空元素、多余逗号和重复的 ID
+                                                // 输入过程中不去重，以免输入 "1,12" 时中间状态 "1,1" 被合并
+                                                js_config.douyin_ids_str = normalize_douyin_ids(&js_config.douyin_ids_str);
+                                                js_config.sync_ids_from_str();
+                                                let removed = dedup_ids(&mut js_config.douyin_ids);
+                                                if removed > 0 {
+                                                    js_config.sync_str_from_ids();
+                                                    removed_duplicates += removed;
+                                                    js_edited = true;
+                                                }
+                                            }
+                                            invalid_ids_warning(ui, &js_config.douyin_ids);
+                                        });
+
+                                        // 逐条编辑 DouyinIDs，每个 ID 独占一行，避免手动维护逗号
+                                        // 使用固定的 id_salt，避免条目数量变化导致折叠状态丢失
+                                        egui::CollapsingHeader::new(
+                                            egui::RichText::new(trf("逐条编辑 DouyinIDs ({})", &[&js_config.douyin_ids.len()])).small()
+                                        )
+                                        .id_salt("douyin_ids_list")
+                                        .show(ui, |ui| {
+                                            // 列表是数据源，编辑字符串仅作为派生缓存同步更新
+                                            if id_list_editor(ui, &mut js_config.douyin_ids) {
+                                                js_config.sync_str_from_ids();
+                                                js_edited = true;
+                                            }
+                                        });
+
+                                        // 同一 JS 文件中的其他 ID 数组（如 rewardIds、bannerIds）
+                                        for array in &mut js_config.extra_id_arrays {
+                                            egui::CollapsingHeader::new(
+                                                egui::RichText::new(trf("逐条编辑 {} ({})", &[&array.name, &array.ids.len()])).small()
+                                            )
+                                            .id_salt(("id_array", &array.name))
+                                            .show(ui, |ui| {
+                                                if id_list_editor(ui, &mut array.ids) {
+                                                    js_edited = true;
+                                                }
+                                            });
+                                        }
+
+                                        // 匹配位置预览：确认正则匹配到了压缩 JS 中正确的位置
+                                        if !js_config.matches.is_empty() {
+                                            egui::CollapsingHeader::new(
+                                                egui::RichText::new(tr("🔍 JS 匹配位置（扫描时）")).small()
+                                            )
+                                            .id_salt("js_matches")
+                                            .show(ui, |ui| {
+                                                for m in &js_config.matches {
+                                                    ui.label(egui::RichText::new(format!(
+                                                        "{} @ {}..{}", m.field, m.span.start, m.span.end
+                                                    )).small().strong());
+                                                    ui.horizontal_wrapped(|ui| {
+                                                        ui.spacing_mut().item_spacing.x = 0.0;
+                                                        ui.label(egui::RichText::new(&m.before).monospace().small().weak());
+                                                        ui.label(egui::RichText::new(&m.matched).monospace().small()
+                                                            .color(egui::Color32::from_rgb(255, 140, 0)));
+                                                        ui.label(egui::RichText::new(&m.after).monospace().small().weak());
+                                                    });
+                                                }
+                                            });
+                                        }
+                                    }
+                                    if js_edited {
+                                        item.refresh_modified();
+                                    }
                                 
-                                // 图片预览区
-                                if !item.image_paths.is_empty() {
+                                    // 图片预览区：延迟查找模式下第一次展开时才查找预览图
                                     ui.separator();
-                                    ui.label(egui::RichText::new(format!("预览图 (共{}张):", item.image_paths.len())).small().strong());
+                                    let header = if item.images_probed {
+                                        trf("预览图 (共{}张)", &[&item.image_paths.len()])
+                                    } else {
+                                        tr("预览图（展开时查找）").to_string()
+                                    };
+                                    egui::CollapsingHeader::new(egui::RichText::new(header).small().strong())
+                                        .id_salt("preview_images")
+                                        .default_open(item.images_probed && !item.image_paths.is_empty())
+                                        .show(ui, |ui| {
+                                            if !item.images_probed {
+                                                probe_indices.push(idx);
+                                                ui.horizontal(|ui| {
+                                                    ui.spinner();
+                                                    ui.label(egui::RichText::new(tr("正在查找预览图...")).small().weak());
+                                                });
+                                                return;
+                                            }
+                                            if item.image_paths.is_empty() {
+                                                ui.label(egui::RichText::new(tr("未找到预览图")).small().weak());
+                                                return;
+                                            }
                                     
-                                    // 显示图片路径列表（方便调试）
-                                    ui.collapsing("查看图片路径", |ui| {
-                                        for img_path in &item.image_paths {
-                                            ui.label(egui::RichText::new(img_path.to_string_lossy()).monospace().small());
-                                        }
-                                    });
+                                            // 显示图片路径列表（方便调试）
+                                            ui.collapsing(tr("查看图片路径"), |ui| {
+                                                for img_path in &item.image_paths {
+                                                    ui.label(egui::RichText::new(img_path.to_string_lossy()).monospace().small());
+                                                }
+                                            });
 
-                                    // 使用 columns 布局并排显示所有图片
-                                    ui.columns(item.image_paths.len(), |columns| {
-                                        for (img_idx, ui) in columns.iter_mut().enumerate() {
-                                            let img_path = &item.image_paths[img_idx];
+                                            // 使用 columns 布局并排显示所有图片
+                                            ui.columns(item.image_paths.len(), |columns| {
+                                                for (img_idx, ui) in columns.iter_mut().enumerate() {
+                                                    let img_path = &item.image_paths[img_idx];
                                             
-                                            ui.group(|ui| {
-                                                ui.vertical_centered(|ui| {
-                                                    ui.label(egui::RichText::new(format!("Image #{}:", img_idx + 1)).small().strong());
+                                                    ui.group(|ui| {
+                                                        ui.vertical_centered(|ui| {
+                                                            ui.label(egui::RichText::new(trf("图片 #{}:", &[&(img_idx + 1)])).small().strong());
                                                     
-                                                    // 检查缓存，如果未加载则尝试加载
-                                                    if !item.texture_cache.contains_key(img_path) {
-                                                        // 尝试加载图片文件
-                                                        let texture = if let Ok(img) = image::open(img_path) {
-                                                            let size = [img.width() as _, img.height() as _];
-                                                            let image_buffer = img.to_rgba8();
-                                                            let pixels = image_buffer.as_flat_samples();
-                                                            let color_image = egui::ColorImage::from_rgba_unmultiplied(
-                                                                size,
-                                                                pixels.as_slice(),
-                                                            );
-                                                            // 加载到 GPU 纹理
-                                                            // 使用特定的名称 (idx, img_idx) 确保唯一性
-                                                            Some(ui.ctx().load_texture(
-                                                                format!("p{}_img{}", idx, img_idx),
-                                                                color_image,
-                                                                egui::TextureOptions::default()
-                                                            ))
-                                                        } else {
-                                                            None
-                                                        };
-                                                        item.texture_cache.insert(img_path.clone(), texture);
-                                                    }
-
-                                                    // 显示图片或错误信息
-                                                    if let Some(Some(texture)) = item.texture_cache.get(img_path) {
-                                                        // max_width 限制图片宽度适应列宽
-                                                        ui.add(egui::Image::new(texture).max_width(ui.available_width()));
-                                                    } else {
-                                                        ui.colored_label(egui::Color32::RED, "❌ 加载失败");
-                                                        ui.label(egui::RichText::new(img_path.to_string_lossy()).small());
-                                                    }
-                                                });
+                                                            // 检查缓存，如果未加载、或上次失败已超过重试间隔则尝试加载
+                                                            let needs_load = match item.texture_cache.get(img_path).map(|cached| &cached.texture) {
+                                                                None => true,
+                                                                Some(Ok(_)) => false,
+                                                                Some(Err(failed_at)) => {
+                                                                    let elapsed = failed_at.elapsed();
+                                                                    if elapsed < TEXTURE_RETRY_DELAY {
+                                                                        ui.ctx().request_repaint_after(TEXTURE_RETRY_DELAY - elapsed);
+                                                                    }
+                                                                    elapsed >= TEXTURE_RETRY_DELAY
+                                                                }
+                                                            };
+                                                            if needs_load {
+                                                                // 尝试加载图片文件
+                                                                let texture = if let Ok(img) = image::open(img_path) {
+                                                                    // 按列宽的物理像素缩小，高 DPI 屏幕上也不会被拉伸变糊
+                                                                    let target = (ui.available_width() * ui.ctx().pixels_per_point()).ceil() as u32;
+                                                                    let img = thumbnail(img, target.clamp(1, THUMBNAIL_MAX_WIDTH));
+                                                                    let size = [img.width() as _, img.height() as _];
+                                                                    let image_buffer = img.to_rgba8();
+                                                                    let pixels = image_buffer.as_flat_samples();
+                                                                    let color_image = egui::ColorImage::from_rgba_unmultiplied(
+                                                                        size,
+                                                                        pixels.as_slice(),
+                                                                    );
+                                                                    // 加载到 GPU 纹理
+                                                                    // 使用特定的名称 (idx, img_idx) 确保唯一性
+                                                                    Ok(ui.ctx().load_texture(
+                                                                        format!("p{}_img{}", idx, img_idx),
+                                                                        color_image,
+                                                                        egui::TextureOptions::default()
+                                                                    ))
+                                                                } else {
+                                                                    Err(Instant::now())
+                                                                };
+                                                                item.texture_cache.insert(
+                                                                    img_path.clone(),
+                                                                    CachedTexture { texture, last_shown: Instant::now() },
+                                                                );
+                                                            }
+
+                                                            // 显示图片或错误信息，并记录显示时间供缓存淘汰使用
+                                                            let shown = item.texture_cache.get_mut(img_path).map(|cached| {
+                                                                cached.last_shown = Instant::now();
+                                                                &cached.texture
+                                                            });
+                                                            if let Some(Ok(texture)) = shown {
+                                                                // 同时限制宽度和高度，按原始比例缩放到列宽和最大高度之内
+                                                                // 加载后列宽变大时不放大纹理，避免缩略图被拉伸变糊
+                                                                let texture_width = texture.size()[0] as f32 / ui.ctx().pixels_per_point();
+                                                                let image = ui.add(
+                                                                    egui::Image::new(texture)
+                                                                        .max_width(ui.available_width().min(texture_width))
+                                                                        .max_height(PREVIEW_MAX_HEIGHT)
+                                                                        .sense(egui::Sense::click())
+                                                                ).on_hover_cursor(egui::CursorIcon::ZoomIn).on_hover_text(tr("点击查看原图"));
+                                                                if image.clicked() {
+                                                                    self.image_viewer.open(img_path.clone());
+                                                                }
+                                                                // 显示原图的尺寸（纹理是缩小后的缩略图）
+                                                                let [w, h] = match item.image_sizes.get(img_idx) {
+                                                                    Some(Some((w, h))) => [*w as usize, *h as usize],
+                                                                    _ => texture.size(),
+                                                                };
+                                                                ui.label(egui::RichText::new(format!("{}×{}", w, h)).small().weak());
+                                                            } else {
+                                                                ui.colored_label(egui::Color32::RED, tr("❌ 加载失败"));
+                                                                ui.label(egui::RichText::new(img_path.to_string_lossy()).small());
+                                                                if ui.small_button(tr("🔄 重试加载")).clicked() {
+                                                                    item.texture_cache.remove(img_path);
+                                                                }
+                                                            }
+                                                        });
+                                                    });
+                                                }
                                             });
-                                        }
-                                    });
-                                }
+                                        });
                                 
-                                // 显示配置文件路径（弱化显示）
-                                ui.label(egui::RichText::new(item.path.to_string_lossy()).weak().small());
+                                    // 显示配置文件路径（弱化显示）
+                                    ui.label(egui::RichText::new(item.path.to_string_lossy()).weak().small());
+                                });
                             });
-                        });
-                        ui.add_space(4.0);
+                            // 目录树点击后滚动到对应项目卡片
+                            if self.scroll_to_project == Some(idx) {
+                                card.response.scroll_to_me(Some(egui::Align::TOP));
+                                self.scroll_to_project = None;
+                            }
+                            ui.add_space(4.0);
+                        }
                     }
+                });
+                if let Some((idx, shift)) = select_click {
+                    self.on_select_clicked(idx, shift, &visible);
                 }
-            });
+                if removed_duplicates > 0 {
+                    self.status_msg = trf("已去掉 {} 个重复的 DouyinID", &[&removed_duplicates]);
+                }
+            }
 
             // --- 底部状态栏 ---
             ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
@@ -519,8 +2923,468 @@ impl eframe::App for MyApp {
             });
         });
 
+        // 批量修改的影响报告，确认后才真正修改
+        if let Some((op, impacts)) = &self.pending_impact {
+            let op = *op;
+            let mut decided = None;
+            egui::Window::new(tr("批量修改影响预览"))
+                .collapsible(false)
+                .resizable(true)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(trf("保存后将写入以下 {} 个文件：", &[&impacts.len()]));
+                    egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                        for file in impacts {
+                            ui.label(egui::RichText::new(file.path.display().to_string()).monospace().small());
+                            for (field, old, new) in &file.changes {
+                                ui.horizontal(|ui| {
+                                    ui.add_space(16.0);
+                                    ui.label(format!("{}:", field));
+                                    ui.label(egui::RichText::new(old).monospace().weak());
+                                    ui.label("→");
+                                    ui.label(egui::RichText::new(new).monospace().strong());
+                                });
+                            }
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("确认修改")).clicked() {
+                            decided = Some(true);
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            decided = Some(false);
+                        }
+                    });
+                });
+            if let Some(confirmed) = decided {
+                self.pending_impact = None;
+                if confirmed {
+                    self.run_batch(op);
+                }
+            }
+        }
+
+        // 批量操作确认对话框
+        if let Some(op) = self.pending_batch {
+            let selected = self.projects.iter().filter(|p| p.selected).count();
+            let action = match op {
+                BatchOp::AppId => tr("AppID (含JS) 修改为"),
+                BatchOp::Name => tr("项目名称修改为"),
+                BatchOp::DouyinIds => tr("DouyinIDs (仅JS) 修改为"),
+                BatchOp::RemoveDouyinIds => tr("DouyinIDs (仅JS) 中移除"),
+            };
+            egui::Window::new(tr("确认批量修改"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(trf("将把 {} 个选中项目的 {}：", &[&selected, &action]));
+                    ui.label(egui::RichText::new(self.batch_value(op)).monospace().strong());
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("确认修改")).clicked() {
+                            self.run_batch(op);
+                            self.pending_batch = None;
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            self.pending_batch = None;
+                        }
+                    });
+                });
+        }
+
+        // "全部还原"确认对话框
+        if self.confirm_revert_all {
+            let modified = self.projects.iter().filter(|p| p.is_modified).count();
+            egui::Window::new(tr("确认还原"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(trf("将丢弃 {} 个项目的所有未保存修改，是否继续？", &[&modified]));
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("确认还原")).clicked() {
+                            self.revert_all();
+                            self.confirm_revert_all = false;
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            self.confirm_revert_all = false;
+                        }
+                    });
+                });
+        }
+
+        // 等待打包完成后退出的提示
+        if self.close_after_zip {
+            egui::Window::new(tr("正在打包"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(tr("正在写入压缩包，打包完成后将自动退出。"));
+                    if ui.button(tr("取消退出")).clicked() {
+                        self.close_after_zip = false;
+                    }
+                });
+        }
+
+        // 退出前保存确认对话框
+        if self.confirm_close {
+            let modified = self.projects.iter().filter(|p| p.is_modified).count();
+            let mut close = false;
+            egui::Window::new(tr("确认退出"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(trf("有 {} 个项目存在未保存的更改。", &[&modified]));
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(!self.app_id_field_stale(), egui::Button::new(tr("💾 保存后退出")))
+                            .on_disabled_hover_text(tr("AppID 字段名已修改，请重新扫描后再保存"))
+                            .clicked()
+                        {
+                            self.confirm_close = false;
+                            // 与普通保存走同一流程（格式和备份确认），保存成功后再退出
+                            self.request_save(true);
+                        }
+                        if ui.button(tr("直接退出")).clicked() {
+                            self.confirm_close = false;
+                            close = true;
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            self.confirm_close = false;
+                        }
+                    });
+                });
+            if close {
+                self.close_confirmed = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        }
+
+        // 已有较新备份时的确认对话框，避免覆盖上一次留下的备份
+        if !self.confirm_backup_overwrite.is_empty() {
+            egui::Window::new(tr("已有较新的备份"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(trf(
+                        "{} 个文件的 .bak 备份比文件本身更新，覆盖后将无法再恢复这些备份：",
+                        &[&self.confirm_backup_overwrite.len()],
+                    ));
+                    egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                        for file in &self.confirm_backup_overwrite {
+                            ui.label(format!("  - {}", file.display()));
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("覆盖备份并保存")).clicked() {
+                            let files = std::mem::take(&mut self.confirm_backup_overwrite);
+                            self.backups.allow_overwrite(&files);
+                            self.start_busy(BusyOp::Save);
+                        }
+                        if ui.button(tr("保留旧备份并保存")).clicked() {
+                            self.confirm_backup_overwrite.clear();
+                            self.start_busy(BusyOp::Save);
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            self.confirm_backup_overwrite.clear();
+                            self.close_after_save = false;
+                        }
+                    });
+                });
+        }
+
+        // AppID 或 DouyinIDs 格式不正确时的确认对话框
+        if !self.confirm_invalid_entries.is_empty() {
+            egui::Window::new(tr("配置格式不正确"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(trf(
+                        "待保存的项目中有 {} 处格式不正确，可能导致游戏无法上传或运行：",
+                        &[&self.confirm_invalid_entries.len()],
+                    ));
+                    egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                        for (path, problem) in &self.confirm_invalid_entries {
+                            ui.label(format!("  - {}  {}", path.display(), problem));
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("仍然保存")).clicked() {
+                            self.confirm_invalid_entries.clear();
+                            self.confirm_backups_then_save();
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            self.confirm_invalid_entries.clear();
+                            self.close_after_save = false;
+                        }
+                    });
+                });
+        }
+
+        // 打包输出路径已存在时的覆盖确认对话框
+        if let Some((index, path)) = self.confirm_zip_overwrite.clone() {
+            egui::Window::new(tr("文件已存在"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(trf("{} 已存在，是否覆盖？", &[&path.display()]));
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("覆盖")).clicked() {
+                            self.confirm_zip_overwrite = None;
+                            self.build_zip(index, path);
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            self.confirm_zip_overwrite = None;
+                        }
+                    });
+                });
+        }
+
+        // 批量打包的输出目录中已有同名压缩包时的覆盖确认对话框
+        if let Some((_, existing)) = &self.confirm_batch_zip_overwrite {
+            let mut decided = None;
+            egui::Window::new(tr("文件已存在"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(trf("输出目录中已有以下 {} 个压缩包，是否覆盖？", &[&existing.len()]));
+                    egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                        for path in existing {
+                            ui.label(format!("  - {}", path.display()));
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("覆盖")).clicked() {
+                            decided = Some(true);
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            decided = Some(false);
+                        }
+                    });
+                });
+            if let Some(overwrite) = decided {
+                if let Some((jobs, _)) = self.confirm_batch_zip_overwrite.take() {
+                    if overwrite {
+                        self.start_zip(jobs, true);
+                    }
+                }
+            }
+        }
+
+        // "恢复备份"确认对话框
+        if self.confirm_restore_backups {
+            egui::Window::new(tr("恢复备份"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(tr("将用 .bak 备份覆盖所有项目的配置文件和 JS 文件并重新扫描，当前内容和未保存的修改都会丢失，是否继续？"));
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("确认恢复")).clicked() {
+                            self.confirm_restore_backups = false;
+                            self.restore_backups();
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            self.confirm_restore_backups = false;
+                        }
+                    });
+                });
+        }
+
+        // "重命名文件夹"确认对话框
+        if self.confirm_rename_folders {
+            let selected = self.projects.iter().filter(|p| p.selected).count();
+            egui::Window::new(tr("确认重命名文件夹"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(trf("将把 {} 个选中项目所在的文件夹重命名为项目名称。", &[&selected]));
+                    ui.label(egui::RichText::new(tr("此操作会移动磁盘上的目录，无法通过\"还原\"撤销。")).color(egui::Color32::from_rgb(220, 140, 0)));
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("确认重命名")).clicked() {
+                            self.rename_selected_folders();
+                            self.confirm_rename_folders = false;
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            self.confirm_rename_folders = false;
+                        }
+                    });
+                });
+        }
+
+        // CSV 导入结果对话框
+        if let Some(report) = &mut self.import_report {
+            let (matched, unmatched, ambiguous) = report.counts();
+            let mut open = true;
+            let mut resolved = None;
+            egui::Window::new(tr("CSV 导入结果"))
+                .collapsible(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(trf("已匹配并应用 {} 行，未匹配 {} 行，有歧义 {} 行。", &[&matched, &unmatched, &ambiguous]));
+                    egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                        for (row_idx, (row, m)) in report.rows.iter().enumerate() {
+                            match m {
+                                RowMatch::Matched(_) => {}
+                                RowMatch::Unmatched => {
+                                    ui.label(egui::RichText::new(trf("第 {} 行「{}」：未找到项目", &[&row.line, &row.folder]))
+                                        .color(egui::Color32::RED));
+                                }
+                                RowMatch::Ambiguous(candidates) => {
+                                    ui.label(egui::RichText::new(trf("第 {} 行「{}」：多个项目同名，请选择：", &[&row.line, &row.folder]))
+                                        .color(egui::Color32::from_rgb(220, 140, 0)));
+                                    for &idx in candidates {
+                                        let path = self.projects[idx].path.parent().unwrap_or(&self.projects[idx].path);
+                                        if ui.button(path.display().to_string()).clicked() {
+                                            resolved = Some((row_idx, idx));
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    });
+                    if ui.button(tr("关闭")).clicked() {
+                        open = false;
+                    }
+                });
+            if let Some((row_idx, idx)) = resolved {
+                let row = &mut report.rows[row_idx];
+                let step = UndoStep::capture(&self.projects, [idx], tr("CSV 导入"));
+                import::apply_row(&mut self.projects[idx], &row.0);
+                self.undo.record(step, &self.projects);
+                row.1 = RowMatch::Matched(idx);
+            }
+            if !open {
+                self.import_report = None;
+            }
+        }
+
+        // 重复 AppID 提示
+        if !self.duplicate_appids.is_empty() {
+            egui::Window::new(tr("AppID 重复"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(tr("以下 AppID 被多个项目使用，上传时可能互相覆盖："));
+                    for (id, count) in &self.duplicate_appids {
+                        ui.label(egui::RichText::new(format!("{}  ×{}", id, count)).monospace().strong());
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("改为自动递增")).clicked() {
+                            self.batch_appid_increment = true;
+                            self.duplicate_appids.clear();
+                            self.request_batch(BatchOp::AppId);
+                        }
+                        if ui.button(tr("保持不变")).clicked() {
+                            self.duplicate_appids.clear();
+                        }
+                    });
+                });
+        }
+
+        // 批量打包结果，逐项列出最终的输出路径
+        if !self.zip_report.is_empty() {
+            let mut close = false;
+            egui::Window::new(tr("打包结果"))
+                .collapsible(false)
+                .resizable(true)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                        for zip in &self.zip_report {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(&zip.name).strong());
+                                match &zip.result {
+                                    Ok(path) => {
+                                        if ui.link(path.display().to_string()).on_hover_text(tr("打开所在文件夹")).clicked() {
+                                            if let Some(parent) = path.parent() {
+                                                let _ = open::that(parent);
+                                            }
+                                        }
+                                        if zip.renamed {
+                                            ui.label(egui::RichText::new(tr("与其他项目同名，已改名")).color(egui::Color32::from_rgb(220, 140, 0)));
+                                        }
+                                    }
+                                    Err(e) => {
+                                        ui.colored_label(egui::Color32::RED, trf("失败: {}", &[&e]));
+                                    }
+                                }
+                            });
+                        }
+                    });
+                    if ui.button(tr("关闭")).clicked() {
+                        close = true;
+                    }
+                });
+            if close {
+                self.zip_report.clear();
+            }
+        }
+
+        // 重新扫描后检测到的外部修改
+        if !self.disk_changes.is_empty() {
+            let mut close = false;
+            egui::Window::new(tr("磁盘上的变更"))
+                .collapsible(false)
+                .resizable(true)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(tr("与上一次扫描相比，以下项目的配置已在外部被修改："));
+                    egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                        for (idx, fields) in &self.disk_changes {
+                            let Some(item) = self.projects.get(*idx) else { continue };
+                            ui.horizontal(|ui| {
+                                let dir = item.path.parent().unwrap_or(&item.path);
+                                if ui.link(dir.display().to_string()).clicked() {
+                                    self.scroll_to_project = Some(*idx);
+                                }
+                                ui.label(egui::RichText::new(fields.iter().map(|field| tr(field)).collect::<Vec<_>>().join(tr("、"))).weak());
+                            });
+                        }
+                    });
+                    if ui.button(tr("知道了")).clicked() {
+                        close = true;
+                    }
+                });
+            if close {
+                self.disk_changes.clear();
+            }
+        }
+
+        if note_edited {
+            self.save_notes();
+        }
         if let Some(idx) = zip_index {
-            self.build_zip(idx);
+            self.request_zip(idx);
+        }
+        if let Some(idx) = ide_index {
+            self.open_in_ide(idx);
+        }
+        for idx in probe_indices {
+            self.request_probe(idx);
+        }
+
+        // 忙碌遮罩：有等待执行的耗时操作，或后台 CSV 导入进行中
+        let busy_text = match (self.busy_op, &self.import_progress) {
+            (Some(op), _) => Some(tr(op.label()).to_string()),
+            (None, Some(progress)) => Some(trf(
+                "正在匹配 CSV：{} / {} 行",
+                &[&progress.processed.load(Ordering::Relaxed), &progress.total.load(Ordering::Relaxed)],
+            )),
+            (None, None) => None,
+        };
+        if let Some(text) = busy_text {
+            show_busy_overlay(ctx, &text);
         }
+        self.image_viewer.show(ctx);
+        self.evict_textures(frame_start);
+        self.run_busy_op(ctx);
     }
 }
@@ -0,0 +1,88 @@
+use crate::model::ProjectItem;
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+
+/// 按目录约定取出项目所属的渠道
+///
+/// 渠道为项目目录相对于根目录的第 `depth` 级文件夹（从 1 开始），
+/// 例如 `<root>/<渠道>/<游戏>/project.config.json` 中 `depth` 为 1。
+/// `depth` 为 0（未启用）、项目不在根目录下或层级不足时返回 None；
+/// 渠道文件夹本身就是项目目录时也返回 None，避免把游戏名误认为渠道。
+pub fn channel_of(root: &Path, item: &ProjectItem, depth: usize) -> Option<String> {
+    if depth == 0 {
+        return None;
+    }
+    let project_dir = item.path.parent()?;
+    let components: Vec<_> = project_dir
+        .strip_prefix(root)
+        .ok()?
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    if components.len() <= depth {
+        return None;
+    }
+    components.into_iter().nth(depth - 1)
+}
+
+/// 计算每个项目的渠道，下标与项目列表一致
+pub fn project_channels(root: &Path, projects: &[ProjectItem], depth: usize) -> Vec<Option<String>> {
+    projects.iter().map(|item| channel_of(root, item, depth)).collect()
+}
+
+/// 按渠道名称分组
+///
+/// # Returns
+/// * `Vec<(String, Vec<usize>)>` - 按渠道名称排序的 (渠道, 项目下标) 列表，没有渠道的项目不在其中
+pub fn group_by_channel(channels: &[Option<String>]) -> Vec<(String, Vec<usize>)> {
+    let mut groups: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (idx, channel) in channels.iter().enumerate() {
+        if let Some(channel) = channel {
+            groups.entry(channel).or_default().push(idx);
+        }
+    }
+    groups.into_iter().map(|(name, indices)| (name.to_string(), indices)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ProjectConfig;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_channel_of() {
+        let root = Path::new("root");
+        let item = |dir: &str| {
+            let config: ProjectConfig = serde_json::from_str("{}").unwrap();
+            ProjectItem::new(PathBuf::from(dir).join("project.config.json"), config, None, None, Vec::new())
+        };
+        let projects = vec![
+            item("root/taptap/game_a"),
+            item("root/taptap/game_b/build"),
+            item("root/douyin/game_c"),
+            // 直接放在根目录下的项目没有渠道
+            item("root/game_d"),
+            item("other/x/game_e"),
+        ];
+
+        let channels = project_channels(root, &projects, 1);
+        assert_eq!(channels[0].as_deref(), Some("taptap"));
+        assert_eq!(channels[1].as_deref(), Some("taptap"));
+        assert_eq!(channels[2].as_deref(), Some("douyin"));
+        assert_eq!(channels[3], None);
+        assert_eq!(channels[4], None);
+
+        // 第 2 级目录
+        assert_eq!(channel_of(root, &projects[1], 2).as_deref(), Some("game_b"));
+        assert_eq!(channel_of(root, &projects[0], 2), None);
+        // 未启用
+        assert_eq!(channel_of(root, &projects[0], 0), None);
+
+        let groups = group_by_channel(&channels);
+        assert_eq!(groups, vec![("douyin".to_string(), vec![2]), ("taptap".to_string(), vec![0, 1])]);
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+/// 抖音开发者工具的启动配置（持久化）
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IdeLauncher {
+    /// 开发者工具（或其命令行工具）的可执行文件路径
+    pub path: String,
+    /// 启动参数，以空白分隔，`{project}` 会被替换为项目目录
+    pub args: String,
+}
+
+impl Default for IdeLauncher {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            args: "{project}".to_string(),
+        }
+    }
+}
+
+impl IdeLauncher {
+    /// 生成启动参数列表
+    /// 先按空白拆分再替换占位符，因此项目路径中包含空格也不会被拆开
+    pub fn build_args(&self, project_dir: &Path) -> Vec<String> {
+        let project = project_dir.display().to_string();
+        self.args
+            .split_whitespace()
+            .map(|arg| arg.replace("{project}", &project))
+            .collect()
+    }
+
+    /// 在开发者工具中打开项目目录
+    /// 只负责启动进程，不等待其退出
+    pub fn launch(&self, project_dir: &Path) -> anyhow::Result<()> {
+        let path = self.path.trim();
+        if path.is_empty() {
+            anyhow::bail!("未配置开发者工具路径，请在「⚙ 设置」中填写");
+        }
+        if !Path::new(path).exists() {
+            anyhow::bail!("找不到开发者工具: {}", path);
+        }
+        Command::new(path).args(self.build_args(project_dir)).spawn()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_args() {
+        let launcher = IdeLauncher {
+            path: String::new(),
+            args: "open --project {project}".to_string(),
+        };
+        assert_eq!(
+            launcher.build_args(Path::new("/games/my game")),
+            vec!["open", "--project", "/games/my game"]
+        );
+        assert!(launcher.launch(Path::new("/games")).is_err());
+    }
+}
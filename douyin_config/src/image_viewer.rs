@@ -0,0 +1,107 @@
+use config_core::i18n::{tr, trf};
+use eframe::egui;
+use std::path::{Path, PathBuf};
+
+/// 缩放倍率的范围
+const ZOOM_RANGE: std::ops::RangeInclusive<f32> = 0.1..=8.0;
+
+/// 查看原图的窗口
+/// 点击预览图时打开，按需加载原分辨率的纹理（不使用预览区的缩略图缓存），关闭时释放
+#[derive(Default)]
+pub struct ImageViewer {
+    /// 正在查看的图片，为 None 时不显示窗口
+    path: Option<PathBuf>,
+    /// 原分辨率纹理，尚未加载时为 None，加载失败时为错误信息
+    texture: Option<Result<egui::TextureHandle, String>>,
+    /// 当前缩放倍率，为 None 时按窗口大小适配
+    zoom: Option<f32>,
+}
+
+/// 让图片完整显示在可用区域内的缩放倍率（不放大超过原尺寸）
+pub fn fit_zoom(image: [usize; 2], available: egui::Vec2) -> f32 {
+    if image[0] == 0 || image[1] == 0 {
+        return 1.0;
+    }
+    (available.x / image[0] as f32)
+        .min(available.y / image[1] as f32)
+        .min(1.0)
+        .clamp(*ZOOM_RANGE.start(), *ZOOM_RANGE.end())
+}
+
+/// 读取图片文件并以原分辨率上传为纹理
+fn load_full_texture(ctx: &egui::Context, path: &Path) -> Result<egui::TextureHandle, String> {
+    let img = image::open(path).map_err(|e| e.to_string())?;
+    let size = [img.width() as _, img.height() as _];
+    let rgba = img.to_rgba8();
+    let color_image = egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_flat_samples().as_slice());
+    Ok(ctx.load_texture("image_viewer", color_image, egui::TextureOptions::default()))
+}
+
+impl ImageViewer {
+    /// 打开窗口查看指定图片
+    pub fn open(&mut self, path: PathBuf) {
+        *self = Self { path: Some(path), ..Default::default() };
+    }
+
+    /// 绘制查看窗口；拖动平移，Ctrl + 滚轮或工具栏缩放
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let Some(path) = self.path.clone() else { return };
+        let texture = self.texture.get_or_insert_with(|| load_full_texture(ctx, &path));
+        let mut open = true;
+        egui::Window::new(path.file_name().unwrap_or_default().to_string_lossy())
+            .id(egui::Id::new("image_viewer"))
+            .open(&mut open)
+            .pivot(egui::Align2::CENTER_CENTER)
+            .default_pos(ctx.screen_rect().center())
+            .default_size([720.0, 600.0])
+            .resizable(true)
+            .show(ctx, |ui| match texture {
+                Ok(texture) => {
+                    let size = texture.size();
+                    // 预留工具栏的高度
+                    let fit = fit_zoom(size, ui.available_size() - egui::vec2(0.0, 32.0));
+                    let mut zoom = self.zoom.unwrap_or(fit);
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}×{}", size[0], size[1]));
+                        ui.add(egui::Slider::new(&mut zoom, ZOOM_RANGE).logarithmic(true).custom_formatter(|v, _| format!("{:.0}%", v * 100.0)));
+                        if ui.button("1:1").clicked() {
+                            zoom = 1.0;
+                        }
+                        if ui.button(tr("适应窗口")).clicked() {
+                            zoom = fit;
+                        }
+                    });
+                    self.zoom = Some(zoom);
+                    let output = egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                        ui.add(egui::Image::new(&*texture).fit_to_exact_size(egui::vec2(size[0] as f32, size[1] as f32) * zoom))
+                    });
+                    let delta = ui.input(|i| i.zoom_delta());
+                    if delta != 1.0 && output.inner.hovered() {
+                        self.zoom = Some((zoom * delta).clamp(*ZOOM_RANGE.start(), *ZOOM_RANGE.end()));
+                    }
+                }
+                Err(e) => {
+                    ui.colored_label(egui::Color32::RED, trf("❌ 加载失败: {}", &[&e]));
+                    ui.label(egui::RichText::new(path.to_string_lossy()).small());
+                }
+            });
+        if !open {
+            // 关闭时释放原分辨率纹理
+            *self = Self::default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_zoom() {
+        // 大图缩小到完整可见
+        assert_eq!(fit_zoom([750, 1500], egui::vec2(600.0, 600.0)), 0.4);
+        // 小图不放大
+        assert_eq!(fit_zoom([100, 100], egui::vec2(600.0, 600.0)), 1.0);
+        assert_eq!(fit_zoom([0, 100], egui::vec2(600.0, 600.0)), 1.0);
+    }
+}
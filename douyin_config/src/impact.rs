@@ -0,0 +1,82 @@
+use crate::model::ProjectItem;
+use std::path::PathBuf;
+
+/// 批量修改对单个文件的影响
+#[derive(Debug, PartialEq)]
+pub struct FileImpact {
+    /// 会被写入的文件
+    pub path: PathBuf,
+    /// 变化的字段：(字段名, 原值, 新值)
+    pub changes: Vec<(&'static str, String, String)>,
+}
+
+/// 比较批量修改前后的项目列表，列出每个会被写入的配置文件和 JS 文件及其新值
+///
+/// `before` 与 `after` 需一一对应（同一份列表试运行前后的状态）。
+/// 共享同一个 JS 文件的多个项目只列出一次。
+pub fn batch_impact(before: &[ProjectItem], after: &[ProjectItem]) -> Vec<FileImpact> {
+    let mut impacts: Vec<FileImpact> = Vec::new();
+    for (old, new) in before.iter().zip(after) {
+        let mut config_changes = Vec::new();
+        if old.config.appid != new.config.appid {
+            config_changes.push(("appid", old.config.appid.clone(), new.config.appid.clone()));
+        }
+        if old.config.projectname != new.config.projectname {
+            config_changes.push(("projectname", old.config.projectname.clone(), new.config.projectname.clone()));
+        }
+        if !config_changes.is_empty() {
+            impacts.push(FileImpact { path: new.path.clone(), changes: config_changes });
+        }
+
+        let (Some(old_js), Some(new_js), Some(js_path)) = (&old.js_config, &new.js_config, &new.js_path) else {
+            continue;
+        };
+        let mut js_changes = Vec::new();
+        if old_js.app_id != new_js.app_id {
+            js_changes.push(("appId", old_js.app_id.clone(), new_js.app_id.clone()));
+        }
+        if old_js.douyin_ids != new_js.douyin_ids {
+            js_changes.push(("douyinIds", old_js.douyin_ids.join(","), new_js.douyin_ids.join(",")));
+        }
+        if !js_changes.is_empty() && !impacts.iter().any(|i| i.path == *js_path) {
+            impacts.push(FileImpact { path: js_path.clone(), changes: js_changes });
+        }
+    }
+    impacts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{JsConfig, ProjectConfig};
+
+    #[test]
+    fn test_batch_impact() {
+        let item = |dir: &str, appid: &str| {
+            let config: ProjectConfig = serde_json::from_value(serde_json::json!({ "appid": appid, "projectname": dir })).unwrap();
+            let js = JsConfig { app_id: appid.to_string(), douyin_ids: vec!["a".to_string()], ..Default::default() };
+            ProjectItem::new(
+                PathBuf::from(dir).join("project.config.json"),
+                config,
+                Some(PathBuf::from(dir).join("index.js")),
+                Some(js),
+                Vec::new(),
+            )
+        };
+        let before = vec![item("game_a", "tt1"), item("game_b", "tt2")];
+        let mut after = before.clone();
+        after[0].config.appid = "tt9".to_string();
+        after[0].js_config.as_mut().unwrap().app_id = "tt9".to_string();
+        after[1].js_config.as_mut().unwrap().douyin_ids = vec!["b".to_string(), "c".to_string()];
+
+        let impacts = batch_impact(&before, &after);
+        assert_eq!(impacts.len(), 3);
+        assert_eq!(impacts[0].path, PathBuf::from("game_a").join("project.config.json"));
+        assert_eq!(impacts[0].changes, vec![("appid", "tt1".to_string(), "tt9".to_string())]);
+        assert_eq!(impacts[1].path, PathBuf::from("game_a").join("index.js"));
+        assert_eq!(impacts[2].changes, vec![("douyinIds", "a".to_string(), "b,c".to_string())]);
+
+        // 没有变化时不产生任何影响
+        assert!(batch_impact(&before, &before).is_empty());
+    }
+}
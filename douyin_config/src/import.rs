@@ -0,0 +1,213 @@
+use crate::model::ProjectItem;
+use anyhow::bail;
+use config_core::text::normalize_douyin_ids;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+
+/// CSV 映射文件中的一行
+/// 第一行为表头，列名不区分大小写：`folder`（或 `path`）、`appid`、`projectname`、`douyinIds`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CsvRow {
+    /// 在文件中的行号（从 1 开始，含表头）
+    pub line: usize,
+    /// 项目文件夹名称；包含 `/` 或 `\` 时按路径结尾匹配
+    pub folder: String,
+    pub appid: String,
+    pub projectname: String,
+    pub douyin_ids: String,
+}
+
+/// 单行的匹配结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowMatch {
+    /// 唯一匹配到的项目下标
+    Matched(usize),
+    /// 没有匹配的项目
+    Unmatched,
+    /// 多个项目的文件夹同名，需要用户按路径选择
+    Ambiguous(Vec<usize>),
+}
+
+/// 导入进度，在后台线程中更新、UI 线程中读取
+#[derive(Debug, Default)]
+pub struct ImportProgress {
+    pub processed: AtomicUsize,
+    pub total: AtomicUsize,
+}
+
+/// CSV 导入结果
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub rows: Vec<(CsvRow, RowMatch)>,
+}
+
+impl ImportReport {
+    /// 统计匹配、未匹配、有歧义的行数
+    pub fn counts(&self) -> (usize, usize, usize) {
+        let mut counts = (0, 0, 0);
+        for (_, m) in &self.rows {
+            match m {
+                RowMatch::Matched(_) => counts.0 += 1,
+                RowMatch::Unmatched => counts.1 += 1,
+                RowMatch::Ambiguous(_) => counts.2 += 1,
+            }
+        }
+        counts
+    }
+}
+
+/// 读取 CSV 映射文件
+pub fn read_rows(path: &Path) -> anyhow::Result<Vec<CsvRow>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_path(path)?;
+    let headers: Vec<String> = reader.headers()?.iter().map(|h| h.to_lowercase()).collect();
+    let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.as_str()));
+    let Some(folder_col) = column(&["folder", "path"]) else {
+        bail!("CSV 缺少 folder 列");
+    };
+    let appid_col = column(&["appid"]);
+    let name_col = column(&["projectname", "name"]);
+    let ids_col = column(&["douyinids"]);
+
+    let mut rows = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        let record = record?;
+        let get = |col: Option<usize>| col.and_then(|c| record.get(c)).unwrap_or("").to_string();
+        let folder = get(Some(folder_col));
+        if folder.is_empty() {
+            continue;
+        }
+        rows.push(CsvRow {
+            line: i + 2,
+            folder,
+            appid: get(appid_col),
+            projectname: get(name_col),
+            douyin_ids: get(ids_col),
+        });
+    }
+    Ok(rows)
+}
+
+/// 将每一行与项目目录匹配
+/// `dirs` 为各项目配置文件所在目录，下标与项目列表一致
+pub fn match_rows(rows: Vec<CsvRow>, dirs: &[PathBuf], progress: &ImportProgress) -> ImportReport {
+    progress.total.store(rows.len(), Ordering::Relaxed);
+    let mut report = ImportReport::default();
+    for row in rows {
+        let key = row.folder.replace('\\', "/");
+        let candidates: Vec<usize> = dirs
+            .iter()
+            .enumerate()
+            .filter(|(_, dir)| ends_with_folders(dir, &key))
+            .map(|(idx, _)| idx)
+            .collect();
+        let matched = match candidates.len() {
+            0 => RowMatch::Unmatched,
+            1 => RowMatch::Matched(candidates[0]),
+            _ => RowMatch::Ambiguous(candidates),
+        };
+        report.rows.push((row, matched));
+        progress.processed.fetch_add(1, Ordering::Relaxed);
+    }
+    report
+}
+
+/// 目录是否以 `key` 中的一级或多级文件夹结尾，按完整的路径组成部分比较（`game1` 不匹配 `mygame1`）
+/// `key` 已统一使用 `/` 分隔，目录中的 `\` 同样先转换，Windows 风格的路径在其他系统上也能匹配
+fn ends_with_folders(dir: &Path, key: &str) -> bool {
+    let key = key.trim_matches('/');
+    let dir = dir.to_string_lossy().replace('\\', "/");
+    !key.is_empty() && Path::new(&dir).ends_with(key)
+}
+
+/// 在后台线程中读取并匹配 CSV，完成后通过返回的 `Receiver` 发送一次结果
+pub fn spawn_import(
+    path: PathBuf,
+    projects: &[ProjectItem],
+) -> (Receiver<anyhow::Result<ImportReport>>, Arc<ImportProgress>) {
+    let dirs: Vec<PathBuf> = projects
+        .iter()
+        .map(|p| p.path.parent().map(Path::to_path_buf).unwrap_or_default())
+        .collect();
+    let progress = Arc::new(ImportProgress::default());
+    let thread_progress = progress.clone();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let result = read_rows(&path).map(|rows| match_rows(rows, &dirs, &thread_progress));
+        let _ = tx.send(result);
+    });
+    (rx, progress)
+}
+
+/// 将一行的非空字段应用到项目，AppID 同时写入 JS 配置
+///
+/// # Returns
+/// * `bool` - 项目是否因此产生未保存的修改
+pub fn apply_row(item: &mut ProjectItem, row: &CsvRow) -> bool {
+    if !row.appid.is_empty() {
+        item.config.appid = row.appid.clone();
+        if let Some(js) = &mut item.js_config {
+            js.app_id = row.appid.clone();
+        }
+    }
+    if !row.projectname.is_empty() {
+        item.config.projectname = row.projectname.clone();
+    }
+    if !row.douyin_ids.is_empty() {
+        if let Some(js) = &mut item.js_config {
+            js.douyin_ids_str = normalize_douyin_ids(&row.douyin_ids);
+            js.sync_ids_from_str();
+        }
+    }
+    item.refresh_modified()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_match_rows() {
+        let test_dir = Path::new("test_output_csv_import");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir).unwrap();
+        let csv_path = test_dir.join("mapping.csv");
+        fs::write(
+            &csv_path,
+            "Folder,AppID,ProjectName\ngame_a,tt1,A\ngame_b,tt2,B\nmissing,tt3,C\nteam2/game_b,tt4,B2\nm2/game_b,tt5,B3\n",
+        ).unwrap();
+
+        let rows = read_rows(&csv_path).unwrap();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0].line, 2);
+        assert_eq!(rows[0].appid, "tt1");
+
+        let dirs = vec![
+            PathBuf::from("root/team1/game_a"),
+            PathBuf::from("root/team1/game_b"),
+            PathBuf::from("root/team2/game_b"),
+        ];
+        let progress = ImportProgress::default();
+        let report = match_rows(rows, &dirs, &progress);
+        let matches: Vec<RowMatch> = report.rows.iter().map(|(_, m)| m.clone()).collect();
+        assert_eq!(matches, vec![
+            RowMatch::Matched(0),
+            RowMatch::Ambiguous(vec![1, 2]),
+            RowMatch::Unmatched,
+            // 带路径的行可以消除歧义
+            RowMatch::Matched(2),
+            // 按完整的文件夹名比较，m2 不匹配 team2
+            RowMatch::Unmatched,
+        ]);
+        assert_eq!(report.counts(), (2, 2, 1));
+        assert_eq!(progress.processed.load(Ordering::Relaxed), 5);
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+}
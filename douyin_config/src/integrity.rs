@@ -0,0 +1,124 @@
+use crate::model::ProjectItem;
+use config_core::i18n::{tr, trf};
+use std::collections::HashMap;
+
+/// 参与比较的最短英文/数字片段长度，过短的片段（如 `v2`、`tt`）容易误判
+const MIN_TOKEN_LEN: usize = 3;
+
+/// 将名称拆分为小写的英文/数字片段（如 `Game_ABC-02` → `game`、`abc`）
+/// 中文等非 ASCII 字符作为分隔符，不参与比较
+fn ascii_tokens(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| t.len() >= MIN_TOKEN_LEN)
+        .map(|t| t.to_ascii_lowercase())
+        .collect()
+}
+
+/// 项目所在文件夹的名称：配置文件所在目录及其上一级目录
+/// 打包产物常放在 `游戏名/build/bytedance-mini-game` 这类目录下，因此上一级也参与比较
+fn folder_names(item: &ProjectItem) -> Vec<String> {
+    item.path
+        .ancestors()
+        .skip(1)
+        .take(2)
+        .filter_map(|dir| dir.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .collect()
+}
+
+/// 项目名称与文件夹名称是否明显不符
+/// 两者都包含英文/数字片段、且没有任何片段出现在对方中时视为不符；
+/// 中文名称与拼音文件夹等无法比较的情况不视为不符
+fn name_disagrees(folders: &[String], projectname: &str) -> bool {
+    let name_tokens = ascii_tokens(projectname);
+    let folder_tokens: Vec<String> = folders.iter().flat_map(|f| ascii_tokens(f)).collect();
+    if name_tokens.is_empty() || folder_tokens.is_empty() {
+        return false;
+    }
+    let name_compact: String = name_tokens.concat();
+    let folder_compact: String = folder_tokens.concat();
+    !name_tokens.iter().any(|t| folder_compact.contains(t.as_str()))
+        && !folder_tokens.iter().any(|t| name_compact.contains(t.as_str()))
+}
+
+/// 检查每个项目的配置是否可能是从其他项目复制时误带过来的
+///
+/// 只作为提示，不阻止任何操作。按磁盘上的配置（扫描时的快照）判断：
+/// - 项目名称与文件夹名称明显不符
+/// - 与另一个文件夹中的项目 AppID 和名称完全相同
+///
+/// # Returns
+/// * `Vec<Option<String>>` - 与项目列表下标一致的提示文字，没有问题的项目为 None
+pub fn mismatch_hints(projects: &[ProjectItem]) -> Vec<Option<String>> {
+    let mut same_config: HashMap<(&str, &str), Vec<usize>> = HashMap::new();
+    for (idx, item) in projects.iter().enumerate() {
+        let config = &item.original.config;
+        if !config.appid.is_empty() {
+            same_config.entry((&config.appid, &config.projectname)).or_default().push(idx);
+        }
+    }
+
+    projects
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let config = &item.original.config;
+            let folders = folder_names(item);
+            let mut hints = Vec::new();
+            if name_disagrees(&folders, &config.projectname) {
+                hints.push(trf(
+                    "项目名称「{}」与文件夹「{}」不符",
+                    &[&config.projectname, &folders.first().map(String::as_str).unwrap_or_default()],
+                ));
+            }
+            let twins: Vec<String> = same_config
+                .get(&(config.appid.as_str(), config.projectname.as_str()))
+                .into_iter()
+                .flatten()
+                .filter(|&&other| other != idx)
+                .filter_map(|&other| folder_names(&projects[other]).into_iter().next())
+                .collect();
+            if !twins.is_empty() {
+                hints.push(trf("AppID 和名称与 {} 完全相同", &[&twins.join("、")]));
+            }
+            (!hints.is_empty()).then(|| hints.join("\n") + "\n" + tr("可能是复制项目时带入了其他项目的配置"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ProjectConfig;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_mismatch_hints() {
+        let item = |dir: &str, appid: &str, name: &str| {
+            let config: ProjectConfig =
+                serde_json::from_value(serde_json::json!({ "appid": appid, "projectname": name })).unwrap();
+            ProjectItem::new(PathBuf::from(dir).join("project.config.json"), config, None, None, Vec::new())
+        };
+        let projects = vec![
+            item("root/game_abc", "tt1", "Game ABC"),
+            // 名称与文件夹无关
+            item("root/game_xyz", "tt2", "Puzzle Hero"),
+            // 中文名称无法与英文文件夹比较，不提示
+            item("root/runner", "tt3", "跑酷"),
+            // 打包目录使用上一级文件夹名称比较
+            item("root/Snake/bytedance-mini-game", "tt4", "Snake 2"),
+            // 与 game_abc 的配置完全相同
+            item("root/game_copy", "tt1", "Game ABC"),
+        ];
+
+        let hints = mismatch_hints(&projects);
+        assert!(hints[1].as_ref().unwrap().contains("不符"));
+        assert!(hints[2].is_none());
+        assert!(hints[3].is_none());
+        assert!(hints[0].as_ref().unwrap().contains("game_copy"));
+        let copy_hint = hints[4].as_ref().unwrap();
+        assert!(copy_hint.contains("game_abc"));
+        // game_copy 中的 "game" 片段与名称匹配，因此只提示配置重复
+        assert!(!copy_hint.contains("不符"));
+    }
+}
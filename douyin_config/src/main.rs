@@ -2,30 +2,61 @@
 
 // 声明项目中的模块
 mod app;      // 应用程序主逻辑和 UI 定义
+mod channel;  // 按目录约定识别渠道
+mod ide;      // 抖音开发者工具启动
+mod image_viewer; // 原图查看窗口
+mod impact;   // 批量修改的影响预览
+mod import;   // CSV 映射导入
+mod integrity; // 配置与文件夹名称的一致性检查
 mod model;    // 数据模型定义
+mod notes;    // 项目备注
+mod packager; // ZIP 打包
+mod profile;  // 配置模板
+mod regex_debug; // JS 正则调试面板
+mod rename;   // 按项目名称重命名文件夹
 mod scanner;  // 文件扫描和处理逻辑
+mod search;   // 跨项目 JS 内容搜索
+mod settings; // 设置文件的读写
+mod texture_lru; // 预览图纹理缓存的淘汰
+mod tree;     // 目录树构建
+mod undo;     // 批量修改的撤销 / 重做
 
 use app::MyApp;
+use settings::Settings;
 use eframe::egui;
 
 // 程序入口点
 // 返回 eframe::Result<()> 以处理可能的启动错误
 fn main() -> eframe::Result<()> {
+    // 读取上次保存的设置；窗口大小要在创建窗口前确定，其余设置交给 MyApp::new 应用
+    let settings = Settings::load();
+
     // 设置原生窗口选项
     let options = eframe::NativeOptions {
         // 配置视口（窗口）属性
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([750.0, 800.0]) // 设置初始窗口大小
-            .with_title("Douyin Config Editor"), // 设置窗口标题
+            .with_inner_size(settings.window_size) // 恢复上次的窗口大小
+            .with_title("Douyin Config Editor") // 设置窗口标题
+            .with_icon(load_icon().unwrap_or_default()), // 设置窗口图标
         ..Default::default()
     };
     
     // 启动 eframe 应用程序
     eframe::run_native(
-        "Douyin Config Editor", // 应用程序名称（用于持久化存储等）
+        "Douyin Config Editor", // 应用程序名称
         options,
         // 创建应用程序实例的闭包
         // cc (CreationContext) 包含了 egui 的上下文，用于初始化字体、样式等
-        Box::new(|cc| Ok(Box::new(MyApp::new(cc)))),
+        Box::new(|cc| Ok(Box::new(MyApp::new(cc, settings)))),
     )
 }
+
+/// 编译时嵌入的窗口图标（与 build.rs 中的 exe 图标相同）
+const EMBEDDED_ICON: &[u8] = include_bytes!("../icon.ico");
+
+/// 加载窗口图标，优先使用嵌入的图标，解码失败时尝试工作目录下的 icon.ico
+fn load_icon() -> Option<egui::IconData> {
+    config_core::icon::decode_icon(EMBEDDED_ICON)
+        .or_else(|| std::fs::read("icon.ico").ok().and_then(|data| config_core::icon::decode_icon(&data)))
+        .map(|(rgba, width, height)| egui::IconData { rgba, width, height })
+}
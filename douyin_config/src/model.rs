@@ -1,6 +1,9 @@
+use config_core::encoding::TextEncoding;
+use config_core::text::{split_douyin_ids, strip_wrapping_quotes};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::path::PathBuf;
+use std::time::Instant;
 use std::fmt;
 
 /// 对应 project.config.json 文件的结构体
@@ -21,6 +24,46 @@ pub struct ProjectConfig {
     pub extra: Value,
 }
 
+impl ProjectConfig {
+    /// 去掉项目名称两端多余的引号（如之前错误编辑留下的 `"Game"`）
+    ///
+    /// # Returns
+    /// * `bool` - 名称是否被修改
+    pub fn normalize_name(&mut self) -> bool {
+        let normalized = strip_wrapping_quotes(&self.projectname);
+        if normalized == self.projectname {
+            return false;
+        }
+        self.projectname = normalized.to_string();
+        true
+    }
+}
+
+/// JS 中除 douyinIds 以外的其他 ID 数组（如 `.rewardIds=[...]`、`.bannerIds=[...]`）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct IdArray {
+    /// 字段名，如 rewardIds
+    pub name: String,
+    /// 数组中的 ID 列表
+    pub ids: Vec<String>,
+}
+
+/// JS 文件中某个字段匹配位置的上下文片段
+/// 在编辑前展示给用户，用于确认正则匹配到了正确的位置
+#[derive(Debug, Clone, Default)]
+pub struct JsMatch {
+    /// 字段名，如 appId、douyinIds
+    pub field: String,
+    /// 匹配内容在文件中的字节范围（扫描时）
+    pub span: std::ops::Range<usize>,
+    /// 匹配位置之前的上下文
+    pub before: String,
+    /// 匹配到的内容
+    pub matched: String,
+    /// 匹配位置之后的上下文
+    pub after: String,
+}
+
 /// 对应 JS 配置文件（如 assets/main/index.js）中提取的配置信息
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct JsConfig {
@@ -32,12 +75,51 @@ pub struct JsConfig {
     /// 使用 #[serde(skip)] 避免将其序列化到 JSON 中，这只是一个 UI 辅助字段
     #[serde(skip)]
     pub douyin_ids_str: String, 
+    /// 同一文件中发现的其他 `.xxxIds=[...]` 数组，按出现顺序排列
+    #[serde(default)]
+    pub extra_id_arrays: Vec<IdArray>,
+    /// 扫描时各字段的匹配位置及上下文，仅用于只读预览
+    #[serde(skip)]
+    pub matches: Vec<JsMatch>,
+    /// 扫描时识别到的文件编码（UTF-8 或旧版打包产物的 GBK），保存时按文件当前编码写回
+    #[serde(skip)]
+    pub encoding: TextEncoding,
+}
+
+impl JsConfig {
+    /// 根据 douyin_ids 列表重新生成逗号分隔的编辑字符串
+    /// 在列表编辑器中增删改条目后调用
+    pub fn sync_str_from_ids(&mut self) {
+        self.douyin_ids_str = self.douyin_ids.join(",");
+    }
+
+    /// 根据逗号分隔的编辑字符串重新解析 douyin_ids 列表
+    /// 在文本框或批量操作修改 douyin_ids_str 后调用，空条目和 ID 两端的引号会被去掉
+    pub fn sync_ids_from_str(&mut self) {
+        self.douyin_ids = split_douyin_ids(&self.douyin_ids_str);
+    }
+
+    /// 比较会被写回文件的字段是否与另一份配置相同
+    /// 编辑字符串和匹配位置只是 UI 辅助数据，不参与比较
+    pub fn same_values(&self, other: &JsConfig) -> bool {
+        self.app_id == other.app_id
+            && self.douyin_ids == other.douyin_ids
+            && self.extra_id_arrays == other.extra_id_arrays
+    }
+}
+
+/// 项目配置的快照
+/// 在扫描或保存后记录，用于还原未保存的修改
+#[derive(Debug, Clone)]
+pub struct ProjectSnapshot {
+    pub config: ProjectConfig,
+    pub js_config: Option<JsConfig>,
 }
 
 /// UI 列表中单个项目的完整状态模型
 #[derive(Clone)]
 pub struct ProjectItem {
-    /// 配置文件 (project.config.json) 的绝对路径
+    /// 配置文件 (project.config.json 或 .yaml / .toml 变体) 的绝对路径
     pub path: PathBuf,
     /// 解析后的 project.config.json 配置内容
     pub config: ProjectConfig,
@@ -47,16 +129,144 @@ pub struct ProjectItem {
     pub js_config: Option<JsConfig>,
     /// 项目目录下找到的符合条件的预览图路径列表
     pub image_paths: Vec<PathBuf>,
-    /// 标记当前项目是否有未保存的修改
+    /// 预览图的尺寸（宽, 高），与 `image_paths` 一一对应，无法读取时为 None
+    pub image_sizes: Vec<Option<(u32, u32)>>,
+    /// 是否已查找过预览图；延迟查找模式下扫描时为 false，展开卡片的预览图区域时才查找
+    pub images_probed: bool,
+    /// 标记当前项目是否有未保存的修改（由 `refresh_modified` 与原始快照比较得出）
     pub is_modified: bool,
     /// 标记当前项目是否在 UI列表中被选中（用于批量操作）
     pub selected: bool,
+    /// 最近一次从磁盘加载或保存时的配置快照
+    pub original: ProjectSnapshot,
+    /// 保存前检查发现配置文件或 JS 文件已不存在（被移动或删除）
+    pub files_missing: bool,
+    /// 用户为项目添加的备注（如"待审核"），保存在根目录的备注文件中，不属于项目配置
+    pub note: String,
     
     /// 图片纹理缓存
     /// key: 图片路径
-    /// value: egui 纹理句柄（加载成功为 Some，失败为 None）
-    /// 用于避免重复加载同一张图片，提高性能
-    pub texture_cache: std::collections::HashMap<PathBuf, Option<egui::TextureHandle>>,
+    /// value: 纹理及其最近显示时间
+    /// 用于避免重复加载同一张图片，提高性能；失败的图片（如正被外部工具写入）过一段时间后会重试
+    pub texture_cache: std::collections::HashMap<PathBuf, CachedTexture>,
+}
+
+/// 纹理缓存中的一项
+#[derive(Clone)]
+pub struct CachedTexture {
+    /// 加载成功为 egui 纹理句柄，失败为失败的时间
+    pub texture: Result<egui::TextureHandle, Instant>,
+    /// 最近一次显示的时间，缓存超出上限时最久未显示的纹理先被释放
+    pub last_shown: Instant,
+}
+
+impl ProjectItem {
+    /// 根据扫描结果创建项目项
+    /// 默认选中，并以当前配置作为原始快照
+    pub fn new(
+        path: PathBuf,
+        config: ProjectConfig,
+        js_path: Option<PathBuf>,
+        js_config: Option<JsConfig>,
+        image_paths: Vec<PathBuf>,
+    ) -> Self {
+        let original = ProjectSnapshot {
+            config: config.clone(),
+            js_config: js_config.clone(),
+        };
+        Self {
+            path,
+            config,
+            js_path,
+            js_config,
+            image_sizes: vec![None; image_paths.len()],
+            image_paths,
+            images_probed: true,
+            is_modified: false,
+            selected: true, // 默认选中，方便用户直接进行批量操作
+            original,
+            files_missing: false,
+            note: String::new(),
+            texture_cache: std::collections::HashMap::new(),
+        }
+    }
+
+    /// 丢弃未保存的修改，恢复到原始快照
+    pub fn revert(&mut self) {
+        self.config = self.original.config.clone();
+        self.js_config = self.original.js_config.clone();
+        self.is_modified = false;
+    }
+
+    /// 将当前配置与原始快照逐字段比较，更新 `is_modified`
+    /// 编辑后又改回原值时，待保存标记会随之清除
+    ///
+    /// # Returns
+    /// * `bool` - 是否存在未保存的修改
+    pub fn refresh_modified(&mut self) -> bool {
+        self.is_modified = !self.matches_snapshot(&self.original);
+        self.is_modified
+    }
+
+    /// 当前配置中会被写回文件的字段是否与快照相同
+    pub fn matches_snapshot(&self, snapshot: &ProjectSnapshot) -> bool {
+        let js_same = match (&self.js_config, &snapshot.js_config) {
+            (Some(current), Some(orig)) => current.same_values(orig),
+            (None, None) => true,
+            _ => false,
+        };
+        self.config.appid == snapshot.config.appid
+            && self.config.projectname == snapshot.config.projectname
+            && js_same
+    }
+
+    /// 当前配置的快照
+    pub fn snapshot(&self) -> ProjectSnapshot {
+        ProjectSnapshot {
+            config: self.config.clone(),
+            js_config: self.js_config.clone(),
+        }
+    }
+
+    /// 恢复到指定快照的配置（用于撤销 / 重做），并更新 `is_modified`
+    pub fn restore(&mut self, snapshot: ProjectSnapshot) {
+        self.config = snapshot.config;
+        self.js_config = snapshot.js_config;
+        self.refresh_modified();
+    }
+
+    /// 检查配置文件和 JS 文件是否仍然存在，并更新 `files_missing`
+    ///
+    /// # Returns
+    /// * `bool` - 文件是否全部存在
+    pub fn check_files_exist(&mut self) -> bool {
+        self.files_missing = !self.path.exists()
+            || self.js_path.as_ref().is_some_and(|p| !p.exists());
+        !self.files_missing
+    }
+
+    /// 从 JS 配置的 douyinIds 中移除指定的 ID（所有出现的位置），其余 ID 保持原有顺序
+    /// 只有确实移除了 ID 时才会重新生成编辑字符串并更新 `is_modified`
+    ///
+    /// # Returns
+    /// * `bool` - 是否移除了至少一个 ID；没有 JS 配置时为 false
+    pub fn remove_douyin_ids(&mut self, ids: &[String]) -> bool {
+        let Some(js) = &mut self.js_config else { return false };
+        let before = js.douyin_ids.len();
+        js.douyin_ids.retain(|id| !ids.contains(id));
+        if js.douyin_ids.len() == before {
+            return false;
+        }
+        js.sync_str_from_ids();
+        self.refresh_modified();
+        true
+    }
+
+    /// 保存成功后调用，以当前配置更新原始快照
+    pub fn mark_saved(&mut self) {
+        self.original = self.snapshot();
+        self.is_modified = false;
+    }
 }
 
 /// 手动实现 Debug trait 以优化输出格式，避免打印过长的 texture_cache 内容
@@ -68,10 +278,47 @@ impl fmt::Debug for ProjectItem {
             .field("js_path", &self.js_path)
             .field("js_config", &self.js_config)
             .field("image_paths", &self.image_paths)
+            .field("image_sizes", &self.image_sizes)
+            .field("images_probed", &self.images_probed)
             .field("is_modified", &self.is_modified)
             .field("selected", &self.selected)
+            .field("original", &self.original)
+            .field("files_missing", &self.files_missing)
+            .field("note", &self.note)
             // 仅打印缓存大小，而不是具体内容
             .field("texture_cache", &format!("HashMap(len={})", self.texture_cache.len()))
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_douyin_ids() {
+        let config = ProjectConfig {
+            appid: "tt1".to_string(),
+            projectname: String::new(),
+            extra: serde_json::json!({}),
+        };
+        let mut js = JsConfig { douyin_ids_str: "a,b,a,c".to_string(), ..Default::default() };
+        js.sync_ids_from_str();
+        let mut item = ProjectItem::new(PathBuf::from("project.config.json"), config.clone(), None, Some(js), Vec::new());
+
+        // 不包含的 ID 不会产生修改
+        assert!(!item.remove_douyin_ids(&["x".to_string()]));
+        assert!(!item.is_modified);
+
+        assert!(item.remove_douyin_ids(&["a".to_string(), "c".to_string()]));
+        let js = item.js_config.as_ref().unwrap();
+        assert_eq!(js.douyin_ids, vec!["b"]);
+        assert_eq!(js.douyin_ids_str, "b");
+        assert!(item.is_modified);
+
+        // 没有 JS 配置的项目
+        let mut no_js = ProjectItem::new(PathBuf::from("project.config.json"), config, None, None, Vec::new());
+        assert!(!no_js.remove_douyin_ids(&["a".to_string()]));
+        assert!(!no_js.is_modified);
+    }
+}
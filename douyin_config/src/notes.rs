@@ -0,0 +1,100 @@
+use crate::model::ProjectItem;
+use config_core::json::{read_json, write_json};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// 备注文件名，保存在扫描根目录下
+/// 打包时始终排除，不会进入项目的压缩包
+pub const NOTES_FILENAME: &str = ".ccc_notes.json";
+
+/// 项目在备注文件中的键：项目目录相对于根目录的路径，统一使用 `/` 分隔
+/// 根目录本身就是项目时为 `.`
+pub fn project_key(root: &Path, item: &ProjectItem) -> String {
+    let dir = item.path.parent().unwrap_or(&item.path);
+    let relative = dir.strip_prefix(root).unwrap_or(dir).to_string_lossy().replace('\\', "/");
+    if relative.is_empty() { ".".to_string() } else { relative }
+}
+
+/// 读取备注文件，文件不存在时返回空表
+fn read_notes(root: &Path) -> anyhow::Result<BTreeMap<String, String>> {
+    let path = root.join(NOTES_FILENAME);
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    Ok(read_json(&path)?)
+}
+
+/// 扫描后加载各项目的备注
+pub fn load_notes(root: &Path, projects: &mut [ProjectItem]) -> anyhow::Result<()> {
+    let notes = read_notes(root)?;
+    for item in projects {
+        item.note = notes.get(&project_key(root, item)).cloned().unwrap_or_default();
+    }
+    Ok(())
+}
+
+/// 将当前项目的备注写回备注文件
+///
+/// 本次扫描中没有出现、但目录仍存在的项目（例如被"仅含 JS"过滤掉）的备注会保留；
+/// 目录已不存在的条目（被删除或重命名）会被清理。备注为空的项目不写入。
+pub fn save_notes(root: &Path, projects: &[ProjectItem]) -> anyhow::Result<()> {
+    let mut notes = read_notes(root)?;
+    notes.retain(|key, _| root.join(key).is_dir());
+    for item in projects {
+        let key = project_key(root, item);
+        let note = item.note.trim();
+        if note.is_empty() {
+            notes.remove(&key);
+        } else {
+            notes.insert(key, note.to_string());
+        }
+    }
+    let path = root.join(NOTES_FILENAME);
+    if notes.is_empty() && !path.exists() {
+        return Ok(());
+    }
+    write_json(&path, &notes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ProjectConfig;
+    use std::fs;
+
+    #[test]
+    fn test_notes_round_trip() {
+        let root = Path::new("test_output_notes");
+        if root.exists() { fs::remove_dir_all(root).unwrap(); }
+        fs::create_dir_all(root.join("a")).unwrap();
+        fs::create_dir_all(root.join("b")).unwrap();
+        let item = |dir: &str| {
+            let config: ProjectConfig = serde_json::from_str("{}").unwrap();
+            ProjectItem::new(root.join(dir).join("project.config.json"), config, None, None, Vec::new())
+        };
+
+        let mut projects = vec![item("a"), item("b")];
+        assert_eq!(project_key(root, &projects[0]), "a");
+        projects[0].note = "待审核".to_string();
+        save_notes(root, &projects).unwrap();
+
+        // 项目 b 不在本次扫描结果中时，已有备注仍保留
+        projects[0].note.clear();
+        load_notes(root, &mut projects).unwrap();
+        assert_eq!(projects[0].note, "待审核");
+        projects[1].note = "已提交".to_string();
+        save_notes(root, &projects[1..]).unwrap();
+        load_notes(root, &mut projects).unwrap();
+        assert_eq!(projects[0].note, "待审核");
+        assert_eq!(projects[1].note, "已提交");
+
+        // 目录被删除后，其备注在下次保存时被清理
+        fs::remove_dir_all(root.join("a")).unwrap();
+        save_notes(root, &projects[1..]).unwrap();
+        let notes = read_notes(root).unwrap();
+        assert_eq!(notes.keys().collect::<Vec<_>>(), vec!["b"]);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,589 @@
+use crate::notes::NOTES_FILENAME;
+use config_core::text::wildcard_match;
+use config_core::walk::SymlinkStats;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// 默认不打包的目录
+pub const DEFAULT_EXCLUDE_PATTERNS: [&str; 3] = ["node_modules", ".git", ".svn"];
+
+/// ZIP 压缩方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ZipCompression {
+    /// 只存储不压缩
+    Stored,
+    /// Deflate 最低压缩级别
+    Fast,
+    /// Deflate 默认压缩级别
+    #[default]
+    Normal,
+    /// Deflate 最高压缩级别
+    Best,
+}
+
+impl ZipCompression {
+    pub const ALL: [ZipCompression; 4] = [
+        ZipCompression::Stored,
+        ZipCompression::Fast,
+        ZipCompression::Normal,
+        ZipCompression::Best,
+    ];
+
+    /// 下拉框中显示的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            ZipCompression::Stored => "仅存储",
+            ZipCompression::Fast => "快速压缩",
+            ZipCompression::Normal => "标准压缩",
+            ZipCompression::Best => "最大压缩",
+        }
+    }
+
+    /// 速度与体积的取舍说明，开始打包时显示在状态栏
+    pub fn tradeoff(self) -> &'static str {
+        match self {
+            ZipCompression::Stored => "不压缩，速度最快，压缩包最大",
+            ZipCompression::Fast => "压缩较快，压缩包略大",
+            ZipCompression::Normal => "速度与体积均衡",
+            ZipCompression::Best => "压缩包最小，速度最慢",
+        }
+    }
+
+    /// 对应的压缩算法和级别
+    fn file_options(self) -> SimpleFileOptions {
+        let (method, level) = match self {
+            ZipCompression::Stored => (CompressionMethod::Stored, None),
+            ZipCompression::Fast => (CompressionMethod::Deflated, Some(1)),
+            ZipCompression::Normal => (CompressionMethod::Deflated, None),
+            ZipCompression::Best => (CompressionMethod::Deflated, Some(9)),
+        };
+        SimpleFileOptions::default()
+            .compression_method(method)
+            .compression_level(level)
+    }
+}
+
+/// 打包选项
+#[derive(Debug, Clone)]
+pub struct ZipOptions {
+    /// 是否跳过源目录中已有的 .zip 文件
+    /// 避免把之前打出的压缩包一层层嵌套进新的压缩包
+    pub skip_zip_files: bool,
+    /// 不打包的文件和目录名的通配符模式（如 `node_modules`、`*.psd`、`.DS_Store`）
+    /// 与每一级的名称比较，不区分大小写；匹配的目录整个跳过
+    pub exclude_patterns: Vec<String>,
+    /// 压缩方式
+    pub compression: ZipCompression,
+    /// 是否跟随符号链接，把链接指向的文件和目录内容打包进去
+    /// 关闭时链接本身不打包；开启时指向上级目录的循环链接会被跳过
+    pub follow_links: bool,
+}
+
+impl Default for ZipOptions {
+    fn default() -> Self {
+        Self {
+            skip_zip_files: true,
+            exclude_patterns: DEFAULT_EXCLUDE_PATTERNS.iter().map(|p| p.to_string()).collect(),
+            compression: ZipCompression::default(),
+            follow_links: false,
+        }
+    }
+}
+
+impl ZipOptions {
+    /// 文件或目录名是否匹配任一排除模式
+    fn is_excluded(&self, name: &str) -> bool {
+        self.exclude_patterns.iter().any(|pattern| wildcard_match(pattern, name))
+    }
+}
+
+/// 计算项目的打包目录和输出路径
+///
+/// 打包配置文件所在目录的上一级目录（没有上一级时打包配置目录本身），
+/// 压缩包以项目名称命名（名称为空时使用文件夹名），放在被打包目录的同级。
+///
+/// # Returns
+/// * `Option<(PathBuf, PathBuf)>` - (被打包的目录, 输出的 ZIP 路径)，配置文件没有所在目录时为 None
+pub fn zip_plan(config_path: &Path, projectname: &str) -> Option<(PathBuf, PathBuf)> {
+    let config_dir = config_path.parent()?;
+    let project_root = config_dir.parent().unwrap_or(config_dir);
+    let name = if projectname.is_empty() {
+        project_root.file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "project".to_string())
+    } else {
+        projectname.to_string()
+    };
+    let zip_filename = format!("{}.zip", name);
+    let zip_path = match project_root.parent() {
+        Some(p) => p.join(&zip_filename),
+        None => project_root.join(&zip_filename),
+    };
+    Some((project_root.to_path_buf(), zip_path))
+}
+
+/// 为同一批打包的输出路径去重，避免同名项目的压缩包互相覆盖
+///
+/// 路径相同（不区分大小写，兼容 Windows）的项目中，第一个保持原名，
+/// 之后的依次在文件名后加 `_<后缀>`（通常为 AppID）；后缀为空或仍冲突时改用 `_2`、`_3` ...
+///
+/// # Returns
+/// * `Vec<usize>` - 输出路径被改名的下标
+pub fn dedup_zip_paths(paths: &mut [PathBuf], suffixes: &[&str]) -> Vec<usize> {
+    let key = |p: &Path| p.to_string_lossy().to_lowercase();
+    let mut used: HashSet<String> = HashSet::new();
+    let mut renamed = Vec::new();
+    for (idx, path) in paths.iter_mut().enumerate() {
+        if used.insert(key(path)) {
+            continue;
+        }
+        let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let suffix = suffixes.get(idx).copied().unwrap_or_default();
+        let mut candidates = (!suffix.is_empty())
+            .then(|| format!("{}_{}.zip", stem, suffix))
+            .into_iter()
+            .chain((2..).map(|n| format!("{}_{}.zip", stem, n)));
+        let unique = candidates
+            .find_map(|name| {
+                let candidate = path.with_file_name(name);
+                used.insert(key(&candidate)).then_some(candidate)
+            })
+            .unwrap();
+        *path = unique;
+        renamed.push(idx);
+    }
+    renamed
+}
+
+/// 确保输出路径以 `.zip` 结尾（不区分大小写），保存对话框中没有输入扩展名时补上
+pub fn with_zip_extension(path: PathBuf) -> PathBuf {
+    if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip")) {
+        return path;
+    }
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".zip");
+    path.with_file_name(name)
+}
+
+/// 将目录打包为 ZIP 文件
+///
+/// 先写入同目录下的 `<输出文件名>.part`，成功后再重命名为 `dst_file`；失败时删除未写完的文件，
+/// 已有的同名压缩包在新文件写完之前保持不变，不会留下损坏的压缩包。
+/// 会跳过名称匹配 `options.exclude_patterns` 的文件和目录（默认为 node_modules、.git、.svn）；输出文件本身即使位于源目录中也不会被打包，
+/// 比较时使用规范化后的路径，避免 `a/./b.zip` 与 `a/b.zip` 这类写法差异导致漏判。
+///
+/// # Arguments
+/// * `src_dir` - 要打包的目录
+/// * `dst_file` - 输出的 ZIP 文件路径
+/// * `options` - 打包选项
+/// * `progress` - 每写入一个文件后以 `(已写入的文件数, 文件总数)` 调用；
+///   会先遍历一遍目录确定要打包的条目，以便在压缩前给出文件总数
+///
+/// # Returns
+/// * `SymlinkStats` - 遇到的符号链接（按 `options.follow_links` 跟随或跳过）
+pub fn create_zip(
+    src_dir: &Path,
+    dst_file: &Path,
+    options: &ZipOptions,
+    progress: impl FnMut(usize, usize),
+) -> anyhow::Result<SymlinkStats> {
+    let part_file = partial_path(dst_file);
+    let result = write_zip(src_dir, dst_file, &part_file, options, progress).and_then(|symlinks| {
+        fs::rename(&part_file, dst_file)?;
+        Ok(symlinks)
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&part_file);
+    }
+    result
+}
+
+/// 写入中的压缩包路径：与输出文件位于同一目录的 `<输出文件名>.part`，写完后重命名即可替换
+fn partial_path(dst_file: &Path) -> PathBuf {
+    let mut name = dst_file.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dst_file.with_file_name(name)
+}
+
+/// 把 `src_dir` 打包写入 `part_file`；`dst_file` 和 `part_file` 位于源目录中时都不会被打包
+fn write_zip(
+    src_dir: &Path,
+    dst_file: &Path,
+    part_file: &Path,
+    options: &ZipOptions,
+    mut progress: impl FnMut(usize, usize),
+) -> anyhow::Result<SymlinkStats> {
+    let file = File::create(part_file)?;
+    // 文件创建后才能规范化路径；输出文件此时可能还不存在
+    let dst_canonical = fs::canonicalize(dst_file).ok();
+    let part_canonical = fs::canonicalize(part_file).ok();
+
+    // 要打包的条目：(路径, 是否为目录)
+    let mut entries = Vec::new();
+    let mut symlinks = SymlinkStats::default();
+    let mut it = WalkDir::new(src_dir).follow_links(options.follow_links).into_iter();
+    while let Some(entry) = it.next() {
+        symlinks.observe(&entry);
+        let entry = match entry {
+            // 循环链接会把上级目录无限嵌套进压缩包，跳过
+            Err(err) if err.loop_ancestor().is_some() => continue,
+            entry => entry?,
+        };
+        let path = entry.path();
+        if path == src_dir {
+            continue;
+        }
+        // 不跟随时链接本身也不打包：指向文件的链接不会被当作普通文件读取，与链接目录的处理一致
+        if !options.follow_links && entry.path_is_symlink() {
+            continue;
+        }
+
+        // 跳过名称匹配排除模式的文件夹和文件
+        let file_name = path.file_name().map(|s| s.to_string_lossy()).unwrap_or_default();
+        if options.is_excluded(&file_name) {
+            if path.is_dir() {
+                it.skip_current_dir();
+            }
+            continue;
+        }
+        if path.is_dir() {
+            entries.push((path.to_path_buf(), true));
+        } else {
+            // 跳过当前的 zip 文件及正在写入的临时文件（如果它们碰巧在源目录中）
+            if is_same_file(path, dst_file, dst_canonical.as_deref())
+                || is_same_file(path, part_file, part_canonical.as_deref())
+            {
+                continue;
+            }
+            // 编辑器的备注文件只供本地使用，不论排除模式如何都不打包
+            if file_name == NOTES_FILENAME {
+                continue;
+            }
+            // 按需跳过源目录中已有的压缩包
+            if options.skip_zip_files
+                && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+            {
+                continue;
+            }
+            entries.push((path.to_path_buf(), false));
+        }
+    }
+
+    let total = entries.iter().filter(|(_, is_dir)| !is_dir).count();
+    let mut zip = ZipWriter::new(file);
+    let file_options = options.compression.file_options().unix_permissions(0o755);
+    let mut buffer = Vec::new();
+    let mut done = 0;
+    for (path, is_dir) in &entries {
+        let name = path.strip_prefix(src_dir)?.to_string_lossy();
+        if *is_dir {
+            zip.add_directory(name, file_options)?;
+        } else {
+            zip.start_file(name, file_options)?;
+            let mut f = File::open(path)?;
+            f.read_to_end(&mut buffer)?;
+            zip.write_all(&buffer)?;
+            buffer.clear();
+            done += 1;
+            progress(done, total);
+        }
+    }
+
+    zip.finish()?;
+    Ok(symlinks)
+}
+
+/// 一个打包任务
+#[derive(Debug, Clone)]
+pub struct ZipJob {
+    /// 项目名称，用于结果列表
+    pub name: String,
+    /// 要打包的目录
+    pub src_dir: PathBuf,
+    /// 输出的 ZIP 文件路径
+    pub dst_file: PathBuf,
+}
+
+/// 后台打包线程发出的事件
+pub enum ZipEvent {
+    /// 打包进度：(当前任务下标, 当前任务已写入的文件数, 当前任务的文件总数)
+    Progress(usize, usize, usize),
+    /// 全部任务结束，按任务顺序给出输出路径及遇到的符号链接，或错误信息
+    Done(Vec<Result<(PathBuf, SymlinkStats), String>>),
+}
+
+/// 在后台线程中依次执行打包任务，通过通道发送进度，最后发送 `ZipEvent::Done`
+///
+/// 丢弃接收端不会中断正在写入的压缩包，但后续的进度不再发送。
+pub fn spawn_zip(jobs: Vec<ZipJob>, options: ZipOptions) -> Receiver<ZipEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let results = jobs
+            .iter()
+            .enumerate()
+            .map(|(idx, job)| {
+                create_zip(&job.src_dir, &job.dst_file, &options, |done, total| {
+                    let _ = tx.send(ZipEvent::Progress(idx, done, total));
+                })
+                .map(|symlinks| (job.dst_file.clone(), symlinks))
+                .map_err(|e| e.to_string())
+            })
+            .collect();
+        let _ = tx.send(ZipEvent::Done(results));
+    });
+    rx
+}
+
+/// 判断 `path` 是否就是输出文件
+/// 先做直接比较；文件名相同（不区分大小写，兼容 Windows）时再比较规范化路径，
+/// 以兼容相对 / 绝对路径、`.`、`..` 等不同写法
+fn is_same_file(path: &Path, dst_file: &Path, dst_canonical: Option<&Path>) -> bool {
+    if path == dst_file {
+        return true;
+    }
+    let name = |p: &Path| p.file_name().map(|s| s.to_string_lossy().to_lowercase());
+    if name(path) != name(dst_file) {
+        return false;
+    }
+    match (fs::canonicalize(path).ok(), dst_canonical) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 读取压缩包中的所有条目名称
+    fn zip_entries(path: &Path) -> Vec<String> {
+        let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+        (0..archive.len())
+            .map(|i| archive.by_index(i).unwrap().name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_dedup_zip_paths() {
+        let (src, zip) = zip_plan(Path::new("root/game_a/build/project.config.json"), "").unwrap();
+        assert_eq!(src, Path::new("root/game_a"));
+        assert_eq!(zip, Path::new("root/game_a.zip"));
+
+        let mut paths = vec![
+            PathBuf::from("out/Game.zip"),
+            PathBuf::from("out/game.zip"),
+            PathBuf::from("out/Game.zip"),
+            PathBuf::from("out/Other.zip"),
+            PathBuf::from("out/Game.zip"),
+        ];
+        let renamed = dedup_zip_paths(&mut paths, &["tt1", "tt2", "tt2", "tt3", ""]);
+        assert_eq!(renamed, vec![1, 2, 4]);
+        assert_eq!(paths[0], Path::new("out/Game.zip"));
+        assert_eq!(paths[1], Path::new("out/game_tt2.zip"));
+        // AppID 也相同时使用序号
+        assert_eq!(paths[2], Path::new("out/Game_2.zip"));
+        assert_eq!(paths[3], Path::new("out/Other.zip"));
+        assert_eq!(paths[4], Path::new("out/Game_3.zip"));
+    }
+
+    #[test]
+    fn test_with_zip_extension() {
+        assert_eq!(with_zip_extension(PathBuf::from("out/Game.zip")), Path::new("out/Game.zip"));
+        assert_eq!(with_zip_extension(PathBuf::from("out/Game.ZIP")), Path::new("out/Game.ZIP"));
+        assert_eq!(with_zip_extension(PathBuf::from("out/Game")), Path::new("out/Game.zip"));
+        assert_eq!(with_zip_extension(PathBuf::from("out/v1.2")), Path::new("out/v1.2.zip"));
+    }
+
+    #[test]
+    fn test_spawn_zip_reports_progress() {
+        let src = Path::new("test_output_zip_spawn");
+        if src.exists() { fs::remove_dir_all(src).unwrap(); }
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("a.txt"), "a").unwrap();
+        fs::write(src.join("sub/b.txt"), "b").unwrap();
+        let jobs = vec![
+            ZipJob { name: "ok".to_string(), src_dir: src.to_path_buf(), dst_file: PathBuf::from("test_output_zip_spawn.zip") },
+            ZipJob { name: "bad".to_string(), src_dir: src.to_path_buf(), dst_file: src.join("missing/out.zip") },
+        ];
+
+        let rx = spawn_zip(jobs, ZipOptions::default());
+        let mut progress = Vec::new();
+        let results = loop {
+            match rx.recv().unwrap() {
+                ZipEvent::Progress(job, done, total) => progress.push((job, done, total)),
+                ZipEvent::Done(results) => break results,
+            }
+        };
+        assert_eq!(progress, vec![(0, 1, 2), (0, 2, 2)]);
+        assert_eq!(results[0].as_ref().unwrap().0, Path::new("test_output_zip_spawn.zip"));
+        // 失败的任务返回错误信息，不影响其他任务
+        assert!(results[1].is_err());
+
+        fs::remove_dir_all(src).unwrap();
+        fs::remove_file("test_output_zip_spawn.zip").unwrap();
+    }
+
+    #[test]
+    fn test_zip_compression() {
+        let src = Path::new("test_output_zip_compression");
+        if src.exists() { fs::remove_dir_all(src).unwrap(); }
+        fs::create_dir_all(src).unwrap();
+        fs::write(src.join("data.txt"), "abc".repeat(10_000)).unwrap();
+        let stored = Path::new("test_output_zip_stored.zip");
+        let best = Path::new("test_output_zip_best.zip");
+
+        create_zip(src, stored, &ZipOptions { compression: ZipCompression::Stored, ..ZipOptions::default() }, |_, _| {}).unwrap();
+        create_zip(src, best, &ZipOptions { compression: ZipCompression::Best, ..ZipOptions::default() }, |_, _| {}).unwrap();
+        let method = |path: &Path| {
+            let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+            let method = archive.by_name("data.txt").unwrap().compression();
+            method
+        };
+        assert_eq!(method(stored), CompressionMethod::Stored);
+        assert_eq!(method(best), CompressionMethod::Deflated);
+        assert!(fs::metadata(best).unwrap().len() < fs::metadata(stored).unwrap().len());
+
+        fs::remove_dir_all(src).unwrap();
+        fs::remove_file(stored).unwrap();
+        fs::remove_file(best).unwrap();
+    }
+
+    #[test]
+    fn test_zip_exclude_patterns() {
+        let src = Path::new("test_output_zip_exclude");
+        if src.exists() { fs::remove_dir_all(src).unwrap(); }
+        fs::create_dir_all(src.join(".git")).unwrap();
+        fs::create_dir_all(src.join("Library/cache")).unwrap();
+        fs::create_dir_all(src.join("art")).unwrap();
+        fs::write(src.join(".git/HEAD"), "ref").unwrap();
+        fs::write(src.join("Library/cache/a.bin"), "a").unwrap();
+        fs::write(src.join("art/hero.PSD"), "psd").unwrap();
+        fs::write(src.join("art/hero.png"), "png").unwrap();
+        fs::write(src.join(".DS_Store"), "").unwrap();
+        fs::write(src.join(NOTES_FILENAME), "{}").unwrap();
+        let dst = Path::new("test_output_zip_exclude.zip");
+
+        // 默认只排除 .git 等目录
+        create_zip(src, dst, &ZipOptions::default(), |_, _| {}).unwrap();
+        let entries = zip_entries(dst);
+        assert!(!entries.iter().any(|e| e.starts_with(".git")));
+        assert!(entries.iter().any(|e| e == "art/hero.PSD"));
+        assert!(!entries.iter().any(|e| e == NOTES_FILENAME));
+
+        // 自定义模式同时匹配目录名和文件名，不区分大小写
+        let options = ZipOptions {
+            exclude_patterns: vec!["library".to_string(), "*.psd".to_string(), ".DS_Store".to_string()],
+            ..ZipOptions::default()
+        };
+        create_zip(src, dst, &options, |_, _| {}).unwrap();
+        let entries = zip_entries(dst);
+        assert!(entries.iter().any(|e| e == "art/hero.png"));
+        assert!(entries.iter().any(|e| e.starts_with(".git")));
+        assert!(!entries.iter().any(|e| e.starts_with("Library")));
+        assert!(!entries.iter().any(|e| e.ends_with(".PSD") || e.ends_with(".DS_Store")));
+        // 备注文件不受排除模式影响，始终不打包
+        assert!(!entries.iter().any(|e| e == NOTES_FILENAME));
+
+        fs::remove_dir_all(src).unwrap();
+        fs::remove_file(dst).unwrap();
+    }
+
+    #[test]
+    fn test_zip_skips_nested_output() {
+        let src = Path::new("test_output_zip_nested");
+        if src.exists() { fs::remove_dir_all(src).unwrap(); }
+        fs::create_dir_all(src.join("build/out")).unwrap();
+        fs::write(src.join("a.txt"), "a").unwrap();
+
+        // 源目录用相对路径、输出用经过 ".." 的绝对路径，两者只有规范化后才相同
+        let dst = std::env::current_dir().unwrap().join(src).join("build/../build/out/game.zip");
+        let options = ZipOptions { skip_zip_files: false, ..ZipOptions::default() };
+        create_zip(src, &dst, &options, |_, _| {}).unwrap();
+        // 再打包一次，输出文件已存在时也不会被包含进去
+        create_zip(src, &dst, &options, |_, _| {}).unwrap();
+        let entries = zip_entries(&dst);
+        assert!(entries.iter().any(|e| e == "a.txt"));
+        assert!(!entries.iter().any(|e| e.ends_with("game.zip")));
+
+        fs::remove_dir_all(src).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_zip_follow_links() {
+        let base = Path::new("test_output_zip_links");
+        if base.exists() { fs::remove_dir_all(base).unwrap(); }
+        let src = base.join("game");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(base.join("shared")).unwrap();
+        fs::write(src.join("a.txt"), "a").unwrap();
+        fs::write(base.join("shared/b.txt"), "b").unwrap();
+        let abs = fs::canonicalize(base).unwrap();
+        std::os::unix::fs::symlink(abs.join("shared"), src.join("assets")).unwrap();
+        std::os::unix::fs::symlink(abs.join("shared/b.txt"), src.join("b_link.txt")).unwrap();
+        // 指向被打包目录本身的循环链接
+        std::os::unix::fs::symlink(abs.join("game"), src.join("loop")).unwrap();
+        let dst = base.join("out.zip");
+
+        // 不跟随：链接目录和链接文件都不打包
+        let stats = create_zip(&src, &dst, &ZipOptions::default(), |_, _| {}).unwrap();
+        assert_eq!(zip_entries(&dst), vec!["a.txt"]);
+        assert_eq!(stats, SymlinkStats { links: 3, loops: 0 });
+
+        // 跟随：打包链接指向的内容，循环链接被跳过
+        let options = ZipOptions { follow_links: true, ..ZipOptions::default() };
+        let stats = create_zip(&src, &dst, &options, |_, _| {}).unwrap();
+        let mut entries = zip_entries(&dst);
+        entries.sort();
+        assert_eq!(entries, vec!["a.txt", "assets/", "assets/b.txt", "b_link.txt"]);
+        assert_eq!(stats, SymlinkStats { links: 2, loops: 1 });
+
+        fs::remove_dir_all(base).unwrap();
+    }
+
+    #[test]
+    fn test_zip_failure_keeps_existing_output() {
+        let dst = Path::new("test_output_zip_failure.zip");
+        fs::write(dst, "old").unwrap();
+        // 源目录不存在，遍历出错
+        assert!(create_zip(Path::new("test_output_zip_failure_missing"), dst, &ZipOptions::default(), |_, _| {}).is_err());
+        // 已有的压缩包不被截断，也不留下未写完的临时文件
+        assert_eq!(fs::read_to_string(dst).unwrap(), "old");
+        assert!(!partial_path(dst).exists());
+        fs::remove_file(dst).unwrap();
+    }
+
+    #[test]
+    fn test_zip_skips_existing_archives_and_output() {
+        let src = Path::new("test_output_zip");
+        if src.exists() { fs::remove_dir_all(src).unwrap(); }
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("a.txt"), "a").unwrap();
+        fs::write(src.join("sub/old.ZIP"), "old").unwrap();
+
+        // 输出路径使用带 "." 的写法，验证规范化比较
+        let dst = src.join(".").join("out.zip");
+        create_zip(src, &dst, &ZipOptions::default(), |_, _| {}).unwrap();
+        let entries = zip_entries(&dst);
+        assert!(entries.iter().any(|e| e == "a.txt"));
+        assert!(!entries.iter().any(|e| e.ends_with("old.ZIP")));
+        assert!(!entries.iter().any(|e| e.ends_with("out.zip")));
+        assert!(!entries.iter().any(|e| e.ends_with(".part")));
+
+        // 关闭选项后保留已有压缩包，但仍然排除输出文件本身
+        create_zip(src, &dst, &ZipOptions { skip_zip_files: false, ..ZipOptions::default() }, |_, _| {}).unwrap();
+        let entries = zip_entries(&dst);
+        assert!(entries.iter().any(|e| e.ends_with("old.ZIP")));
+        assert!(!entries.iter().any(|e| e.ends_with("out.zip")));
+
+        fs::remove_dir_all(src).unwrap();
+    }
+}
@@ -0,0 +1,124 @@
+use crate::model::ProjectItem;
+use config_core::text::split_douyin_ids;
+use serde::{Deserialize, Serialize};
+
+/// 已保存的配置模板
+/// 用于一键把"标准配置"应用到选中的项目，保存在设置文件中（见 `settings` 模块）
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ConfigProfile {
+    /// 模板名称
+    pub name: String,
+    /// 要应用的 AppID，留空表示不修改
+    pub appid: String,
+    /// 项目名称模板，支持 `{folder}` 和 `{index}` 占位符，留空表示不修改
+    pub name_pattern: String,
+    /// 要应用的 DouyinIDs（逗号分隔），留空表示不修改
+    pub douyin_ids: String,
+}
+
+impl ConfigProfile {
+    /// 替换模板中的占位符
+    ///
+    /// * `{folder}` - 项目所在文件夹名称
+    /// * `{index}` - 项目在本次应用中的序号（从 1 开始）
+    pub fn expand(template: &str, folder: &str, index: usize) -> String {
+        template
+            .replace("{folder}", folder)
+            .replace("{index}", &index.to_string())
+    }
+
+    /// 将模板应用到单个项目
+    ///
+    /// # Returns
+    /// * `bool` - 项目的值是否被改变；模板中的值与项目现有的值相同时为 false
+    pub fn apply_to(&self, item: &mut ProjectItem, index: usize) -> bool {
+        let folder = item.path.parent()
+            .and_then(|p| p.file_name())
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut changed = false;
+
+        if !self.appid.trim().is_empty() {
+            let appid = Self::expand(self.appid.trim(), &folder, index);
+            if item.config.appid != appid {
+                item.config.appid = appid.clone();
+                changed = true;
+            }
+            // 与批量修改一致，同时更新 JS 中的 AppID
+            if let Some(js) = &mut item.js_config {
+                if js.app_id != appid {
+                    js.app_id = appid;
+                    changed = true;
+                }
+            }
+        }
+
+        if !self.name_pattern.trim().is_empty() {
+            let name = Self::expand(self.name_pattern.trim(), &folder, index);
+            if item.config.projectname != name {
+                item.config.projectname = name;
+                changed = true;
+            }
+        }
+
+        if !self.douyin_ids.trim().is_empty() {
+            if let Some(js) = &mut item.js_config {
+                let ids = split_douyin_ids(&self.douyin_ids);
+                if js.douyin_ids != ids {
+                    js.douyin_ids = ids;
+                    js.sync_str_from_ids();
+                    changed = true;
+                }
+            }
+        }
+
+        if changed {
+            item.refresh_modified();
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{JsConfig, ProjectConfig};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_expand_tokens() {
+        assert_eq!(ConfigProfile::expand("{folder}-{index}", "game_a", 3), "game_a-3");
+        assert_eq!(ConfigProfile::expand("固定名称", "game_a", 1), "固定名称");
+    }
+
+    #[test]
+    fn test_apply_to_reports_real_changes() {
+        let config = ProjectConfig {
+            appid: "tt1".to_string(),
+            projectname: "game_a".to_string(),
+            extra: serde_json::json!({}),
+        };
+        let js = JsConfig {
+            app_id: "tt1".to_string(),
+            douyin_ids: vec!["a".to_string(), "b".to_string()],
+            douyin_ids_str: "a,b".to_string(),
+            ..Default::default()
+        };
+        let mut item = ProjectItem::new(PathBuf::from("game_a/project.config.json"), config, None, Some(js), Vec::new());
+
+        // 模板的值与项目现有的值相同：不算修改
+        let same = ConfigProfile {
+            appid: "tt1".to_string(),
+            name_pattern: "{folder}".to_string(),
+            douyin_ids: "\"a\", b".to_string(),
+            ..Default::default()
+        };
+        assert!(!same.apply_to(&mut item, 1));
+        assert!(!item.is_modified);
+
+        let other = ConfigProfile { douyin_ids: "a,c".to_string(), ..same };
+        assert!(other.apply_to(&mut item, 1));
+        assert_eq!(item.js_config.as_ref().unwrap().douyin_ids_str, "a,c");
+        assert!(item.is_modified);
+    }
+}
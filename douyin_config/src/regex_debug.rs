@@ -0,0 +1,126 @@
+use config_core::i18n::{tr, trf};
+use config_core::js_patch::{
+    app_id_regex, find_app_id, find_id_arrays, parse_id_list, replace_app_id, replace_id_array, unescape_js_string,
+    DOUYIN_IDS_FIELD,
+};
+use config_core::text::split_douyin_ids;
+use eframe::egui;
+
+/// 正则调试面板的状态
+/// 粘贴一段 JS，查看当前字段名下的匹配结果和替换后的内容，不读写任何文件
+#[derive(Debug, Default)]
+pub struct RegexDebug {
+    /// 是否显示面板
+    pub open: bool,
+    /// 粘贴的 JS 片段
+    snippet: String,
+    /// 试替换的 AppID，为空时不替换
+    app_id: String,
+    /// 试替换的 DouyinIDs（逗号分隔），为空时不替换
+    douyin_ids: String,
+}
+
+/// 对 JS 片段执行一次匹配与替换的结果
+#[derive(Debug, PartialEq)]
+pub struct PatchPreview {
+    /// 识别到的 AppID
+    pub app_id: Option<String>,
+    /// 识别到的 ID 数组（字段名及解析后的元素）
+    pub id_arrays: Vec<(String, Vec<String>)>,
+    /// 替换后的内容，与保存时使用相同的函数
+    pub output: String,
+}
+
+/// 在 JS 片段上执行与扫描、保存相同的匹配和替换
+pub fn preview(snippet: &str, field: &str, app_id: &str, douyin_ids: &str) -> PatchPreview {
+    let mut output = snippet.to_string();
+    if !app_id.trim().is_empty() {
+        output = replace_app_id(&output, field, app_id.trim());
+    }
+    if !douyin_ids.trim().is_empty() {
+        output = replace_id_array(&output, DOUYIN_IDS_FIELD, &split_douyin_ids(douyin_ids));
+    }
+    PatchPreview {
+        app_id: find_app_id(snippet, field).map(|range| unescape_js_string(&snippet[range])),
+        id_arrays: find_id_arrays(snippet)
+            .into_iter()
+            .map(|(name, range)| (name, parse_id_list(&snippet[range])))
+            .collect(),
+        output,
+    }
+}
+
+impl RegexDebug {
+    /// 绘制调试窗口
+    pub fn show(&mut self, ctx: &egui::Context, field: &str) {
+        let mut open = self.open;
+        egui::Window::new(tr("🧪 正则调试"))
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new(trf("AppID 正则: {}", &[&app_id_regex(field).as_str()])).monospace().small());
+                ui.label(tr("JS 片段:"));
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.snippet)
+                        .code_editor()
+                        .desired_rows(6)
+                        .desired_width(f32::INFINITY)
+                        .hint_text(tr("粘贴打包后 JS 中的一段内容，如 d.appId=\"tt123\",d.douyinIds=[\"a\"]")),
+                );
+                egui::Grid::new("regex_debug_inputs").num_columns(2).show(ui, |ui| {
+                    ui.label(tr("替换 AppID:"));
+                    ui.text_edit_singleline(&mut self.app_id);
+                    ui.end_row();
+                    ui.label(tr("替换 DouyinIDs:"));
+                    ui.text_edit_singleline(&mut self.douyin_ids);
+                    ui.end_row();
+                });
+
+                let result = preview(&self.snippet, field, &self.app_id, &self.douyin_ids);
+                ui.separator();
+                match &result.app_id {
+                    Some(app_id) => ui.label(format!("✔ AppID: {}", app_id)),
+                    None => ui.colored_label(egui::Color32::from_rgb(220, 140, 0), trf("✖ 未匹配到 .{}=\"...\"", &[&field])),
+                };
+                if result.id_arrays.is_empty() {
+                    ui.colored_label(egui::Color32::from_rgb(220, 140, 0), tr("✖ 未匹配到 .xxxIds=[...]"));
+                }
+                for (name, ids) in &result.id_arrays {
+                    ui.label(format!("✔ {}: {:?}", name, ids));
+                }
+                ui.label(tr("替换结果:"));
+                let mut output = result.output;
+                ui.add(
+                    egui::TextEdit::multiline(&mut output)
+                        .code_editor()
+                        .interactive(false)
+                        .desired_rows(6)
+                        .desired_width(f32::INFINITY),
+                );
+            });
+        self.open = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview() {
+        let snippet = r#"var myappId="x";d.appId='tt_old',d.douyinIds=["a", "b"],d.rewardIds=[]"#;
+        let result = preview(snippet, "appId", "tt_new", "c, d");
+        assert_eq!(result.app_id.as_deref(), Some("tt_old"));
+        assert_eq!(
+            result.id_arrays,
+            vec![
+                ("douyinIds".to_string(), vec!["a".to_string(), "b".to_string()]),
+                ("rewardIds".to_string(), Vec::new()),
+            ]
+        );
+        assert_eq!(result.output, r#"var myappId="x";d.appId='tt_new',d.douyinIds=["c", "d"],d.rewardIds=[]"#);
+
+        // 未填写替换值时内容不变
+        assert_eq!(preview(snippet, "appId", "", "").output, snippet);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::model::ProjectItem;
+use anyhow::bail;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 文件夹名称中不允许出现的字符（按 Windows 的规则，兼顾其它平台）
+const INVALID_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// 将项目名称转换为合法的文件夹名称
+/// 非法字符和控制字符替换为 `_`，并去掉两端的空格和末尾的 `.`（Windows 不允许）
+pub fn sanitize_folder_name(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| if INVALID_CHARS.contains(&c) || c.is_control() { '_' } else { c })
+        .collect();
+    replaced.trim().trim_end_matches('.').trim_end().to_string()
+}
+
+/// 如果 `path` 位于 `from` 目录下，返回其在 `to` 目录下对应的新路径
+fn rebase(path: &Path, from: &Path, to: &Path) -> Option<PathBuf> {
+    path.strip_prefix(from).ok().map(|rest| to.join(rest))
+}
+
+/// 目录被重命名后，更新项目中所有位于该目录下的路径
+/// 纹理缓存以旧路径为键，路径变化后需要由调用方使其失效
+///
+/// # Returns
+/// * `bool` - 项目中是否有路径发生了变化
+pub fn rebase_item_paths(item: &mut ProjectItem, from: &Path, to: &Path) -> bool {
+    let mut changed = false;
+    if let Some(path) = rebase(&item.path, from, to) {
+        item.path = path;
+        changed = true;
+    }
+    if let Some(js_path) = item.js_path.as_ref().and_then(|p| rebase(p, from, to)) {
+        item.js_path = Some(js_path);
+        changed = true;
+    }
+    for img in &mut item.image_paths {
+        if let Some(path) = rebase(img, from, to) {
+            *img = path;
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// 将项目配置文件所在的文件夹重命名为（清理后的）项目名称
+///
+/// 目标名称已被占用时依次尝试 `名称_2`、`名称_3` ...，不会覆盖已有目录。
+/// 重命名成功后会更新 `item` 中的路径。
+///
+/// # Returns
+/// * `Ok(Some((old, new)))` - 文件夹的旧路径和新路径
+/// * `Ok(None)` - 文件夹名称已与项目名称一致（或为带后缀的同名文件夹），无需重命名
+pub fn rename_project_folder(item: &mut ProjectItem) -> anyhow::Result<Option<(PathBuf, PathBuf)>> {
+    let name = sanitize_folder_name(&item.config.projectname);
+    if name.is_empty() {
+        bail!("项目名称为空，无法用作文件夹名称");
+    }
+    let Some(dir) = item.path.parent().map(Path::to_path_buf) else {
+        bail!("无法获取配置文件所在目录");
+    };
+    let Some(parent) = dir.parent() else {
+        bail!("无法获取 {} 的上级目录", dir.display());
+    };
+
+    let mut target = parent.join(&name);
+    let mut suffix = 1;
+    while target.exists() {
+        // 之前已重命名过（含冲突后缀），保持不变
+        if target == dir {
+            return Ok(None);
+        }
+        suffix += 1;
+        target = parent.join(format!("{}_{}", name, suffix));
+    }
+    fs::rename(&dir, &target)?;
+    rebase_item_paths(item, &dir, &target);
+    Ok(Some((dir, target)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ProjectConfig;
+
+    #[test]
+    fn test_sanitize_folder_name() {
+        assert_eq!(sanitize_folder_name("消消乐: 第2版?"), "消消乐_ 第2版_");
+        assert_eq!(sanitize_folder_name("  Game.. "), "Game");
+        assert_eq!(sanitize_folder_name("a/b\\c"), "a_b_c");
+    }
+
+    #[test]
+    fn test_rename_with_collision() {
+        let test_dir = Path::new("test_output_rename");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        let project_dir = test_dir.join("old_folder");
+        fs::create_dir_all(project_dir.join("assets")).unwrap();
+        // 目标名称已被占用
+        fs::create_dir_all(test_dir.join("Game")).unwrap();
+        let config_path = project_dir.join("project.config.json");
+        fs::write(&config_path, r#"{"projectname": "Game"}"#).unwrap();
+        let js_path = project_dir.join("assets").join("index.js");
+        fs::write(&js_path, "").unwrap();
+
+        let config: ProjectConfig = serde_json::from_str(r#"{"projectname": "Game"}"#).unwrap();
+        let mut item = ProjectItem::new(config_path, config, Some(js_path), None, Vec::new());
+        let (old, new) = rename_project_folder(&mut item).unwrap().unwrap();
+
+        assert_eq!(old, project_dir);
+        assert_eq!(new, test_dir.join("Game_2"));
+        assert!(!project_dir.exists());
+        assert_eq!(item.path, new.join("project.config.json"));
+        assert_eq!(item.js_path, Some(new.join("assets").join("index.js")));
+        assert!(item.path.exists());
+        // 路径已更新过，再次调整时没有变化
+        assert!(!rebase_item_paths(&mut item, &old, &new));
+
+        // 名称已一致时不再重命名
+        assert!(rename_project_folder(&mut item).unwrap().is_none());
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+}
@@ -1,33 +1,241 @@
-use crate::model::{ProjectConfig, ProjectItem, JsConfig};
+use crate::model::{IdArray, JsMatch, ProjectConfig, ProjectItem, JsConfig};
+use config_core::apply::{apply_js_changes, apply_to_config_file, JsChanges, CONFIG_FILENAMES};
+use config_core::backup::Backups;
+use config_core::error::ConfigError;
+use config_core::image_probe::{image_size, is_image_file, PREVIEW_WIDTHS};
+use config_core::config_file::read_config;
+use config_core::js_patch::{
+    app_id_field, find_app_id, find_id_arrays, parse_id_list, unescape_js_string,
+    DEFAULT_APP_ID_FIELD, DOUYIN_IDS_FIELD,
+};
+use config_core::encoding::read_text_detect;
+use config_core::text::{dedup_ids, split_douyin_ids, wildcard_match};
+use config_core::walk::{is_hidden, walk_logged, SymlinkStats};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
-use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use rayon::prelude::*;
 
 use walkdir::WalkDir;
 
-const CONFIG_FILENAME: &str = "project.config.json";
+
+/// JS 匹配预览中，匹配位置前后各保留的字符数
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+
+/// 扫描选项
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// 探测预览图尺寸时使用的并行线程数
+    /// 机械硬盘上可以调低以避免磁头来回寻道
+    pub probe_threads: usize,
+    /// 是否跳过隐藏文件和目录（以 `.` 开头，如 `.cache`、`.vscode`、`.git`）
+    pub skip_hidden: bool,
+    /// 是否只保留找到 JS 配置的项目（只有 project.config.json 的项目不加入结果）
+    pub js_only: bool,
+    /// 是否按宽度（`PREVIEW_WIDTHS`，即 750px）识别预览图
+    pub match_width: bool,
+    /// 按文件名识别预览图的通配符模式（如 `preview.*`、`cover.jpg`），不区分大小写
+    /// 与宽度规则为"或"的关系：满足任意一条即视为预览图
+    pub name_patterns: Vec<String>,
+    /// JS 中 AppID 的字段名（默认 `appId`），部分打包产物使用 `aid`、`applicationId` 等
+    pub app_id_field: String,
+    /// 扫描时不查找预览图，等到在界面上展开项目的预览图区域时再查找（`probe_preview_images`）
+    /// 素材很多的项目目录遍历和读取图片文件头很耗时，延迟后扫描只需解析配置
+    pub lazy_images: bool,
+    /// 是否跟随符号链接（目录联接）进入链接指向的目录
+    /// 关闭时链接本身被忽略；开启时指向上级目录的循环链接会被检测到并跳过
+    pub follow_links: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            probe_threads: default_probe_threads(),
+            skip_hidden: true,
+            js_only: false,
+            match_width: true,
+            name_patterns: Vec::new(),
+            app_id_field: DEFAULT_APP_ID_FIELD.to_string(),
+            lazy_images: true,
+            follow_links: false,
+        }
+    }
+}
+
+/// 默认的图片探测线程数（CPU 核心数）
+pub fn default_probe_threads() -> usize {
+    std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+/// 保存选项
+#[derive(Debug, Clone)]
+pub struct SaveOptions {
+    /// 是否把写入的赋值语句规范为 `.appId = "x"`、`.douyinIds = [...]` 的空格风格
+    /// 只处理本工具修改的字段，不会格式化整个压缩后的 JS 文件
+    pub format_assignments: bool,
+    /// JS 中 AppID 的字段名，应与扫描时使用的 `ScanOptions::app_id_field` 一致
+    pub app_id_field: String,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            format_assignments: false,
+            app_id_field: DEFAULT_APP_ID_FIELD.to_string(),
+        }
+    }
+}
+
+/// 图片尺寸缓存中的一项：文件的修改时间、大小以及读取到的尺寸
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CachedSize {
+    modified: Option<SystemTime>,
+    len: u64,
+    size: Option<(u32, u32)>,
+}
+
+/// 图片尺寸缓存
+///
+/// 按路径记录每张候选图片的元数据和尺寸。重新扫描时修改时间和文件大小都未变化的图片
+/// 直接复用上次的尺寸，不再读取文件；元数据变化或已不存在的图片在新缓存中被替换或丢弃。
+#[derive(Debug, Default, Clone)]
+pub struct ProbeCache {
+    entries: HashMap<PathBuf, CachedSize>,
+}
+
+impl ProbeCache {
+    /// 合并一次未完成扫描的缓存：新记录覆盖旧记录，未扫描到的图片保留旧记录
+    pub fn merge(&mut self, newer: ProbeCache) {
+        self.entries.extend(newer.entries);
+    }
+}
+
+/// 一次扫描的统计信息（项目本身在扫描过程中逐个交出）
+pub struct ScanReport {
+    /// 实际用于图片探测的线程数
+    pub probe_threads: usize,
+    /// 目录遍历与配置解析的耗时
+    pub walk_duration: Duration,
+    /// 预览图尺寸探测的耗时
+    pub probe_duration: Duration,
+    /// 遍历时因无法访问（权限不足、断开的链接等）而跳过的条目数
+    pub skipped_entries: usize,
+    /// 遍历中遇到的符号链接（是否进入取决于 `ScanOptions::follow_links`）
+    pub symlinks: SymlinkStats,
+    /// 开启 `js_only` 时，因没有 JS 配置而未加入结果的项目数
+    pub skipped_without_js: usize,
+    /// 元数据未变化、直接复用缓存尺寸的图片数
+    pub reused_probes: usize,
+    /// 本次扫描的图片尺寸缓存，供下一次扫描使用
+    pub probe_cache: ProbeCache,
+    /// 扫描是否被取消（此时只包含取消前找到的项目）
+    pub cancelled: bool,
+}
+
+impl ScanReport {
+    /// 扫描总耗时
+    pub fn total_duration(&self) -> Duration {
+        self.walk_duration + self.probe_duration
+    }
+}
+
+/// 后台扫描线程发出的事件
+pub enum ScanEvent {
+    /// 扫描进度：(已处理的配置文件数, 配置文件总数)
+    Progress(usize, usize),
+    /// 找到一个项目
+    Project(Box<ProjectItem>),
+    /// 扫描结束
+    Done(ScanReport),
+}
+
+/// 在后台线程中扫描目录，每找到一个项目就通过通道发送，最后发送 `ScanEvent::Done`
+///
+/// 设置 `cancel` 或丢弃接收端（例如扫描途中又点了刷新）后，扫描线程会尽快停止。
+pub fn spawn_scan(root: PathBuf, options: ScanOptions, cache: ProbeCache, cancel: Arc<AtomicBool>) -> Receiver<ScanEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let report = scan_directory(&root, &options, &cache, &cancel, |event| tx.send(event).is_ok());
+        let _ = tx.send(ScanEvent::Done(report));
+    });
+    rx
+}
 
 /// 扫描指定目录下的配置文件
 /// 
-/// 该函数会递归遍历目录，寻找 `project.config.json` 文件。
+/// 该函数会递归遍历目录，寻找 `project.config.json` 文件（或 `CONFIG_FILENAMES` 中的 YAML / TOML 变体）。
 /// 找到配置文件后，会尝试进一步查找关联的 JS 配置文件（如 `assets/main/index.js`）
 /// 以及项目中的预览图片（宽度为 750px 的图片）。
 ///
 /// # Arguments
 /// * `root` - 要扫描的根目录路径
+/// * `options` - 扫描选项（如图片探测线程数）
+/// * `cache` - 上一次扫描的图片尺寸缓存（首次扫描传入空缓存），
+///   修改时间和文件大小未变化的图片不再重新读取，刷新大部分文件未变的大目录时更快
+/// * `cancel` - 取消标志，遍历每个条目前检查，被设置后停止扫描
+/// * `emit` - 每找到一个项目或进度变化时调用（`ScanEvent::Project` / `ScanEvent::Progress`），
+///   返回 false 时提前结束扫描
 ///
 /// # Returns
-/// * `Vec<ProjectItem>` - 扫描到的项目列表
-pub fn scan_directory(root: &Path) -> Vec<ProjectItem> {
-    let mut results = Vec::new();
+/// * `ScanReport` - 扫描信息
+fn scan_directory(
+    root: &Path,
+    options: &ScanOptions,
+    cache: &ProbeCache,
+    cancel: &AtomicBool,
+    mut emit: impl FnMut(ScanEvent) -> bool,
+) -> ScanReport {
+    let mut probe_cache = ProbeCache::default();
+    let mut reused_probes = 0;
+    let started = Instant::now();
+    let mut probe_duration = Duration::ZERO;
+    let mut skipped_entries = 0;
+    let mut skipped_without_js = 0;
+
+    // 图片探测是扫描中最耗时的部分，使用独立的线程池并行处理
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.probe_threads.max(1))
+        .build()
+        .ok();
     
-    // min_depth(1) 避免扫描根目录本身（如果根目录本身就是项目目录，可以改为0，但通常是选父级）
-    // max_depth(5) 限制深度，防止遍历太深导致性能问题或不相关的扫描
-    for entry in WalkDir::new(root).min_depth(1).max_depth(5).into_iter().filter_map(|e| e.ok()) {
-        if entry.file_name() == CONFIG_FILENAME {
+    // 先快速遍历一遍统计配置文件总数，用于显示进度；只比较文件名，不读取内容
+    let total = project_walker(root, options)
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !skip_symlink(entry, options))
+        .take_while(|_| !cancel.load(Ordering::Relaxed))
+        .filter(is_config_entry)
+        .count();
+    let mut processed = 0;
+    let mut cancelled = false;
+
+    // 单个条目出错时只跳过该条目，其同级目录仍会继续遍历
+    let mut walk_skipped = 0;
+    let mut symlinks = SymlinkStats::default();
+    let walker = project_walker(root, options).inspect(|entry| symlinks.observe(entry));
+    for entry in walk_logged(walker, &mut walk_skipped) {
+        if cancel.load(Ordering::Relaxed) {
+            cancelled = true;
+            break;
+        }
+        if skip_symlink(&entry, options) {
+            continue;
+        }
+        if is_config_entry(&entry) {
+            processed += 1;
+            if !emit(ScanEvent::Progress(processed, total.max(processed))) {
+                break;
+            }
             let path = entry.path().to_path_buf();
-            // 尝试加载 JSON 配置
-            if let Ok(config) = load_config(&path) {
+            // 尝试加载配置（格式由扩展名决定）
+            if let Ok(mut config) = load_config(&path) {
+                // 名称被多余的引号包裹时自动修正，并标记为待保存
+                let raw_name = config.projectname.clone();
+                let name_fixed = config.normalize_name();
                 // 尝试查找关联的 JS 文件
                 let mut js_path = None;
                 if let Some(parent) = path.parent() {
@@ -37,10 +245,10 @@ pub fn scan_directory(root: &Path) -> Vec<ProjectItem> {
                     for candidate in candidates {
                         let target = parent.join(candidate);
                         if target.exists() {
-                            // 简单的预检查：读取文件内容，检查是否包含 appId 或 douyinIds 关键字
+                            // 简单的预检查：读取文件内容，检查是否包含 AppID 字段名或 douyinIds 关键字
                             // 这样可以避免解析无关的 JS 文件
-                            if let Ok(content) = fs::read_to_string(&target) {
-                                if content.contains("appId") || content.contains("douyinIds") {
+                            if let Ok((content, _)) = read_text_detect(&target) {
+                                if content.contains(app_id_field(&options.app_id_field)) || content.contains("douyinIds") {
                                     js_path = Some(target);
                                     break;
                                 }
@@ -52,92 +260,287 @@ pub fn scan_directory(root: &Path) -> Vec<ProjectItem> {
                 // 如果找到了 JS 文件，尝试解析其中的配置
                 let mut js_config = None;
                 if let Some(ref p) = js_path {
-                    if let Ok(cfg) = load_js_config(p) {
+                    if let Ok(cfg) = load_js_config(p, &options.app_id_field) {
                         js_config = Some(cfg);
                     } else {
                         eprintln!("Failed to load JS config from {:?}", p);
                     }
                 }
+                // 只关心 JS 配置时，跳过其余项目（也省去图片探测）
+                if options.js_only && js_config.is_none() {
+                    skipped_without_js += 1;
+                    continue;
+                }
 
-                // 查找预览图片 (匹配任意图片文件)
-                // 策略：扫描项目根目录下的所有文件，寻找符合特定条件的图片
-                let mut image_paths = Vec::new();
-                if let Some(project_root) = path.parent() {
-                     for entry in WalkDir::new(project_root).into_iter().filter_map(|e| e.ok()) {
-                        let p = entry.path();
-                        if p.is_file() {
-                             if let Some(ext) = p.extension().and_then(|e| e.to_str()) {
-                                match ext.to_lowercase().as_str() {
-                                    "png" | "jpg" | "jpeg" | "bmp" | "webp" => {
-                                        // 检查图片宽度是否为 750
-                                        // 这是一个特定的业务规则，用于识别特定的预览图
-                                        if let Ok(img) = image::open(p) {
-                                            if img.width() == 750 {
-                                                image_paths.push(p.to_path_buf());
-                                            }
-                                        }
-                                    }
-                                    _ => {}
-                                }
-                             }
+                // 延迟查找时先不处理预览图，由界面按需调用 `probe_preview_images`
+                let (image_paths, image_sizes) = if options.lazy_images {
+                    (Vec::new(), Vec::new())
+                } else {
+                    // 查找预览图片 (匹配任意图片文件)
+                    // 策略：先收集项目目录下的所有图片文件，再并行检查尺寸
+                    let candidates = match path.parent() {
+                        Some(project_root) => image_candidates(project_root, options, &mut skipped_entries),
+                        None => Vec::new(),
+                    };
+                    let probe_started = Instant::now();
+                    let probes: Vec<Probe> = match &pool {
+                        Some(pool) => pool.install(|| {
+                            candidates.into_par_iter().map(|p| probe_image(p, options, cache)).collect()
+                        }),
+                        None => candidates.into_iter().map(|p| probe_image(p, options, cache)).collect(),
+                    };
+                    probe_duration += probe_started.elapsed();
+                    let mut image_paths = Vec::new();
+                    let mut image_sizes = Vec::new();
+                    for probe in probes {
+                        reused_probes += probe.reused as usize;
+                        if probe.is_preview {
+                            image_paths.push(probe.path.clone());
+                            image_sizes.push(probe.cached.size);
                         }
-                     }
-                }
+                        probe_cache.entries.insert(probe.path, probe.cached);
+                    }
+                    (image_paths, image_sizes)
+                };
 
                 // 构建完整的项目项并添加到结果列表
-                results.push(ProjectItem {
-                    path,
-                    config,
-                    js_path,
-                    js_config,
-                    image_paths,
-                    is_modified: false,
-                    selected: true, // 默认选中，方便用户直接进行批量操作
-                    texture_cache: std::collections::HashMap::new(),
-                });
+                let mut item = ProjectItem::new(path, config, js_path, js_config, image_paths);
+                item.image_sizes = image_sizes;
+                item.images_probed = !options.lazy_images;
+                if name_fixed {
+                    // 快照保留磁盘上的原始名称，使修正后的名称被视为待保存的修改
+                    item.original.config.projectname = raw_name;
+                    item.refresh_modified();
+                }
+                if !emit(ScanEvent::Project(Box::new(item))) {
+                    break;
+                }
             }
         }
     }
-    results
+
+    ScanReport {
+        probe_threads: pool.as_ref().map_or(1, |p| p.current_num_threads()),
+        walk_duration: started.elapsed().saturating_sub(probe_duration),
+        probe_duration,
+        skipped_entries: skipped_entries + walk_skipped,
+        symlinks,
+        skipped_without_js,
+        reused_probes,
+        probe_cache,
+        cancelled,
+    }
 }
 
-/// 加载并解析 project.config.json 文件
+/// 遍历扫描根目录，查找项目配置文件
+/// min_depth(1) 避免扫描根目录本身（如果根目录本身就是项目目录，可以改为0，但通常是选父级）
+/// max_depth(5) 限制深度，防止遍历太深导致性能问题或不相关的扫描
+/// 开启 `follow_links` 时进入符号链接指向的目录，循环链接以错误形式出现并被跳过
+fn project_walker(root: &Path, options: &ScanOptions) -> impl Iterator<Item = walkdir::Result<walkdir::DirEntry>> {
+    let skip_hidden = options.skip_hidden;
+    let keep = move |e: &walkdir::DirEntry| !(skip_hidden && is_hidden(e));
+    WalkDir::new(root)
+        .min_depth(1)
+        .max_depth(5)
+        .follow_links(options.follow_links)
+        .into_iter()
+        .filter_entry(keep)
+}
+
+/// 不跟随符号链接时跳过链接本身（包括指向文件的链接），与不进入链接目录的行为保持一致
+/// 遍历的起点本身不算，即使用户选择的根目录是一个链接
+fn skip_symlink(entry: &walkdir::DirEntry, options: &ScanOptions) -> bool {
+    !options.follow_links && entry.depth() > 0 && entry.path_is_symlink()
+}
+
+/// 条目是否为项目配置文件（`CONFIG_FILENAMES` 之一）
+fn is_config_entry(entry: &walkdir::DirEntry) -> bool {
+    CONFIG_FILENAMES.iter().any(|name| entry.file_name() == *name)
+}
+
+/// 单张候选图片的探测结果
+struct Probe {
+    path: PathBuf,
+    cached: CachedSize,
+    /// 尺寸是否取自缓存
+    reused: bool,
+    /// 是否为预览图
+    is_preview: bool,
+}
+
+/// 检查图片是否为预览图：文件名匹配任一模式，或宽度为 750（无法读取的图片视为不匹配）
+/// 只读取图片文件头获取尺寸，不解码像素；尺寸随结果返回，供宽高比校验使用。
+/// 缓存中有修改时间和大小都相同的记录时直接复用其尺寸。
+fn probe_image(path: PathBuf, options: &ScanOptions, cache: &ProbeCache) -> Probe {
+    let metadata = fs::metadata(&path).ok();
+    let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+    let len = metadata.map_or(0, |m| m.len());
+    let previous = cache.entries.get(&path)
+        .filter(|entry| modified.is_some() && entry.modified == modified && entry.len == len);
+    let reused = previous.is_some();
+    let cached = previous.copied().unwrap_or_else(|| CachedSize {
+        modified,
+        len,
+        size: image_size(&path).ok(),
+    });
+
+    let name_matched = path.file_name().is_some_and(|name| {
+        let name = name.to_string_lossy();
+        options.name_patterns.iter().any(|pattern| wildcard_match(pattern, &name))
+    });
+    let width_matched = options.match_width && cached.size.is_some_and(|(width, _)| PREVIEW_WIDTHS.contains(&width));
+    Probe { path, cached, reused, is_preview: name_matched || width_matched }
+}
+
+/// 收集项目目录下的所有图片文件，尚未检查尺寸
+fn image_candidates(project_root: &Path, options: &ScanOptions, skipped: &mut usize) -> Vec<PathBuf> {
+    let skip_hidden = options.skip_hidden;
+    let keep = move |e: &walkdir::DirEntry| !(skip_hidden && is_hidden(e));
+    let walker = WalkDir::new(project_root).follow_links(options.follow_links).into_iter().filter_entry(keep);
+    walk_logged(walker, skipped)
+        .filter(|entry| !skip_symlink(entry, options))
+        .filter(|entry| entry.path().is_file() && is_image_file(entry.path()))
+        .map(|entry| entry.path().to_path_buf())
+        .collect()
+}
+
+/// 后台查找单个项目预览图的结果
+pub struct ProbeOutcome {
+    /// 预览图路径
+    pub image_paths: Vec<PathBuf>,
+    /// 与 `image_paths` 一一对应的尺寸
+    pub image_sizes: Vec<Option<(u32, u32)>>,
+    /// 记入了本次探测结果的缓存，由调用方合并回去
+    pub cache: ProbeCache,
+}
+
+/// 在后台线程中查找单个项目目录下的预览图，完成后结果通过返回的 `Receiver` 发送一次
+///
+/// 延迟查找模式下展开卡片时使用，避免遍历素材目录时卡住界面。
+pub fn spawn_probe(project_root: PathBuf, options: ScanOptions, mut cache: ProbeCache) -> Receiver<ProbeOutcome> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (image_paths, image_sizes) = probe_preview_images(&project_root, &options, &mut cache);
+        // 接收端已被丢弃（例如重新扫描）时发送失败，直接忽略
+        let _ = tx.send(ProbeOutcome { image_paths, image_sizes, cache });
+    });
+    rx
+}
+
+/// 查找单个项目目录下的预览图
+/// 只处理一个项目，不使用线程池；只读取图片文件头，探测结果同时记入 `cache`
+///
+/// # Returns
+/// * `(Vec<PathBuf>, Vec<Option<(u32, u32)>>)` - 预览图路径及对应的尺寸
+pub fn probe_preview_images(
+    project_root: &Path,
+    options: &ScanOptions,
+    cache: &mut ProbeCache,
+) -> (Vec<PathBuf>, Vec<Option<(u32, u32)>>) {
+    let mut skipped = 0;
+    let mut image_paths = Vec::new();
+    let mut image_sizes = Vec::new();
+    for path in image_candidates(project_root, options, &mut skipped) {
+        let probe = probe_image(path, options, cache);
+        if probe.is_preview {
+            image_paths.push(probe.path.clone());
+            image_sizes.push(probe.cached.size);
+        }
+        cache.entries.insert(probe.path, probe.cached);
+    }
+    (image_paths, image_sizes)
+}
+
+/// 查找共享同一个 JS 文件的项目
+///
+/// 如果多个项目的 `js_path` 指向同一个物理文件（例如通过共享的 assets 目录或符号链接），
+/// 修改其中一个项目的 JS 配置会影响到其他项目。路径会先规范化再比较。
+///
+/// # Returns
+/// * `Vec<Vec<usize>>` - 每组为共享同一 JS 文件的项目下标（仅包含两个及以上项目的组）
+pub fn find_shared_js(projects: &[ProjectItem]) -> Vec<Vec<usize>> {
+    let mut groups: Vec<(std::path::PathBuf, Vec<usize>)> = Vec::new();
+    for (idx, item) in projects.iter().enumerate() {
+        if let Some(js_path) = &item.js_path {
+            let key = fs::canonicalize(js_path).unwrap_or_else(|_| js_path.clone());
+            match groups.iter_mut().find(|(p, _)| *p == key) {
+                Some((_, members)) => members.push(idx),
+                None => groups.push((key, vec![idx])),
+            }
+        }
+    }
+    groups.into_iter()
+        .map(|(_, members)| members)
+        .filter(|members| members.len() > 1)
+        .collect()
+}
+
+/// 比较两次扫描的结果，找出磁盘上的值发生变化的项目
+///
+/// 按配置文件路径对应前后两次的项目，比较各自从磁盘加载时的快照（不含未保存的编辑），
+/// 用于在重新扫描后提示用户哪些项目被外部修改过。新增或消失的项目不计入。
+///
+/// # Returns
+/// * `Vec<(usize, Vec<&'static str>)>` - `current` 中的项目下标及发生变化的字段名（中文原文，显示时用 `tr` 翻译）
+pub fn diff_disk_changes(previous: &[ProjectItem], current: &[ProjectItem]) -> Vec<(usize, Vec<&'static str>)> {
+    let mut changes = Vec::new();
+    for (idx, item) in current.iter().enumerate() {
+        let Some(prev) = previous.iter().find(|p| p.path == item.path) else {
+            continue;
+        };
+        let (old, new) = (&prev.original, &item.original);
+        let mut fields = Vec::new();
+        let old_js_app_id = old.js_config.as_ref().map(|js| js.app_id.as_str());
+        let new_js_app_id = new.js_config.as_ref().map(|js| js.app_id.as_str());
+        if old.config.appid != new.config.appid || old_js_app_id != new_js_app_id {
+            fields.push("AppID");
+        }
+        if old.config.projectname != new.config.projectname {
+            fields.push("项目名称");
+        }
+        let old_ids = old.js_config.as_ref().map(|js| (&js.douyin_ids, &js.extra_id_arrays));
+        let new_ids = new.js_config.as_ref().map(|js| (&js.douyin_ids, &js.extra_id_arrays));
+        if old_ids != new_ids {
+            fields.push("DouyinIDs");
+        }
+        if !fields.is_empty() {
+            changes.push((idx, fields));
+        }
+    }
+    changes
+}
+
+/// 加载并解析项目配置文件，按扩展名选择 JSON / YAML / TOML 解析
 fn load_config(path: &Path) -> anyhow::Result<ProjectConfig> {
-    let content = fs::read_to_string(path)?;
-    let config = serde_json::from_str(&content)?;
-    Ok(config)
+    Ok(read_config(path)?)
 }
 
 /// 加载并解析 JS 配置文件
 /// 使用正则表达式提取配置，因为 JS 文件不是标准的 JSON
-fn load_js_config(path: &Path) -> anyhow::Result<JsConfig> {
-    let content = fs::read_to_string(path)?;
+fn load_js_config(path: &Path, app_id_field_name: &str) -> anyhow::Result<JsConfig> {
+    // UTF-8 解码失败时回退到 GBK（中文 Windows 上的旧版打包产物）
+    let (content, encoding) = read_text_detect(path)?;
     
-    // 匹配 .appId="xxx" 或 .appId='xxx'
-    // 捕获组 1 为 appId 的值
-    let re_app_id = Regex::new(r#"\.appId\s*=\s*["']([^"']+)["']"#).unwrap();
-    // 匹配 .douyinIds=["xxx", "yyy"]
-    // 捕获组 1 为数组内部的字符串
-    let re_douyin_ids = Regex::new(r#"\.douyinIds\s*=\s*\[(.*?)\]"#).unwrap();
-
-    let app_id = re_app_id.captures(&content)
-        .and_then(|cap| cap.get(1))
-        .map(|m| m.as_str().to_string())
-        .unwrap_or_default();
+    // 匹配 .appId="xxx" 或 .appId='xxx'（字段名可在设置中修改）
+    let mut matches = Vec::new();
+    let app_id = match find_app_id(&content, app_id_field_name) {
+        Some(range) => {
+            matches.push(js_match(&content, app_id_field(app_id_field_name), range.clone()));
+            unescape_js_string(&content[range])
+        }
+        None => String::new(),
+    };
 
+    // 发现所有 `.xxxIds=[...]` 数组，douyinIds 单独存放，其余作为额外数组
     let mut douyin_ids = Vec::new();
-    if let Some(cap) = re_douyin_ids.captures(&content) {
-        if let Some(array_str) = cap.get(1) {
-            let inner = array_str.as_str();
-            // 分割数组内容并清理引号
-            for part in inner.split(',') {
-                let trimmed = part.trim();
-                let trim_matches: &[_] = &['"', '\''];
-                let id = trimmed.trim_matches(trim_matches);
-                if !id.is_empty() {
-                    douyin_ids.push(id.to_string());
-                }
-            }
+    let mut extra_id_arrays = Vec::new();
+    for (name, range) in find_id_arrays(&content) {
+        matches.push(js_match(&content, &name, range.clone()));
+        let ids = parse_id_list(&content[range]);
+        if name == DOUYIN_IDS_FIELD {
+            douyin_ids = ids;
+        } else {
+            extra_id_arrays.push(IdArray { name, ids });
         }
     }
 
@@ -145,66 +548,114 @@ fn load_js_config(path: &Path) -> anyhow::Result<JsConfig> {
         app_id,
         douyin_ids: douyin_ids.clone(),
         douyin_ids_str: douyin_ids.join(","), // 生成用于 UI 编辑的字符串
+        extra_id_arrays,
+        matches,
+        encoding,
     })
 }
 
-/// 保存 JS 配置文件
-/// 使用正则表达式进行替换，以保留原文件的格式和注释
-fn save_js_config(path: &Path, config: &JsConfig) -> anyhow::Result<()> {
-    let mut content = fs::read_to_string(path)?;
-    
-    // 替换 appId
-    // 查找模式：(.appId\s*=\s*["'])原始内容(["'])
-    // 替换为：$1新内容$2
-    let re_app_id_replace = Regex::new(r#"(\.appId\s*=\s*["'])[^"']+(["'])"#).unwrap();
-    content = re_app_id_replace.replace(&content, |caps: &regex::Captures| {
-        format!("{}{}{}", &caps[1], config.app_id, &caps[2])
-    }).to_string();
-
-    // 替换 douyinIds
-    // 首先从 douyin_ids_str 解析出 ID 列表，以支持用户在 UI 中的修改
-    let current_ids: Vec<String> = config.douyin_ids_str.split(',')
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect();
-
-    // 重新构建 JS 数组字符串： "id1","id2"
-    let ids_str = current_ids.iter()
-        .map(|id| format!(r#""{}""#, id))
-        .collect::<Vec<_>>()
-        .join(",");
-    
-    // 替换整个数组内容
-    let re_douyin_ids_replace = Regex::new(r#"(\.douyinIds\s*=\s*\[).*?(\])"#).unwrap();
-    content = re_douyin_ids_replace.replace(&content, |caps: &regex::Captures| {
-        format!("{}{}{}", &caps[1], ids_str, &caps[2])
-    }).to_string();
+/// 截取匹配位置前后各 `SNIPPET_CONTEXT_CHARS` 个字符作为预览
+/// 按字符而非字节截取，避免切断中文等多字节字符；换行替换为空格以便单行显示
+pub fn js_match(content: &str, field: &str, span: std::ops::Range<usize>) -> JsMatch {
+    let before_start = content[..span.start]
+        .char_indices()
+        .rev()
+        .take(SNIPPET_CONTEXT_CHARS)
+        .last()
+        .map_or(span.start, |(i, _)| i);
+    let after_end = content[span.end..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT_CHARS)
+        .map_or(content.len(), |(i, _)| span.end + i);
+    let flatten = |s: &str| s.replace(['\r', '\n'], " ");
 
-    fs::write(path, content)?;
-    Ok(())
+    JsMatch {
+        field: field.to_string(),
+        before: flatten(&content[before_start..span.start]),
+        matched: flatten(&content[span.clone()]),
+        after: flatten(&content[span.end..after_end]),
+        span,
+    }
 }
 
-/// 保存单个项目的所有配置（包括 JSON 和 JS）
-pub fn save_project_item(item: &ProjectItem) -> anyhow::Result<()> {
-    // 保存 JSON 配置文件
-    // 使用 pretty print 格式化输出，方便人类阅读
-    let content = serde_json::to_string_pretty(&item.config)?;
-    fs::write(&item.path, content)?;
-    
+/// 把界面中编辑的 JS 配置转换为 `config_core::apply` 的修改项
+fn js_changes(config: &JsConfig, options: &SaveOptions) -> JsChanges {
+    // appId 为空（未识别或用户未填写）时不写入，避免把文件中的值清空成 appId=""
+    let app_id = (!config.app_id.trim().is_empty()).then(|| config.app_id.clone());
+
+    // 从 douyin_ids_str 解析出 ID 列表，以支持用户在 UI 中的修改
+    // 重复的 ID 只保留第一次出现的位置，批量追加等操作产生的重复不会写入文件
+    let mut douyin_ids = split_douyin_ids(&config.douyin_ids_str);
+    dedup_ids(&mut douyin_ids);
+
+    // 文件中没有 douyinIds 数组且未填写 ID 时不写入；填写了 ID 时交给 `apply_js_changes` 报告字段不存在
+    let mut id_arrays = Vec::new();
+    if !douyin_ids.is_empty() || config.matches.iter().any(|m| m.field == DOUYIN_IDS_FIELD) {
+        id_arrays.push((DOUYIN_IDS_FIELD.to_string(), douyin_ids));
+    }
+    // 其他 ID 数组（如 rewardIds、bannerIds）
+    for array in &config.extra_id_arrays {
+        id_arrays.push((array.name.clone(), split_douyin_ids(&array.ids.join(","))));
+    }
+
+    JsChanges { app_id, id_arrays, format_assignments: options.format_assignments }
+}
+
+/// 保存 JS 配置文件
+/// 替换由 `config_core::apply::apply_js_changes` 完成，与 bytegame 编辑器共用同一实现，保留原文件的格式、注释、编码和换行
+/// 要修改的字段在文件中不存在时不写入任何内容，并按扫描时是否找到该字段给出不同的提示
+fn save_js_config(path: &Path, config: &JsConfig, options: &SaveOptions, backups: Option<&mut Backups>) -> anyhow::Result<()> {
+    match apply_js_changes(path, &options.app_id_field, &js_changes(config, options), backups) {
+        Ok(_) => Ok(()),
+        Err(ConfigError::JsPatternNotFound { path, field }) if config.matches.iter().any(|m| m.field == field) => {
+            anyhow::bail!("{} 中的 {} 字段在扫描后已不存在（文件可能被其他工具修改），请重新扫描", path.display(), field)
+        }
+        Err(ConfigError::JsPatternNotFound { path, field }) => {
+            anyhow::bail!("{} 中没有 {} 字段，无法写入新的值，请先在 JS 文件中添加该字段", path.display(), field)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 保存单个项目的所有配置（包括配置文件和 JS）
+/// 通过 `config_core::apply` 写入，只有内容实际变化的文件才会被写回；
+/// `backups` 为 `Some` 时，覆盖每个文件前先备份为 `.bak`（见 `config_core::backup`）
+pub fn save_project_item(item: &ProjectItem, options: &SaveOptions, mut backups: Option<&mut Backups>) -> anyhow::Result<()> {
+    // 项目配置文件按原文件的格式写回，其它字段保持不变
+    apply_to_config_file(
+        &item.path,
+        Some(&item.config.appid),
+        Some(&item.config.projectname),
+        backups.as_deref_mut(),
+    )?;
+
     // 如果存在 JS 配置，也一并保存
     if let (Some(js_path), Some(js_config)) = (&item.js_path, &item.js_config) {
-        save_js_config(js_path, js_config)?;
+        save_js_config(js_path, js_config, options, backups)?;
     }
-    
+
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::model::ProjectConfig;
+    use crate::model::{ProjectConfig, ProjectItem};
     use regex::Regex;
     use std::fs;
 
+    /// 同步扫描并收集全部项目
+    fn scan_all(root: &std::path::Path, options: &crate::scanner::ScanOptions, cache: &crate::scanner::ProbeCache) -> (Vec<ProjectItem>, crate::scanner::ScanReport) {
+        let mut projects = Vec::new();
+        let cancel = std::sync::atomic::AtomicBool::new(false);
+        let report = crate::scanner::scan_directory(root, options, cache, &cancel, |event| {
+            if let crate::scanner::ScanEvent::Project(item) = event {
+                projects.push(*item);
+            }
+            true
+        });
+        (projects, report)
+    }
+
     #[test]
     fn test_config_flatten() {
         let json_data = r#"{
@@ -261,6 +712,725 @@ mod tests {
         assert!(new_content_2.contains(r#"d.douyinIds=["new1","new2"]"#));
     }
 
+    #[test]
+    fn test_js_match_snippet() {
+        let prefix = "中".repeat(100);
+        let content = format!("{}\nd.appId=\"tt01\";{}", prefix, "x".repeat(10));
+        let start = content.find("tt01").unwrap();
+        let m = crate::scanner::js_match(&content, "appId", start..start + 4);
+
+        assert_eq!(m.matched, "tt01");
+        assert_eq!(m.before.chars().count(), crate::scanner::SNIPPET_CONTEXT_CHARS);
+        assert!(m.before.ends_with(" d.appId=\""));
+        assert_eq!(m.after, "\";xxxxxxxxxx");
+        assert_eq!(&content[m.span], "tt01");
+    }
+
+    #[test]
+    fn test_find_shared_js() {
+        use crate::model::ProjectItem;
+        use std::path::PathBuf;
+
+        let item = |dir: &str, js: Option<&str>| {
+            let config: ProjectConfig = serde_json::from_str("{}").unwrap();
+            ProjectItem::new(
+                PathBuf::from(dir).join("project.config.json"),
+                config,
+                js.map(PathBuf::from),
+                None,
+                Vec::new(),
+            )
+        };
+        let projects = vec![
+            item("a", Some("shared/application.js")),
+            item("b", Some("b/application.js")),
+            item("c", None),
+            item("d", Some("shared/application.js")),
+        ];
+
+        let groups = crate::scanner::find_shared_js(&projects);
+        assert_eq!(groups, vec![vec![0, 3]]);
+    }
+
+    #[test]
+    fn test_diff_disk_changes() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_disk_changes");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        for name in ["a", "b"] {
+            fs::create_dir_all(test_dir.join(name)).unwrap();
+            fs::write(test_dir.join(name).join("project.config.json"), r#"{"appid": "tt1", "projectname": "Game"}"#).unwrap();
+            fs::write(test_dir.join(name).join("application.js"), r#"d.appId="tt1",d.douyinIds=["x"]"#).unwrap();
+        }
+        let options = crate::scanner::ScanOptions::default();
+        let mut previous = scan_all(test_dir, &options, &crate::scanner::ProbeCache::default()).0;
+        // 未保存的编辑不算作磁盘上的变化
+        previous[0].config.projectname = "Edited".to_string();
+
+        fs::write(test_dir.join("b").join("project.config.json"), r#"{"appid": "tt1", "projectname": "Game 2"}"#).unwrap();
+        fs::write(test_dir.join("b").join("application.js"), r#"d.appId="tt1",d.douyinIds=["x","y"]"#).unwrap();
+        let current = scan_all(test_dir, &options, &crate::scanner::ProbeCache::default()).0;
+
+        let changes = crate::scanner::diff_disk_changes(&previous, &current);
+        assert_eq!(changes.len(), 1);
+        assert!(current[changes[0].0].path.starts_with(test_dir.join("b")));
+        assert_eq!(changes[0].1, vec!["项目名称", "DouyinIDs"]);
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_preview_name_patterns() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_preview_names");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir.join("game")).unwrap();
+        let game = test_dir.join("game");
+        fs::write(game.join("project.config.json"), r#"{"appid": "tt1"}"#).unwrap();
+        image::RgbImage::new(750, 10).save(game.join("wide.png")).unwrap();
+        image::RgbImage::new(320, 480).save(game.join("Cover.JPG")).unwrap();
+        image::RgbImage::new(64, 64).save(game.join("icon.png")).unwrap();
+
+        let names = |options: &crate::scanner::ScanOptions| {
+            let mut names: Vec<String> = scan_all(test_dir, options, &crate::scanner::ProbeCache::default()).0[0]
+                .image_paths
+                .iter()
+                .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            names
+        };
+
+        let mut options = crate::scanner::ScanOptions { lazy_images: false, ..Default::default() };
+        assert_eq!(names(&options), vec!["wide.png"]);
+        // 扫描时记录预览图尺寸
+        let project = &scan_all(test_dir, &options, &crate::scanner::ProbeCache::default()).0[0];
+        assert_eq!(project.image_sizes, vec![Some((750, 10))]);
+        // 两条规则可以同时生效
+        options.name_patterns = vec!["cover.*".to_string()];
+        assert_eq!(names(&options), vec!["Cover.JPG", "wide.png"]);
+        // 只按文件名匹配
+        options.match_width = false;
+        assert_eq!(names(&options), vec!["Cover.JPG"]);
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_lazy_image_probe() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_lazy_images");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        let game = test_dir.join("game");
+        fs::create_dir_all(&game).unwrap();
+        fs::write(game.join("project.config.json"), r#"{"appid": "tt1"}"#).unwrap();
+        image::RgbImage::new(750, 10).save(game.join("a.png")).unwrap();
+
+        // 延迟查找时扫描不读取任何图片
+        let options = crate::scanner::ScanOptions::default();
+        let (projects, report) = scan_all(test_dir, &options, &crate::scanner::ProbeCache::default());
+        assert!(!projects[0].images_probed);
+        assert!(projects[0].image_paths.is_empty());
+        assert!(report.probe_cache.entries.is_empty());
+
+        // 展开时查找，结果记入缓存
+        let mut cache = crate::scanner::ProbeCache::default();
+        let (paths, sizes) = crate::scanner::probe_preview_images(&game, &options, &mut cache);
+        assert_eq!(paths, vec![game.join("a.png")]);
+        assert_eq!(sizes, vec![Some((750, 10))]);
+        assert_eq!(cache.entries.len(), 1);
+
+        // 后台查找的结果与同步查找一致
+        let outcome = crate::scanner::spawn_probe(game.clone(), options.clone(), Default::default()).recv().unwrap();
+        assert_eq!(outcome.image_paths, paths);
+        assert_eq!(outcome.image_sizes, sizes);
+        assert_eq!(outcome.cache.entries.len(), 1);
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_scan_reuses_probe_cache() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_probe_cache");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        let game = test_dir.join("game");
+        fs::create_dir_all(&game).unwrap();
+        fs::write(game.join("project.config.json"), r#"{"appid": "tt1"}"#).unwrap();
+        image::RgbImage::new(750, 10).save(game.join("a.png")).unwrap();
+        image::RgbImage::new(64, 64).save(game.join("b.png")).unwrap();
+
+        let options = crate::scanner::ScanOptions { lazy_images: false, ..Default::default() };
+        let (first_projects, first) = scan_all(test_dir, &options, &crate::scanner::ProbeCache::default());
+        assert_eq!(first.reused_probes, 0);
+        assert_eq!(first.probe_cache.entries.len(), 2);
+
+        // 未变化的图片复用缓存，结果与首次扫描一致
+        let (second_projects, second) = scan_all(test_dir, &options, &first.probe_cache);
+        assert_eq!(second.reused_probes, 2);
+        assert_eq!(second_projects[0].image_paths, first_projects[0].image_paths);
+        assert_eq!(second_projects[0].image_sizes, vec![Some((750, 10))]);
+
+        // 文件变化后重新读取，已删除的图片从缓存中移除
+        image::RgbImage::new(750, 20).save(game.join("b.png")).unwrap();
+        fs::remove_file(game.join("a.png")).unwrap();
+        let (third_projects, third) = scan_all(test_dir, &options, &second.probe_cache);
+        assert_eq!(third.reused_probes, 0);
+        assert_eq!(third.probe_cache.entries.len(), 1);
+        assert_eq!(third_projects[0].image_sizes, vec![Some((750, 20))]);
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_spawn_scan_streams_projects() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_spawn_scan");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        for name in ["game_a", "game_b"] {
+            fs::create_dir_all(test_dir.join(name)).unwrap();
+            fs::write(test_dir.join(name).join("project.config.json"), r#"{"appid": "tt1"}"#).unwrap();
+        }
+
+        let rx = crate::scanner::spawn_scan(
+            test_dir.to_path_buf(),
+            crate::scanner::ScanOptions::default(),
+            crate::scanner::ProbeCache::default(),
+            Default::default(),
+        );
+        let mut found = 0;
+        let mut progress = Vec::new();
+        let mut cancelled = None;
+        for event in rx {
+            match event {
+                crate::scanner::ScanEvent::Progress(done, total) => progress.push((done, total)),
+                crate::scanner::ScanEvent::Project(_) => found += 1,
+                crate::scanner::ScanEvent::Done(report) => cancelled = Some(report.cancelled),
+            }
+        }
+        assert_eq!(found, 2);
+        assert_eq!(progress, vec![(1, 2), (2, 2)]);
+        assert_eq!(cancelled, Some(false));
+
+        // 已取消时不再处理任何项目
+        let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let rx = crate::scanner::spawn_scan(
+            test_dir.to_path_buf(),
+            crate::scanner::ScanOptions::default(),
+            crate::scanner::ProbeCache::default(),
+            cancel,
+        );
+        let events: Vec<_> = rx.into_iter().collect();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], crate::scanner::ScanEvent::Done(report) if report.cancelled));
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_scan_js_only() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_js_only");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir.join("with_js")).unwrap();
+        fs::create_dir_all(test_dir.join("json_only")).unwrap();
+        fs::write(test_dir.join("with_js").join("project.config.json"), r#"{"appid": "tt1"}"#).unwrap();
+        fs::write(test_dir.join("with_js").join("application.js"), r#"d.appId="tt1""#).unwrap();
+        fs::write(test_dir.join("json_only").join("project.config.json"), r#"{"appid": "tt2"}"#).unwrap();
+
+        let mut options = crate::scanner::ScanOptions::default();
+        assert_eq!(scan_all(test_dir, &options, &crate::scanner::ProbeCache::default()).0.len(), 2);
+
+        options.js_only = true;
+        let (projects, report) = scan_all(test_dir, &options, &crate::scanner::ProbeCache::default());
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].config.appid, "tt1");
+        assert_eq!(report.skipped_without_js, 1);
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_follow_links() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_follow_links");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir.join("root/games")).unwrap();
+        fs::create_dir_all(test_dir.join("outside/game")).unwrap();
+        fs::write(test_dir.join("outside/game/project.config.json"), r#"{"appid": "tt1"}"#).unwrap();
+        let abs = fs::canonicalize(test_dir).unwrap();
+        std::os::unix::fs::symlink(abs.join("outside/game"), test_dir.join("root/games/linked")).unwrap();
+        // 指向扫描根目录的循环链接
+        std::os::unix::fs::symlink(abs.join("root"), test_dir.join("root/games/loop")).unwrap();
+
+        let root = test_dir.join("root");
+        let mut options = crate::scanner::ScanOptions::default();
+        let (projects, report) = scan_all(&root, &options, &crate::scanner::ProbeCache::default());
+        assert!(projects.is_empty());
+        assert_eq!(report.symlinks.links, 2);
+
+        options.follow_links = true;
+        let (projects, report) = scan_all(&root, &options, &crate::scanner::ProbeCache::default());
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].config.appid, "tt1");
+        assert_eq!(report.symlinks.links, 1);
+        assert_eq!(report.symlinks.loops, 1);
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_yaml_and_toml_config_variants() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_config_variants");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir.join("yaml_game")).unwrap();
+        fs::create_dir_all(test_dir.join("toml_game")).unwrap();
+        let yaml_path = test_dir.join("yaml_game").join("project.config.yaml");
+        let toml_path = test_dir.join("toml_game").join("project.config.toml");
+        fs::write(&yaml_path, "appid: tt_yaml\nprojectname: YamlGame\ncompileType: game\n").unwrap();
+        fs::write(&toml_path, "appid = \"tt_toml\"\nprojectname = \"TomlGame\"\n\n[setting]\nes6 = true\n").unwrap();
+
+        let mut items = scan_all(test_dir, &crate::scanner::ScanOptions::default(), &crate::scanner::ProbeCache::default()).0;
+        items.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].config.appid, "tt_toml");
+        assert_eq!(items[1].config.projectname, "YamlGame");
+
+        for item in &mut items {
+            item.config.appid.push_str("_new");
+            crate::scanner::save_project_item(item, &crate::scanner::SaveOptions::default(), None).unwrap();
+        }
+
+        // 按原格式写回，未编辑的字段保留
+        let toml_content = fs::read_to_string(&toml_path).unwrap();
+        assert!(toml_content.contains("appid = \"tt_toml_new\""));
+        assert!(toml_content.contains("[setting]"));
+        let yaml_content = fs::read_to_string(&yaml_path).unwrap();
+        assert!(yaml_content.contains("appid: tt_yaml_new"));
+        assert!(yaml_content.contains("compileType: game"));
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_single_quoted_douyin_ids_round_trip() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_single_quote");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir).unwrap();
+        let js_path = test_dir.join("index.js");
+        fs::write(&js_path, r#"d.appId='tt1',d.douyinIds=['id1','id2'],d.rewardIds=["r1"]"#).unwrap();
+
+        let mut js = crate::scanner::load_js_config(&js_path, crate::scanner::DEFAULT_APP_ID_FIELD).unwrap();
+        assert_eq!(js.douyin_ids, vec!["id1", "id2"]);
+        js.douyin_ids_str = "id3,id4".to_string();
+        crate::scanner::save_js_config(&js_path, &js, &crate::scanner::SaveOptions::default(), None).unwrap();
+
+        // 单引号数组保持单引号，其他数组的双引号不受影响
+        assert_eq!(
+            fs::read_to_string(&js_path).unwrap(),
+            r#"d.appId='tt1',d.douyinIds=['id3','id4'],d.rewardIds=["r1"]"#
+        );
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_douyin_ids_keep_separator_spacing() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_id_spacing");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir).unwrap();
+        let js_path = test_dir.join("index.js");
+        let original = r#"d.appId="tt1",d.douyinIds=[ "a", "b" ],d.rewardIds=["r1" ,"r2"]"#;
+        fs::write(&js_path, original).unwrap();
+
+        // 未修改时重复保存，内容逐字节不变
+        let mut js = crate::scanner::load_js_config(&js_path, crate::scanner::DEFAULT_APP_ID_FIELD).unwrap();
+        let options = crate::scanner::SaveOptions::default();
+        crate::scanner::save_js_config(&js_path, &js, &options, None).unwrap();
+        assert_eq!(fs::read_to_string(&js_path).unwrap(), original);
+
+        // 修改后沿用原数组的 `, ` 分隔和首尾空格
+        js.douyin_ids_str = "a,b,c".to_string();
+        js.extra_id_arrays[0].ids.push("r3".to_string());
+        crate::scanner::save_js_config(&js_path, &js, &options, None).unwrap();
+        assert_eq!(
+            fs::read_to_string(&js_path).unwrap(),
+            r#"d.appId="tt1",d.douyinIds=[ "a", "b", "c" ],d.rewardIds=["r1" ,"r2" ,"r3"]"#
+        );
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_save_js_config_missing_field() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_js_missing_field");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir).unwrap();
+        let js_path = test_dir.join("index.js");
+        fs::write(&js_path, r#"d.appId="tt1";"#).unwrap();
+        let options = crate::scanner::SaveOptions::default();
+
+        // 文件中没有 douyinIds 数组、也没有填写 ID 时照常保存 AppID
+        let mut js = crate::scanner::load_js_config(&js_path, crate::scanner::DEFAULT_APP_ID_FIELD).unwrap();
+        js.app_id = "tt2".to_string();
+        crate::scanner::save_js_config(&js_path, &js, &options, None).unwrap();
+        assert_eq!(fs::read_to_string(&js_path).unwrap(), r#"d.appId="tt2";"#);
+
+        // 填写了 ID 时报告字段不存在，文件保持不变
+        js.app_id = "tt3".to_string();
+        js.douyin_ids_str = "a".to_string();
+        let err = crate::scanner::save_js_config(&js_path, &js, &options, None).unwrap_err();
+        assert!(err.to_string().contains("没有 douyinIds 字段"));
+        assert_eq!(fs::read_to_string(&js_path).unwrap(), r#"d.appId="tt2";"#);
+
+        // 扫描时存在的字段被外部删除后，提示重新扫描
+        fs::write(&js_path, r#"d.aid="tt2";"#).unwrap();
+        js.douyin_ids_str.clear();
+        let err = crate::scanner::save_js_config(&js_path, &js, &options, None).unwrap_err();
+        assert!(err.to_string().contains("请重新扫描"));
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_crlf_line_endings() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_crlf");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir).unwrap();
+        let js_path = test_dir.join("index.js");
+        fs::write(&js_path, "var d = {};\r\nd.appId = \"tt1\";\r\nd.douyinIds = [\r\n  \"a\",\r\n  \"b\"\r\n];\r\n").unwrap();
+
+        let mut js = crate::scanner::load_js_config(&js_path, crate::scanner::DEFAULT_APP_ID_FIELD).unwrap();
+        js.app_id = "tt2".to_string();
+        js.douyin_ids_str = "a,b,c".to_string();
+        crate::scanner::save_js_config(&js_path, &js, &crate::scanner::SaveOptions::default(), None).unwrap();
+
+        // 换行符保持 CRLF，末尾换行不变
+        assert_eq!(
+            fs::read_to_string(&js_path).unwrap(),
+            "var d = {};\r\nd.appId = \"tt2\";\r\nd.douyinIds = [\r\n  \"a\",\r\n  \"b\",\r\n  \"c\"\r\n];\r\n"
+        );
+
+        // 没有末尾换行的文件保存后也不会多出换行
+        fs::write(&js_path, "d.appId=\"tt1\",\r\nd.douyinIds=[\"a\"]").unwrap();
+        let mut js = crate::scanner::load_js_config(&js_path, crate::scanner::DEFAULT_APP_ID_FIELD).unwrap();
+        js.douyin_ids_str = "b".to_string();
+        crate::scanner::save_js_config(&js_path, &js, &crate::scanner::SaveOptions::default(), None).unwrap();
+        assert_eq!(fs::read_to_string(&js_path).unwrap(), "d.appId=\"tt1\",\r\nd.douyinIds=[\"b\"]");
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_save_dedups_douyin_ids() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_dedup_ids");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir).unwrap();
+        let js_path = test_dir.join("index.js");
+        fs::write(&js_path, "d.appId=\"tt1\",d.douyinIds=[\"1\"]").unwrap();
+
+        let mut js = crate::scanner::load_js_config(&js_path, crate::scanner::DEFAULT_APP_ID_FIELD).unwrap();
+        js.douyin_ids_str = "1,2,2,3,1".to_string();
+        crate::scanner::save_js_config(&js_path, &js, &crate::scanner::SaveOptions::default(), None).unwrap();
+        assert_eq!(fs::read_to_string(&js_path).unwrap(), "d.appId=\"tt1\",d.douyinIds=[\"1\",\"2\",\"3\"]");
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_utf8_bom() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_bom");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir.join("assets/main")).unwrap();
+        let config_path = test_dir.join("project.config.json");
+        fs::write(&config_path, "\u{FEFF}{\"appid\": \"tt1\", \"projectname\": \"游戏\"}").unwrap();
+        let js_path = test_dir.join("assets/main/index.js");
+        fs::write(&js_path, "\u{FEFF}d.appId=\"tt1\",d.douyinIds=[\"a\"]").unwrap();
+
+        // 以 BOM 开头的配置文件可以正常扫描
+        let mut items = scan_all(test_dir, &crate::scanner::ScanOptions::default(), &crate::scanner::ProbeCache::default()).0;
+        assert_eq!(items.len(), 1);
+        let item = &mut items[0];
+        assert_eq!(item.config.appid, "tt1");
+        assert_eq!(item.config.projectname, "游戏");
+        assert_eq!(item.js_config.as_ref().unwrap().app_id, "tt1");
+
+        // 保存后两个文件仍以 BOM 开头
+        item.config.appid = "tt2".to_string();
+        item.js_config.as_mut().unwrap().app_id = "tt2".to_string();
+        crate::scanner::save_project_item(item, &crate::scanner::SaveOptions::default(), None).unwrap();
+        let saved = fs::read_to_string(&config_path).unwrap();
+        assert!(saved.starts_with('\u{FEFF}'));
+        assert!(saved.contains("tt2"));
+        assert_eq!(fs::read_to_string(&js_path).unwrap(), "\u{FEFF}d.appId=\"tt2\",d.douyinIds=[\"a\"]");
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_gbk_js_round_trip() {
+        use config_core::encoding::TextEncoding;
+        use std::path::Path;
+        let test_dir = Path::new("test_output_gbk");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir.join("assets/main")).unwrap();
+        fs::write(test_dir.join("project.config.json"), r#"{"appid": "tt1", "projectname": "游戏"}"#).unwrap();
+        let js_path = test_dir.join("assets/main/index.js");
+        let gbk = TextEncoding::Gbk.encode(r#"var s="中文";d.appId="tt1",d.douyinIds=["抖音1"]"#).unwrap();
+        fs::write(&js_path, &gbk).unwrap();
+
+        // GBK 文件不再被跳过
+        let mut items = scan_all(test_dir, &crate::scanner::ScanOptions::default(), &crate::scanner::ProbeCache::default()).0;
+        let item = &mut items[0];
+        let js = item.js_config.as_mut().unwrap();
+        assert_eq!(js.encoding, TextEncoding::Gbk);
+        assert_eq!(js.douyin_ids, vec!["抖音1"]);
+
+        // 按 GBK 写回
+        js.douyin_ids_str = "抖音2".to_string();
+        crate::scanner::save_project_item(item, &crate::scanner::SaveOptions::default(), None).unwrap();
+        assert_eq!(
+            fs::read(&js_path).unwrap(),
+            TextEncoding::Gbk.encode(r#"var s="中文";d.appId="tt1",d.douyinIds=["抖音2"]"#).unwrap()
+        );
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_refresh_modified_after_revert() {
+        use crate::model::{JsConfig, ProjectItem};
+        use std::path::PathBuf;
+
+        let config: ProjectConfig = serde_json::from_str(r#"{"appid": "tt1", "projectname": "Game"}"#).unwrap();
+        let js = JsConfig { app_id: "tt1".to_string(), douyin_ids: vec!["id1".to_string()], ..Default::default() };
+        let mut item = ProjectItem::new(PathBuf::from("project.config.json"), config, None, Some(js), Vec::new());
+
+        item.config.appid = "tt2".to_string();
+        assert!(item.refresh_modified());
+        // 改回原值后不再标记为待保存
+        item.config.appid = "tt1".to_string();
+        assert!(!item.refresh_modified());
+
+        let js = item.js_config.as_mut().unwrap();
+        js.douyin_ids_str = "id1,id2".to_string();
+        js.sync_ids_from_str();
+        assert!(item.refresh_modified());
+        let js = item.js_config.as_mut().unwrap();
+        js.douyin_ids_str = "id1".to_string();
+        js.sync_ids_from_str();
+        assert!(!item.refresh_modified());
+    }
+
+    #[test]
+    fn test_unicode_round_trip() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_unicode");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir.join("assets/main")).unwrap();
+
+        let config_path = test_dir.join("project.config.json");
+        let js_path = test_dir.join("assets/main/index.js");
+        fs::write(&config_path, r#"{"appid": "tt旧的", "projectname": "旧游戏", "setting": {"es6": true}}"#).unwrap();
+        fs::write(&js_path, r#"var s="中文前缀";d.appId="tt旧的",d.douyinIds=["抖音1"];// 结尾注释"#).unwrap();
+
+        let mut items = scan_all(test_dir, &crate::scanner::ScanOptions::default(), &crate::scanner::ProbeCache::default()).0;
+        assert_eq!(items.len(), 1);
+        let item = &mut items[0];
+        assert_eq!(item.config.projectname, "旧游戏");
+        assert_eq!(item.js_config.as_ref().unwrap().app_id, "tt旧的");
+
+        // 中文、emoji 和组合字符（e + U+0301）
+        let new_id = "tt新🎮e\u{301}";
+        let new_name = "消消乐🍬 Café\u{301}";
+        item.config.appid = new_id.to_string();
+        item.config.projectname = new_name.to_string();
+        if let Some(js) = &mut item.js_config {
+            js.app_id = new_id.to_string();
+            js.douyin_ids_str = "抖音🎵,id2".to_string();
+        }
+        crate::scanner::save_project_item(item, &crate::scanner::SaveOptions::default(), None).unwrap();
+
+        let reloaded = scan_all(test_dir, &crate::scanner::ScanOptions::default(), &crate::scanner::ProbeCache::default()).0;
+        let reloaded = &reloaded[0];
+        assert_eq!(reloaded.config.appid, new_id);
+        assert_eq!(reloaded.config.projectname, new_name);
+        assert_eq!(reloaded.config.extra["setting"]["es6"], true);
+        let js = reloaded.js_config.as_ref().unwrap();
+        assert_eq!(js.app_id, new_id);
+        assert_eq!(js.douyin_ids, vec!["抖音🎵".to_string(), "id2".to_string()]);
+
+        // 多字节内容前后的其他代码保持不变
+        let saved_js = fs::read_to_string(&js_path).unwrap();
+        assert!(saved_js.starts_with(r#"var s="中文前缀";"#));
+        assert!(saved_js.ends_with("// 结尾注释"));
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_multiple_id_arrays() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_id_arrays");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir).unwrap();
+        let js_path = test_dir.join("index.js");
+        fs::write(
+            &js_path,
+            r#"d.appId="tt1",d.douyinIds=["a"],d.rewardIds=["r1","r]2"],d.bannerIds=["b1"],e._RF.pop()"#,
+        ).unwrap();
+
+        let mut js = crate::scanner::load_js_config(&js_path, crate::scanner::DEFAULT_APP_ID_FIELD).unwrap();
+        assert_eq!(js.douyin_ids, vec!["a".to_string()]);
+        assert_eq!(js.extra_id_arrays.len(), 2);
+        assert_eq!(js.extra_id_arrays[0].name, "rewardIds");
+        // 括号匹配会跳过字符串中的 ]
+        assert_eq!(js.extra_id_arrays[0].ids, vec!["r1".to_string(), "r]2".to_string()]);
+        assert_eq!(js.extra_id_arrays[1].name, "bannerIds");
+
+        js.extra_id_arrays[0].ids = vec!["r3".to_string(), "r4".to_string()];
+        crate::scanner::save_js_config(&js_path, &js, &crate::scanner::SaveOptions::default(), None).unwrap();
+
+        let saved = fs::read_to_string(&js_path).unwrap();
+        assert_eq!(
+            saved,
+            r#"d.appId="tt1",d.douyinIds=["a"],d.rewardIds=["r3","r4"],d.bannerIds=["b1"],e._RF.pop()"#
+        );
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_js_without_app_id() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_no_app_id");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir).unwrap();
+        let js_path = test_dir.join("index.js");
+        fs::write(&js_path, r#"d.appKey="k",d.douyinIds=["id1"]"#).unwrap();
+
+        let mut js = crate::scanner::load_js_config(&js_path, crate::scanner::DEFAULT_APP_ID_FIELD).unwrap();
+        assert!(js.app_id.is_empty());
+        assert_eq!(js.douyin_ids, vec!["id1"]);
+
+        // 未填写 appId 时只写入 douyinIds，不会凭空产生 appId=""
+        js.douyin_ids_str = "id2".to_string();
+        crate::scanner::save_js_config(&js_path, &js, &crate::scanner::SaveOptions::default(), None).unwrap();
+        assert_eq!(fs::read_to_string(&js_path).unwrap(), r#"d.appKey="k",d.douyinIds=["id2"]"#);
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_same_result_as_bytegame_editor() {
+        // 与 change_appconfig 中 test_same_result_as_douyin_editor 使用相同的输入和期望结果
+        use std::path::Path;
+        let test_dir = Path::new("test_output_shared_fixture");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir).unwrap();
+        let js_path = test_dir.join("index.js");
+        fs::write(&js_path, r#"var myappId="keep";d.appId='tt_old',d.douyinIds=["a", "b"],d.rewardIds=["r1"];"#).unwrap();
+
+        let mut js = crate::scanner::load_js_config(&js_path, crate::scanner::DEFAULT_APP_ID_FIELD).unwrap();
+        assert_eq!(js.app_id, "tt_old");
+        assert_eq!(js.douyin_ids, vec!["a", "b"]);
+
+        js.app_id = "tt_new".to_string();
+        js.douyin_ids_str = "c,d".to_string();
+        crate::scanner::save_js_config(&js_path, &js, &crate::scanner::SaveOptions::default(), None).unwrap();
+        assert_eq!(
+            fs::read_to_string(&js_path).unwrap(),
+            r#"var myappId="keep";d.appId='tt_new',d.douyinIds=["c", "d"],d.rewardIds=["r1"];"#
+        );
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_custom_app_id_field() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_app_id_field");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir).unwrap();
+        let js_path = test_dir.join("index.js");
+        fs::write(&js_path, r#"d.applicationId="tt_old",d.appId="other",d.douyinIds=["a"]"#).unwrap();
+
+        let mut js = crate::scanner::load_js_config(&js_path, "applicationId").unwrap();
+        assert_eq!(js.app_id, "tt_old");
+        assert_eq!(js.matches[0].field, "applicationId");
+
+        js.app_id = "tt_new".to_string();
+        let options = crate::scanner::SaveOptions {
+            app_id_field: "applicationId".to_string(),
+            ..Default::default()
+        };
+        crate::scanner::save_js_config(&js_path, &js, &options, None).unwrap();
+        // 只写入配置的字段，默认的 appId 不受影响
+        assert_eq!(
+            fs::read_to_string(&js_path).unwrap(),
+            r#"d.applicationId="tt_new",d.appId="other",d.douyinIds=["a"]"#
+        );
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_format_assignments() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_format_assignments");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir).unwrap();
+        let js_path = test_dir.join("index.js");
+        fs::write(&js_path, r#"a.b=1;d.appId  ="tt1",d.douyinIds=["id1"],d.rewardIds= ["r1"],d.other="x""#).unwrap();
+
+        let js = crate::scanner::load_js_config(&js_path, crate::scanner::DEFAULT_APP_ID_FIELD).unwrap();
+        let options = crate::scanner::SaveOptions { format_assignments: true, ..Default::default() };
+        crate::scanner::save_js_config(&js_path, &js, &options, None).unwrap();
+        // 只规范本工具写入的字段，其余内容保持原样
+        assert_eq!(
+            fs::read_to_string(&js_path).unwrap(),
+            r#"a.b=1;d.appId = "tt1",d.douyinIds = ["id1"],d.rewardIds = ["r1"],d.other="x""#
+        );
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_quoted_project_name() {
+        use std::path::Path;
+        let test_dir = Path::new("test_output_quoted_name");
+        if test_dir.exists() { fs::remove_dir_all(test_dir).unwrap(); }
+        fs::create_dir_all(test_dir).unwrap();
+        let config_path = test_dir.join("project.config.json");
+        // 之前的错误编辑把引号写进了名称本身
+        fs::write(&config_path, r#"{"appid": "tt1", "projectname": "\"Game\""}"#).unwrap();
+        let raw: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(raw["projectname"], "\"Game\"");
+
+        let mut items = scan_all(test_dir, &crate::scanner::ScanOptions::default(), &crate::scanner::ProbeCache::default()).0;
+        let item = &mut items[0];
+        assert_eq!(item.config.projectname, "Game");
+        assert!(item.is_modified);
+
+        let mut backups = config_core::backup::Backups::default();
+        crate::scanner::save_project_item(item, &crate::scanner::SaveOptions::default(), Some(&mut backups)).unwrap();
+        let saved: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(saved["projectname"], "Game");
+        // 覆盖前的原内容保存在 .bak 中
+        let bak: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_dir.join("project.config.json.bak")).unwrap()).unwrap();
+        assert_eq!(bak, raw);
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
     #[test]
     fn test_full_workflow() {
         use std::path::Path;
@@ -276,7 +1446,7 @@ mod tests {
         fs::write(&js_path, r#"d.appId="old_id",d.douyinIds=["id1"]"#).unwrap();
         
         // 1. Scan
-        let mut items = crate::scanner::scan_directory(test_dir);
+        let mut items = scan_all(test_dir, &crate::scanner::ScanOptions::default(), &crate::scanner::ProbeCache::default()).0;
         assert_eq!(items.len(), 1);
         let item = &mut items[0];
         
@@ -293,7 +1463,7 @@ mod tests {
         }
         
         // 3. Save
-        crate::scanner::save_project_item(item).unwrap();
+        crate::scanner::save_project_item(item, &crate::scanner::SaveOptions::default(), None).unwrap();
         
         // 4. Verify
         let saved_config = fs::read_to_string(&config_path).unwrap();
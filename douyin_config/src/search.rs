@@ -0,0 +1,85 @@
+use crate::model::{JsMatch, ProjectItem};
+use crate::scanner::js_match;
+use config_core::encoding::read_text_detect;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// 单个项目的搜索结果
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    /// 项目在列表中的下标
+    pub project: usize,
+    /// 关键字在 JS 文件中出现的次数
+    pub count: usize,
+    /// 第一次出现位置的上下文
+    pub first: JsMatch,
+}
+
+/// 在后台线程中搜索所有项目的 JS 文件内容
+///
+/// 只搜索有 `js_path` 的项目；文件在后台重新读取，因此搜索的是磁盘上的当前内容。
+/// 搜索完成后结果通过返回的 `Receiver` 发送一次。
+pub fn spawn_search(projects: &[ProjectItem], needle: &str) -> Receiver<Vec<SearchHit>> {
+    let targets: Vec<(usize, PathBuf)> = projects
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| item.js_path.clone().map(|p| (idx, p)))
+        .collect();
+    let needle = needle.to_string();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        // 接收端已被丢弃（例如重新扫描）时发送失败，直接忽略
+        let _ = tx.send(search_files(&targets, &needle));
+    });
+    rx
+}
+
+/// 逐个读取文件并统计关键字出现次数，无法读取的文件会被跳过
+fn search_files(targets: &[(usize, PathBuf)], needle: &str) -> Vec<SearchHit> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let mut hits = Vec::new();
+    for (project, path) in targets {
+        let Ok((content, _)) = read_text_detect(path) else {
+            eprintln!("搜索时无法读取 {:?}", path);
+            continue;
+        };
+        let mut positions = content.match_indices(needle).map(|(i, _)| i);
+        if let Some(start) = positions.next() {
+            hits.push(SearchHit {
+                project: *project,
+                count: 1 + positions.count(),
+                first: js_match(&content, needle, start..start + needle.len()),
+            });
+        }
+    }
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_search_files() {
+        let dir = std::env::temp_dir().join("douyin_config_search_test");
+        fs::create_dir_all(&dir).unwrap();
+        let a = dir.join("a.js");
+        let b = dir.join("b.js");
+        fs::write(&a, "sdkKey=\"OLD\";x.OLD=1").unwrap();
+        fs::write(&b, "sdkKey=\"NEW\"").unwrap();
+        let targets = vec![(0, a), (1, b), (2, dir.join("missing.js"))];
+
+        let hits = search_files(&targets, "OLD");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].project, 0);
+        assert_eq!(hits[0].count, 2);
+        assert_eq!(hits[0].first.before, "sdkKey=\"");
+        assert!(search_files(&targets, "").is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
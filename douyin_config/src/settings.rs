@@ -0,0 +1,175 @@
+use crate::ide::IdeLauncher;
+use crate::packager::{ZipCompression, ZipOptions};
+use crate::profile::ConfigProfile;
+use crate::scanner::ScanOptions;
+use config_core::i18n::Lang;
+use config_core::settings;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 设置文件所在的子目录名（`<系统配置目录>/douyin_config_editor/settings.toml`）
+const APP_DIR_NAME: &str = "douyin_config_editor";
+
+/// 默认允许的预览图宽高比
+pub const DEFAULT_ASPECT_RATIOS: &str = "3:4, 1:1";
+
+/// 默认窗口大小（逻辑像素，不含界面缩放）
+pub const DEFAULT_WINDOW_SIZE: [f32; 2] = [750.0, 800.0];
+
+/// 需要跨启动保留的设置
+/// 启动时读取一次、退出时写回；缺少的字段（如旧版本写入的文件）取默认值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// 窗口大小（逻辑像素，不含界面缩放）
+    pub window_size: [f32; 2],
+    /// 界面语言
+    pub lang: Lang,
+    /// 界面缩放比例
+    pub ui_scale: f32,
+    /// 上次选择的根目录，启动时只恢复路径，不自动扫描
+    pub last_root_path: Option<PathBuf>,
+    /// 选择根目录后是否立即扫描
+    pub scan_on_pick: bool,
+    /// 渠道目录层级（0 表示不按目录识别渠道）
+    pub channel_depth: usize,
+    /// 图片探测线程数
+    pub probe_threads: usize,
+    /// 扫描时是否跳过隐藏目录
+    pub skip_hidden: bool,
+    /// 是否只保留找到 JS 配置的项目
+    pub js_only: bool,
+    /// 扫描和打包时是否跟随符号链接
+    pub follow_links: bool,
+    /// 是否按宽度识别预览图
+    pub match_width: bool,
+    /// 是否等到展开项目时再查找预览图
+    pub lazy_images: bool,
+    /// 按文件名识别预览图的通配符模式
+    pub preview_name_patterns: Vec<String>,
+    /// 是否校验预览图宽高比
+    pub aspect_check: bool,
+    /// 允许的预览图宽高比（界面上输入的原文，如 `3:4, 1:1`）
+    pub aspect_ratios: String,
+    /// JS 中 AppID 的字段名
+    pub app_id_field: String,
+    /// 保存时是否规范 JS 赋值语句的空格
+    pub format_js_assignments: bool,
+    /// 保存前是否备份为 `.bak`
+    pub backup_before_save: bool,
+    /// 选中项目较多时批量操作是否需要确认
+    pub batch_confirm: bool,
+    /// 批量修改前是否预览影响的文件
+    pub batch_preview: bool,
+    /// 打包时是否跳过已有的 .zip 文件
+    pub skip_zip_files: bool,
+    /// 打包压缩方式
+    pub zip_compression: ZipCompression,
+    /// 打包时排除的文件和目录名模式
+    pub zip_exclude_patterns: Vec<String>,
+    /// 批量打包的输出目录
+    pub zip_output_dir: Option<PathBuf>,
+    /// 开发者工具启动配置
+    pub ide_launcher: IdeLauncher,
+    /// 已保存的配置模板
+    pub profiles: Vec<ConfigProfile>,
+}
+
+impl Default for Settings {
+    /// 与各选项自身的默认值保持一致
+    fn default() -> Self {
+        let scan = ScanOptions::default();
+        let zip = ZipOptions::default();
+        Self {
+            window_size: DEFAULT_WINDOW_SIZE,
+            lang: Lang::default(),
+            ui_scale: 1.0,
+            last_root_path: None,
+            scan_on_pick: true,
+            channel_depth: 0,
+            probe_threads: scan.probe_threads,
+            skip_hidden: scan.skip_hidden,
+            js_only: scan.js_only,
+            follow_links: scan.follow_links,
+            match_width: scan.match_width,
+            lazy_images: scan.lazy_images,
+            preview_name_patterns: scan.name_patterns,
+            aspect_check: false,
+            aspect_ratios: DEFAULT_ASPECT_RATIOS.to_string(),
+            app_id_field: scan.app_id_field,
+            format_js_assignments: false,
+            backup_before_save: true,
+            batch_confirm: true,
+            batch_preview: true,
+            skip_zip_files: zip.skip_zip_files,
+            zip_compression: zip.compression,
+            zip_exclude_patterns: zip.exclude_patterns,
+            zip_output_dir: None,
+            ide_launcher: IdeLauncher::default(),
+            profiles: Vec::new(),
+        }
+    }
+}
+
+impl Settings {
+    /// 从系统配置目录读取设置
+    /// 文件不存在或无法解析时使用默认值，不影响程序启动
+    pub fn load() -> Self {
+        let Some(path) = settings::settings_path(APP_DIR_NAME) else {
+            return Self::default();
+        };
+        settings::load(&path).unwrap_or_else(|e| {
+            eprintln!("Warning: {}，使用默认设置", e);
+            Self::default()
+        })
+    }
+
+    /// 写入系统配置目录，失败时只打印警告（程序正在退出，无处提示）
+    pub fn save(&self) {
+        let Some(path) = settings::settings_path(APP_DIR_NAME) else {
+            return;
+        };
+        if let Err(e) = settings::save(&path, self) {
+            eprintln!("Warning: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn test_settings_toml_round_trip() {
+        // 默认值与各选项的默认值一致
+        let defaults = Settings::default();
+        assert_eq!(defaults.probe_threads, ScanOptions::default().probe_threads);
+        assert_eq!(defaults.zip_exclude_patterns, ZipOptions::default().exclude_patterns);
+
+        let mut saved = Settings {
+            last_root_path: Some(PathBuf::from("D:/小游戏")),
+            lang: Lang::En,
+            zip_compression: ZipCompression::Stored,
+            ..Settings::default()
+        };
+        saved.profiles.push(ConfigProfile { name: "标准".to_string(), appid: "tt123".to_string(), ..Default::default() });
+        let dir = Path::new("test_output_settings");
+        let path = dir.join(settings::SETTINGS_FILE_NAME);
+        settings::save(&path, &saved).unwrap();
+        let loaded: Settings = settings::load(&path).unwrap();
+        assert_eq!(loaded.last_root_path, saved.last_root_path);
+        assert_eq!(loaded.lang, Lang::En);
+        assert_eq!(loaded.zip_compression, ZipCompression::Stored);
+        assert_eq!(loaded.profiles.len(), 1);
+        assert_eq!(loaded.profiles[0].appid, "tt123");
+
+        // 未设置的可选路径不写入文件，读回仍为 None
+        settings::save(&path, &Settings::default()).unwrap();
+        assert!(!fs::read_to_string(&path).unwrap().contains("zip_output_dir"));
+        assert_eq!(settings::load::<Settings>(&path).unwrap().zip_output_dir, None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// 预览图纹理缓存的像素总数上限
+/// 约为 40 张 750×1334 的原尺寸图片（RGBA 约 160MB），预览图按缩略图上传时可容纳数百张；
+/// 超出后释放最久未显示的纹理
+pub const MAX_TEXTURE_PIXELS: usize = 40 * 750 * 1334;
+
+/// 按最近显示时间从旧到新挑出需要释放的纹理，使剩余的像素总数不超过 `budget`
+/// `protect_since` 之后显示过的纹理（即当前帧正在显示的）不会被释放，即使因此仍超出上限
+///
+/// # Arguments
+/// * `entries` - 每个已加载的纹理：(项目下标, 图片路径, 最近显示时间, 像素数)
+///
+/// # Returns
+/// * `Vec<(usize, PathBuf)>` - 需要从对应项目的缓存中移除的 (项目下标, 图片路径)
+pub fn lru_evictions(
+    mut entries: Vec<(usize, PathBuf, Instant, usize)>,
+    budget: usize,
+    protect_since: Instant,
+) -> Vec<(usize, PathBuf)> {
+    let mut total: usize = entries.iter().map(|(_, _, _, pixels)| pixels).sum();
+    entries.sort_by_key(|(_, _, last_shown, _)| *last_shown);
+    let mut evicted = Vec::new();
+    for (idx, path, last_shown, pixels) in entries {
+        if total <= budget || last_shown >= protect_since {
+            break;
+        }
+        total -= pixels;
+        evicted.push((idx, path));
+    }
+    evicted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_lru_evictions() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let entries = vec![
+            (0, PathBuf::from("a.png"), at(3), 100),
+            (1, PathBuf::from("b.png"), at(1), 100),
+            (2, PathBuf::from("c.png"), at(2), 100),
+            (2, PathBuf::from("d.png"), at(10), 100),
+        ];
+
+        // 未超出上限时不释放
+        assert!(lru_evictions(entries.clone(), 400, at(10)).is_empty());
+        // 从最久未显示的开始释放，直到不超出上限
+        assert_eq!(
+            lru_evictions(entries.clone(), 200, at(10)),
+            vec![(1, PathBuf::from("b.png")), (2, PathBuf::from("c.png"))]
+        );
+        // 当前帧显示的纹理始终保留
+        assert_eq!(
+            lru_evictions(entries, 0, at(3)),
+            vec![(1, PathBuf::from("b.png")), (2, PathBuf::from("c.png"))]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::model::ProjectItem;
+use std::path::Path;
+
+/// 目录树中的单个节点
+/// 由扫描结果的路径构建，仅包含通往项目目录的文件夹
+#[derive(Debug, Default)]
+pub struct DirNode {
+    /// 文件夹名称（根节点为扫描根目录的名称）
+    pub name: String,
+    /// 子文件夹，按名称排序
+    pub children: Vec<DirNode>,
+    /// 如果该文件夹包含 project.config.json，记录对应项目在列表中的下标
+    pub project: Option<usize>,
+}
+
+impl DirNode {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// 查找或创建指定名称的子节点
+    fn child_mut(&mut self, name: &str) -> &mut DirNode {
+        let pos = match self.children.iter().position(|c| c.name == name) {
+            Some(pos) => pos,
+            None => {
+                self.children.push(DirNode::new(name.to_string()));
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[pos]
+    }
+
+    /// 递归按名称排序子节点，保证显示顺序稳定
+    fn sort(&mut self) {
+        self.children.sort_by(|a, b| a.name.cmp(&b.name));
+        for child in &mut self.children {
+            child.sort();
+        }
+    }
+}
+
+/// 根据扫描结果构建目录树
+///
+/// 每个项目的目录（project.config.json 所在文件夹）相对于 `root` 拆分为路径组件，
+/// 逐级插入树中。不在 `root` 之下的项目会直接挂在根节点上。
+pub fn build_tree(root: &Path, projects: &[ProjectItem]) -> DirNode {
+    let root_name = root
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| root.display().to_string());
+    let mut tree = DirNode::new(root_name);
+
+    for (idx, item) in projects.iter().enumerate() {
+        let Some(project_dir) = item.path.parent() else { continue };
+
+        let mut node = &mut tree;
+        match project_dir.strip_prefix(root) {
+            Ok(rel) => {
+                for component in rel.components() {
+                    node = node.child_mut(&component.as_os_str().to_string_lossy());
+                }
+            }
+            Err(_) => {
+                node = node.child_mut(&project_dir.display().to_string());
+            }
+        }
+        node.project = Some(idx);
+    }
+
+    tree.sort();
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ProjectConfig;
+    use std::path::PathBuf;
+
+    fn item(path: &str) -> ProjectItem {
+        let config = ProjectConfig {
+            appid: String::new(),
+            projectname: String::new(),
+            extra: serde_json::Value::Object(Default::default()),
+        };
+        ProjectItem::new(PathBuf::from(path), config, None, None, Vec::new())
+    }
+
+    #[test]
+    fn test_build_tree_nested() {
+        let root = Path::new("/games");
+        let projects = vec![
+            item("/games/b/game2/project.config.json"),
+            item("/games/a/game1/project.config.json"),
+            item("/games/project.config.json"),
+        ];
+
+        let tree = build_tree(root, &projects);
+        assert_eq!(tree.name, "games");
+        assert_eq!(tree.project, Some(2));
+        assert_eq!(tree.children.len(), 2);
+        assert_eq!(tree.children[0].name, "a");
+        assert_eq!(tree.children[0].project, None);
+        assert_eq!(tree.children[0].children[0].name, "game1");
+        assert_eq!(tree.children[0].children[0].project, Some(1));
+        assert_eq!(tree.children[1].children[0].project, Some(0));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::model::{ProjectItem, ProjectSnapshot};
+use std::collections::VecDeque;
+
+/// 撤销栈保留的最大步数，超出后丢弃最早的一步
+pub const MAX_UNDO_DEPTH: usize = 50;
+
+/// 一步可撤销的操作：操作名称及受影响项目在操作前（或撤销前）的配置
+pub struct UndoStep {
+    /// 操作名称，如"批量应用 AppID"
+    pub label: String,
+    /// (项目下标, 配置快照)
+    states: Vec<(usize, ProjectSnapshot)>,
+}
+
+impl UndoStep {
+    /// 在执行操作前记录指定项目的当前配置
+    pub fn capture(projects: &[ProjectItem], indices: impl IntoIterator<Item = usize>, label: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            states: indices
+                .into_iter()
+                .filter_map(|idx| projects.get(idx).map(|item| (idx, item.snapshot())))
+                .collect(),
+        }
+    }
+
+    /// 用已有的快照构造一步操作，如保存前各项目在磁盘上的配置
+    pub fn from_snapshots(label: &str, states: Vec<(usize, ProjectSnapshot)>) -> Self {
+        Self { label: label.to_string(), states }
+    }
+
+    /// 把记录的配置写回项目，返回记录着被覆盖的配置的反向操作
+    fn apply(self, projects: &mut [ProjectItem]) -> Self {
+        let mut reverse = Vec::with_capacity(self.states.len());
+        for (idx, snapshot) in self.states {
+            if let Some(item) = projects.get_mut(idx) {
+                reverse.push((idx, item.snapshot()));
+                item.restore(snapshot);
+            }
+        }
+        Self { label: self.label, states: reverse }
+    }
+}
+
+/// 批量修改的撤销 / 重做栈
+///
+/// 项目按列表下标记录，重新扫描后下标失效，需要调用 `clear`
+#[derive(Default)]
+pub struct UndoStack {
+    undo: VecDeque<UndoStep>,
+    redo: Vec<UndoStep>,
+}
+
+impl UndoStack {
+    /// 操作完成后调用：只保留配置实际发生变化的项目，没有任何变化时不记录
+    ///
+    /// # Returns
+    /// * `bool` - 是否记录了这一步
+    pub fn record(&mut self, mut step: UndoStep, projects: &[ProjectItem]) -> bool {
+        step.states.retain(|(idx, snapshot)| {
+            projects.get(*idx).is_some_and(|item| !item.matches_snapshot(snapshot))
+        });
+        if step.states.is_empty() {
+            return false;
+        }
+        self.undo.push_back(step);
+        if self.undo.len() > MAX_UNDO_DEPTH {
+            self.undo.pop_front();
+        }
+        self.redo.clear();
+        true
+    }
+
+    /// 撤销最近一步，返回该步的名称
+    pub fn undo(&mut self, projects: &mut [ProjectItem]) -> Option<String> {
+        let step = self.undo.pop_back()?.apply(projects);
+        let label = step.label.clone();
+        self.redo.push(step);
+        Some(label)
+    }
+
+    /// 重做最近撤销的一步，返回该步的名称
+    pub fn redo(&mut self, projects: &mut [ProjectItem]) -> Option<String> {
+        let step = self.redo.pop()?.apply(projects);
+        let label = step.label.clone();
+        self.undo.push_back(step);
+        Some(label)
+    }
+
+    /// 下一次撤销的操作名称
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo.back().map(|step| step.label.as_str())
+    }
+
+    /// 下一次重做的操作名称
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|step| step.label.as_str())
+    }
+
+    /// 清空撤销和重做记录
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ProjectConfig;
+    use std::path::PathBuf;
+
+    fn item(appid: &str) -> ProjectItem {
+        let config = ProjectConfig {
+            appid: appid.to_string(),
+            projectname: String::new(),
+            extra: serde_json::json!({}),
+        };
+        ProjectItem::new(PathBuf::from("project.config.json"), config, None, None, Vec::new())
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut projects = vec![item("tt1"), item("tt2")];
+        let mut stack = UndoStack::default();
+
+        let step = UndoStep::capture(&projects, 0..2, "批量应用 AppID");
+        projects[0].config.appid = "tt9".to_string();
+        projects[0].refresh_modified();
+        assert!(stack.record(step, &projects));
+        // 没有变化的操作不记录
+        let step = UndoStep::capture(&projects, 0..2, "批量应用项目名");
+        assert!(!stack.record(step, &projects));
+        assert_eq!(stack.undo_label(), Some("批量应用 AppID"));
+
+        assert_eq!(stack.undo(&mut projects).as_deref(), Some("批量应用 AppID"));
+        assert_eq!(projects[0].config.appid, "tt1");
+        assert!(!projects[0].is_modified);
+        assert!(stack.undo(&mut projects).is_none());
+
+        assert_eq!(stack.redo(&mut projects).as_deref(), Some("批量应用 AppID"));
+        assert_eq!(projects[0].config.appid, "tt9");
+        assert!(projects[0].is_modified);
+        assert!(stack.redo_label().is_none());
+
+        // 新的操作会清空重做记录，超出深度时丢弃最早的步骤
+        stack.undo(&mut projects);
+        for i in 0..MAX_UNDO_DEPTH + 5 {
+            let step = UndoStep::capture(&projects, [1], "批量应用 AppID");
+            projects[1].config.appid = format!("tt{}", 100 + i);
+            stack.record(step, &projects);
+        }
+        assert!(stack.redo_label().is_none());
+        let mut undone = 0;
+        while stack.undo(&mut projects).is_some() {
+            undone += 1;
+        }
+        assert_eq!(undone, MAX_UNDO_DEPTH);
+        assert_eq!(projects[1].config.appid, "tt104");
+    }
+
+    #[test]
+    fn test_undo_save() {
+        let mut projects = vec![item("tt1")];
+        let mut stack = UndoStack::default();
+        projects[0].config.appid = "tt2".to_string();
+        projects[0].refresh_modified();
+
+        // 保存后记录保存前的磁盘内容
+        let saved = vec![(0, projects[0].original.clone())];
+        projects[0].mark_saved();
+        assert!(stack.record(UndoStep::from_snapshots("保存", saved), &projects));
+
+        // 撤销后磁盘上的旧值成为未保存的修改
+        assert_eq!(stack.undo(&mut projects).as_deref(), Some("保存"));
+        assert_eq!(projects[0].config.appid, "tt1");
+        assert!(projects[0].is_modified);
+    }
+}